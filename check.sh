@@ -4,7 +4,7 @@
 
 set -e # stops on error
 
-MSRV="1.72.0" # sync with readme, Cargo.toml & .github/workflows/check.yml
+MSRV="1.82.0" # sync with readme, Cargo.toml & .github/workflows/check.yml
 RCMD="rustup -v run $MSRV"
 
 rustup override set $MSRV
@@ -17,5 +17,6 @@ cmd="$RCMD cargo cNu"; echo "no_std, no_alloc, unsafe\n$" $cmd; $cmd
 
 cmd="$RCMD cargo t"; echo "tests\n$" $cmd; $cmd
 cmd="$RCMD cargo tu"; echo "tests, unsafe\n$" $cmd; $cmd
+cmd="$RCMD cargo t --features=palette"; echo "tests, palette\n$" $cmd; $cmd
 
 cmd="cargo +nightly nd"; echo "docs\n$" $cmd; $cmd
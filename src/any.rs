@@ -0,0 +1,290 @@
+// acolor::any
+//
+//! Runtime dispatch over all the supported color types.
+//!
+//! Colors can also be converted by [`ColorSpace`] identifier with [`convert`].
+//
+// # TOC
+//
+// - ColorSpace
+// - AnyColor
+// - DynColor
+// - convert
+//
+
+use crate::{
+    math::f32_to_u8,
+    oklab::{Oklab32, Oklch32},
+    ops::LengthError,
+    srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8},
+};
+use core::fmt::Debug;
+
+// Defines `ColorSpace`, `AnyColor` and the impls that need to match each variant.
+macro_rules! any_color {
+    ($($type:ident[$n:literal]: $to_type:ident),+ $(,)?) => {
+        /// The discriminant of an [`AnyColor`], naming each supported color type.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ColorSpace {
+            $(
+                #[doc = concat!("[`", stringify!($type), "`]")]
+                $type,
+            )+
+        }
+
+        impl ColorSpace {
+            /// All the color spaces.
+            pub const ALL: &'static [ColorSpace] = &[ $( ColorSpace::$type, )+ ];
+
+            /// Returns the name of the color type.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $( ColorSpace::$type => stringify!($type), )+
+                }
+            }
+
+            /// Returns the number of components of the color type.
+            pub const fn components(self) -> usize {
+                match self {
+                    $( ColorSpace::$type => $n, )+
+                }
+            }
+        }
+
+        /// A color of any of the supported types, chosen at runtime.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum AnyColor {
+            $(
+                #[doc = concat!("[`", stringify!($type), "`]")]
+                $type($type),
+            )+
+        }
+
+        impl AnyColor {
+            /// Returns the color space of the wrapped color.
+            pub const fn space(&self) -> ColorSpace {
+                match self {
+                    $( AnyColor::$type(_) => ColorSpace::$type, )+
+                }
+            }
+
+            /// Returns a new color of the given `space` from its `components`, in field order.
+            ///
+            /// The components of the `u8` types are expected normalized to `0..=1`.
+            ///
+            /// # Errors
+            /// Returns [`LengthError`] if the number of `components` doesn't match the `space`.
+            pub fn from_components(
+                space: ColorSpace,
+                components: &[f32],
+            ) -> Result<AnyColor, LengthError> {
+                let expected = space.components();
+                if components.len() != expected {
+                    return Err(LengthError { expected, found: components.len() });
+                }
+                Ok(match space {
+                    $(
+                        ColorSpace::$type => {
+                            let mut c = $type::default();
+                            for (dst, src) in (&mut c).into_iter().zip(components) {
+                                *dst = ComponentFromF32::from_f32(*src);
+                            }
+                            AnyColor::$type(c)
+                        }
+                    )+
+                })
+            }
+        }
+
+        $(
+            impl From<$type> for AnyColor {
+                #[inline]
+                fn from(c: $type) -> AnyColor {
+                    AnyColor::$type(c)
+                }
+            }
+        )+
+
+        #[cfg(any(feature = "std", feature = "no_std"))]
+        #[cfg_attr(
+            feature = "nightly",
+            doc(cfg(any(feature = "std", feature = "no_std")))
+        )]
+        mod impl_any {
+            use super::*;
+            use crate::{math::u8_to_f32, Color};
+
+            // Widens the components of any color type into `f32`.
+            trait ComponentF32 {
+                fn f32(self) -> f32;
+            }
+            impl ComponentF32 for u8 {
+                fn f32(self) -> f32 {
+                    u8_to_f32(self)
+                }
+            }
+            impl ComponentF32 for f32 {
+                fn f32(self) -> f32 {
+                    self
+                }
+            }
+
+            impl AnyColor {
+                /// Converts the wrapped color into the given color `space`.
+                pub fn convert_to(&self, space: ColorSpace) -> AnyColor {
+                    match space {
+                        $( ColorSpace::$type => AnyColor::$type(self.$to_type()), )+
+                    }
+                }
+            }
+
+            /// The components are returned as `f32`, with `u8` ones normalized to `0..=1`.
+            impl Color for AnyColor {
+                type Component = f32;
+
+                fn color_red(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_red().f32(), )+ }
+                }
+                fn color_green(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_green().f32(), )+ }
+                }
+                fn color_blue(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_blue().f32(), )+ }
+                }
+                fn color_alpha(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_alpha().f32(), )+ }
+                }
+                fn color_luminosity_f32(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_luminosity_f32(), )+ }
+                }
+                fn color_hue_f32(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_hue_f32(), )+ }
+                }
+                fn color_chroma_f32(&self) -> f32 {
+                    match self { $( AnyColor::$type(c) => c.color_chroma_f32(), )+ }
+                }
+
+                fn color_to_array3(&self) -> [f32; 3] {
+                    match self { $( AnyColor::$type(c) => c.color_to_array3().map(ComponentF32::f32), )+ }
+                }
+                fn color_to_array4(&self) -> [f32; 4] {
+                    match self { $( AnyColor::$type(c) => c.color_to_array4().map(ComponentF32::f32), )+ }
+                }
+
+                fn color_to_srgb8(&self) -> Srgb8 {
+                    match self { $( AnyColor::$type(c) => c.color_to_srgb8(), )+ }
+                }
+                fn color_to_srgba8(&self) -> Srgba8 {
+                    match self { $( AnyColor::$type(c) => c.color_to_srgba8(), )+ }
+                }
+                fn color_to_srgb32(&self) -> Srgb32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_srgb32(), )+ }
+                }
+                fn color_to_srgba32(&self) -> Srgba32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_srgba32(), )+ }
+                }
+                fn color_to_linear_srgb32(&self) -> LinearSrgb32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_linear_srgb32(), )+ }
+                }
+                fn color_to_linear_srgba32(&self) -> LinearSrgba32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_linear_srgba32(), )+ }
+                }
+                fn color_to_oklab32(&self) -> Oklab32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_oklab32(), )+ }
+                }
+                fn color_to_oklch32(&self) -> Oklch32 {
+                    match self { $( AnyColor::$type(c) => c.color_to_oklch32(), )+ }
+                }
+            }
+        }
+    };
+}
+any_color![
+    Srgb8[3]: color_to_srgb8,
+    Srgba8[4]: color_to_srgba8,
+    Srgb32[3]: color_to_srgb32,
+    Srgba32[4]: color_to_srgba32,
+    LinearSrgb32[3]: color_to_linear_srgb32,
+    LinearSrgba32[4]: color_to_linear_srgba32,
+    Oklab32[3]: color_to_oklab32,
+    Oklch32[3]: color_to_oklch32,
+];
+
+// Narrows an `f32` into the components of any color type.
+trait ComponentFromF32 {
+    fn from_f32(f: f32) -> Self;
+}
+impl ComponentFromF32 for u8 {
+    fn from_f32(f: f32) -> u8 {
+        f32_to_u8(f)
+    }
+}
+impl ComponentFromF32 for f32 {
+    fn from_f32(f: f32) -> f32 {
+        f
+    }
+}
+
+/// Converts the `input` components from one color space into another,
+/// selected by their identifiers.
+///
+/// The components of the `u8` types are normalized to `0..=1`, both in the input
+/// and in the output. The output has 4 components, with alpha at max opacity
+/// if the target space has no alpha.
+///
+/// # Errors
+/// Returns [`LengthError`] if the number of `input` components doesn't match `from`.
+///
+/// # Example
+/// ```
+/// use acolor::any::{convert, ColorSpace};
+///
+/// let out = convert(&[1., 0., 0.], ColorSpace::Srgb32, ColorSpace::Srgba8).unwrap();
+/// assert_eq![out, [1., 0., 0., 1.]];
+/// ```
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn convert(input: &[f32], from: ColorSpace, to: ColorSpace) -> Result<[f32; 4], LengthError> {
+    use crate::Color;
+    let color = AnyColor::from_components(from, input)?;
+    Ok(color.convert_to(to).color_to_array4())
+}
+
+/// An object safe color trait, for using colors as trait objects.
+///
+/// The [`Color`][crate::Color] trait can't be made into an object,
+/// so this trait gives access to the same functionality through [`AnyColor`].
+///
+/// It's implemented for every type that converts into `AnyColor`.
+///
+/// # Example
+/// ```
+/// # #[cfg(all(any(feature = "std", feature = "no_std"), not(feature = "micromath")))] {
+/// use acolor::{any::DynColor, oklab::Oklch32, srgb::Srgb8, Color};
+///
+/// let colors: [&dyn DynColor; 2] = [&Srgb8::new(255, 0, 0), &Oklch32::new(0.7, 0.1, 120.)];
+/// for c in colors {
+///     let srgb = c.to_any_color().color_to_srgb8();
+///     assert_eq![srgb.to_oklch32().to_srgb8(), srgb];
+/// }
+/// # }
+/// ```
+pub trait DynColor: Debug {
+    /// Returns the color wrapped in an [`AnyColor`].
+    fn to_any_color(&self) -> AnyColor;
+
+    /// Returns the color space of the color.
+    fn color_space(&self) -> ColorSpace {
+        self.to_any_color().space()
+    }
+}
+
+impl<C: Into<AnyColor> + Copy + Debug> DynColor for C {
+    #[inline]
+    fn to_any_color(&self) -> AnyColor {
+        (*self).into()
+    }
+}
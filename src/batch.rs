@@ -0,0 +1,983 @@
+// acolor::batch
+//
+//! Bulk conversions over slices of colors.
+//!
+//! These functions avoid writing per-pixel loops in user code when converting
+//! image-sized buffers between color representations.
+//!
+//! The functions ending in `_with` take a [`ColorConfig`][crate::ColorConfig]
+//! to choose the transfer function used instead of the default sRGB curve.
+//
+// # TOC
+//
+// - generic:
+//   - convert_slice
+//   - convert_vec
+// - in place (over `[f32; N]` buffers):
+//   - srgb_to_linear_in_place[_with]
+//   - linear_to_srgb_in_place[_with]
+//   - linear_to_oklab_in_place
+//   - oklab_to_linear_in_place
+//   - oklab_to_oklch_in_place
+//   - oklch_to_oklab_in_place
+// - raw bytes (over interleaved `[u8]` buffers):
+//   - ByteLayout
+//   - map_bytes_in_place
+//   - map_bytes_oklch_in_place[_with]
+//   - bytes_to_linear_planes[_with]
+//   - linear_planes_to_bytes[_with]
+// - parallel (with the `rayon` feature):
+//   - PAR_CHUNK_SIZE
+//   - par_convert_slice
+//   - par_convert_vec
+// - iterators:
+//   - ColorItem
+//   - ColorIterator
+//   - Convert
+//   - MapColor
+// - averages:
+//   - average_color[_oklab]
+//   - weighted_average_color[_oklab]
+// - common pairs (allocating):
+//   - srgb8_slice_to_linear[_with]
+//   - linear_slice_to_srgb8[_with]
+//   - srgba8_slice_to_linear[_with]
+//   - linear_slice_to_srgba8[_with]
+//   - srgb8_slice_to_oklab[_with]
+//   - oklab_slice_to_srgb8[_with]
+//   - linear_slice_to_oklab
+//   - oklab_slice_to_linear
+//   - oklab_slice_to_oklch
+//   - oklch_slice_to_oklab
+//
+
+use crate::srgb::Srgba8;
+#[cfg(any(feature = "std", feature = "no_std"))]
+use crate::{
+    oklab::{Oklab32, Oklch32},
+    srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgb8},
+    Color, ColorConfig,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/* generic */
+
+/// Converts each color in `src` into the same position in `dst`.
+///
+/// # Panics
+/// Panics if both slices have different lengths.
+#[inline]
+pub fn convert_slice<A: Copy, B: From<A>>(src: &[A], dst: &mut [B]) {
+    assert_eq![
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    ];
+    for (s, d) in src.iter().zip(dst.iter_mut()) {
+        *d = B::from(*s);
+    }
+}
+
+/// Returns a new vector with each color in `src` converted.
+#[inline]
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub fn convert_vec<A: Copy, B: From<A>>(src: &[A]) -> Vec<B> {
+    src.iter().map(|c| B::from(*c)).collect()
+}
+
+/* in place */
+
+// Applies `f` in place to the first 3 components of each element of `buf`.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn map3_in_place<const N: usize>(buf: &mut [[f32; N]], f: impl Fn([f32; 3]) -> [f32; 3]) {
+    assert![N >= 3, "buffer elements must have at least 3 components"];
+    for c in buf.iter_mut() {
+        let [x, y, z] = f([c[0], c[1], c[2]]);
+        c[0] = x;
+        c[1] = y;
+        c[2] = z;
+    }
+}
+
+/// Converts in place a buffer of gamma encoded sRGB components into linear sRGB.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn srgb_to_linear_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    srgb_to_linear_in_place_with(buf, ColorConfig::DEFAULT);
+}
+
+/// Converts in place a buffer of encoded RGB components into linear RGB,
+/// using the transfer function of the given `config`.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn srgb_to_linear_in_place_with<const N: usize>(buf: &mut [[f32; N]], config: ColorConfig) {
+    map3_in_place(buf, |c| {
+        LinearSrgb32::to_array(Srgb32::from_array(c).to_linear_srgb32_with(config.transfer))
+    });
+}
+
+/// Converts in place a buffer of linear sRGB components into gamma encoded sRGB.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linear_to_srgb_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    linear_to_srgb_in_place_with(buf, ColorConfig::DEFAULT);
+}
+
+/// Converts in place a buffer of linear RGB components into encoded RGB,
+/// using the transfer function of the given `config`.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linear_to_srgb_in_place_with<const N: usize>(buf: &mut [[f32; N]], config: ColorConfig) {
+    map3_in_place(buf, |c| {
+        Srgb32::to_array(Srgb32::from_linear_srgb32_with(
+            LinearSrgb32::from_array(c),
+            config.transfer,
+        ))
+    });
+}
+
+/// Converts in place a buffer of linear sRGB components into Oklab.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linear_to_oklab_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    map3_in_place(buf, |c| {
+        Oklab32::to_array(LinearSrgb32::from_array(c).to_oklab32())
+    });
+}
+
+/// Converts in place a buffer of Oklab components into linear sRGB.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn oklab_to_linear_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    map3_in_place(buf, |c| {
+        LinearSrgb32::to_array(Oklab32::from_array(c).to_linear_srgb32())
+    });
+}
+
+/// Converts in place a buffer of Oklab components into Oklch.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn oklab_to_oklch_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    map3_in_place(buf, |c| {
+        Oklch32::to_array(Oklab32::from_array(c).to_oklch32())
+    });
+}
+
+/// Converts in place a buffer of Oklch components into Oklab.
+///
+/// Any component after the first 3 (e.g. alpha) is left untouched.
+///
+/// # Panics
+/// Panics if `N < 3`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn oklch_to_oklab_in_place<const N: usize>(buf: &mut [[f32; N]]) {
+    map3_in_place(buf, |c| {
+        Oklab32::to_array(Oklch32::from_array(c).to_oklab32())
+    });
+}
+
+/* raw bytes */
+
+/// The channel order of an interleaved 8-bit pixel buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteLayout {
+    /// `3` bytes per pixel, in red, green, blue order.
+    Rgb,
+    /// `4` bytes per pixel, in red, green, blue, alpha order.
+    Rgba,
+    /// `3` bytes per pixel, in blue, green, red order.
+    Bgr,
+    /// `4` bytes per pixel, in blue, green, red, alpha order.
+    Bgra,
+}
+
+impl ByteLayout {
+    /// Returns the number of bytes per pixel.
+    #[inline]
+    pub const fn channels(self) -> usize {
+        match self {
+            ByteLayout::Rgb | ByteLayout::Bgr => 3,
+            ByteLayout::Rgba | ByteLayout::Bgra => 4,
+        }
+    }
+
+    /// Returns `true` if the layout has an alpha channel.
+    #[inline]
+    pub const fn has_alpha(self) -> bool {
+        self.channels() == 4
+    }
+
+    /// Returns the byte offsets of the red, green and blue channels.
+    #[inline]
+    pub const fn offsets(self) -> [usize; 3] {
+        match self {
+            ByteLayout::Rgb | ByteLayout::Rgba => [0, 1, 2],
+            ByteLayout::Bgr | ByteLayout::Bgra => [2, 1, 0],
+        }
+    }
+
+    /* private helpers */
+
+    // Returns the number of pixels in `buf`, checking its length.
+    fn pixels(self, buf: &[u8]) -> usize {
+        assert![
+            buf.len() % self.channels() == 0,
+            "buffer length is not a multiple of the number of channels"
+        ];
+        buf.len() / self.channels()
+    }
+
+    // Reads the pixel starting at `px`. Layouts without alpha return max opacity.
+    #[inline]
+    fn read(self, px: &[u8]) -> Srgba8 {
+        let [r, g, b] = self.offsets();
+        let a = if self.has_alpha() { px[3] } else { u8::MAX };
+        Srgba8::new(px[r], px[g], px[b], a)
+    }
+
+    // Writes the pixel starting at `px`. Layouts without alpha ignore it.
+    #[inline]
+    fn write(self, px: &mut [u8], c: Srgba8) {
+        let [r, g, b] = self.offsets();
+        px[r] = c.r;
+        px[g] = c.g;
+        px[b] = c.b;
+        if self.has_alpha() {
+            px[3] = c.a;
+        }
+    }
+}
+
+/// Applies `f` in place to each pixel of an interleaved byte buffer.
+///
+/// For layouts without alpha `f` receives max opacity and its returned alpha is ignored.
+///
+/// # Panics
+/// Panics if the length of `buf` is not a multiple of the layout channels.
+pub fn map_bytes_in_place(buf: &mut [u8], layout: ByteLayout, mut f: impl FnMut(Srgba8) -> Srgba8) {
+    layout.pixels(buf);
+    for px in buf.chunks_exact_mut(layout.channels()) {
+        let c = f(layout.read(px));
+        layout.write(px, c);
+    }
+}
+
+/// Applies an [`Oklch32`] transform `f` in place to each pixel of an interleaved byte buffer.
+///
+/// The alpha channel, if any, is left untouched.
+///
+/// # Panics
+/// Panics if the length of `buf` is not a multiple of the layout channels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn map_bytes_oklch_in_place(
+    buf: &mut [u8],
+    layout: ByteLayout,
+    f: impl FnMut(Oklch32) -> Oklch32,
+) {
+    map_bytes_oklch_in_place_with(buf, layout, ColorConfig::DEFAULT, f);
+}
+
+/// Applies an [`Oklch32`] transform `f` in place to each pixel of an interleaved byte buffer,
+/// using the transfer function of the given `config`.
+///
+/// The alpha channel, if any, is left untouched.
+///
+/// # Panics
+/// Panics if the length of `buf` is not a multiple of the layout channels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn map_bytes_oklch_in_place_with(
+    buf: &mut [u8],
+    layout: ByteLayout,
+    config: ColorConfig,
+    mut f: impl FnMut(Oklch32) -> Oklch32,
+) {
+    let tf = config.transfer;
+    map_bytes_in_place(buf, layout, |c| {
+        let lch = f(c.to_srgb8().to_linear_srgb32_with(tf).to_oklch32());
+        encode_srgb8(lch.to_linear_srgb32(), config).to_srgba8(c.a)
+    });
+}
+
+/// Gamma decodes an interleaved byte buffer into separate linear `r`, `g`, `b` planes.
+///
+/// The alpha channel, if any, is ignored.
+///
+/// # Panics
+/// Panics if the length of `src` is not a multiple of the layout channels,
+/// or if any plane length differs from the number of pixels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn bytes_to_linear_planes(src: &[u8], layout: ByteLayout, planes: [&mut [f32]; 3]) {
+    bytes_to_linear_planes_with(src, layout, ColorConfig::DEFAULT, planes);
+}
+
+/// Decodes an interleaved byte buffer into separate linear `r`, `g`, `b` planes,
+/// using the transfer function of the given `config`.
+///
+/// The alpha channel, if any, is ignored.
+///
+/// # Panics
+/// Panics if the length of `src` is not a multiple of the layout channels,
+/// or if any plane length differs from the number of pixels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn bytes_to_linear_planes_with(
+    src: &[u8],
+    layout: ByteLayout,
+    config: ColorConfig,
+    planes: [&mut [f32]; 3],
+) {
+    let len = layout.pixels(src);
+    let [r, g, b] = planes;
+    assert![
+        r.len() == len && g.len() == len && b.len() == len,
+        "plane lengths differ from the number of pixels"
+    ];
+    for (i, px) in src.chunks_exact(layout.channels()).enumerate() {
+        let c = layout
+            .read(px)
+            .to_srgb8()
+            .to_linear_srgb32_with(config.transfer);
+        r[i] = c.r;
+        g[i] = c.g;
+        b[i] = c.b;
+    }
+}
+
+/// Gamma encodes separate linear `r`, `g`, `b` planes into an interleaved byte buffer.
+///
+/// The alpha channel, if any, is left untouched.
+///
+/// # Panics
+/// Panics if the length of `dst` is not a multiple of the layout channels,
+/// or if any plane length differs from the number of pixels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linear_planes_to_bytes(planes: [&[f32]; 3], layout: ByteLayout, dst: &mut [u8]) {
+    linear_planes_to_bytes_with(planes, layout, ColorConfig::DEFAULT, dst);
+}
+
+/// Encodes separate linear `r`, `g`, `b` planes into an interleaved byte buffer,
+/// using the transfer function of the given `config`.
+///
+/// The alpha channel, if any, is left untouched.
+///
+/// # Panics
+/// Panics if the length of `dst` is not a multiple of the layout channels,
+/// or if any plane length differs from the number of pixels.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linear_planes_to_bytes_with(
+    planes: [&[f32]; 3],
+    layout: ByteLayout,
+    config: ColorConfig,
+    dst: &mut [u8],
+) {
+    let len = layout.pixels(dst);
+    let [r, g, b] = planes;
+    assert![
+        r.len() == len && g.len() == len && b.len() == len,
+        "plane lengths differ from the number of pixels"
+    ];
+    for (i, px) in dst.chunks_exact_mut(layout.channels()).enumerate() {
+        let a = layout.read(px).a;
+        let c = LinearSrgb32::new(r[i], g[i], b[i]);
+        let c = encode_srgb8(c, config).to_srgba8(a);
+        layout.write(px, c);
+    }
+}
+
+// Encodes a linear color into `Srgb8`, with the transfer function and rounding of `config`.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn encode_srgb8(c: LinearSrgb32, config: ColorConfig) -> Srgb8 {
+    Srgb8::new(
+        config.encode8(c.r),
+        config.encode8(c.g),
+        config.encode8(c.b),
+    )
+}
+
+// Encodes a linear color into `Srgba8`, with the transfer function and rounding of `config`.
+#[inline]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn encode_srgba8(c: LinearSrgba32, config: ColorConfig) -> Srgba8 {
+    Srgba8::new(
+        config.encode8(c.r),
+        config.encode8(c.g),
+        config.encode8(c.b),
+        config.quantize8(c.a),
+    )
+}
+
+/* parallel */
+
+#[cfg(feature = "rayon")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "rayon")))]
+mod parallel {
+    use super::convert_slice;
+    use alloc::vec::Vec;
+    use rayon::prelude::*;
+
+    /// The number of colors converted by each parallel task.
+    pub const PAR_CHUNK_SIZE: usize = 16 * 1024;
+
+    /// Converts in parallel each color in `src` into the same position in `dst`.
+    ///
+    /// The work is split in chunks of [`PAR_CHUNK_SIZE`] colors.
+    ///
+    /// # Panics
+    /// Panics if both slices have different lengths.
+    pub fn par_convert_slice<A, B>(src: &[A], dst: &mut [B])
+    where
+        A: Copy + Sync,
+        B: From<A> + Send,
+    {
+        assert_eq![
+            src.len(),
+            dst.len(),
+            "source and destination lengths differ"
+        ];
+        src.par_chunks(PAR_CHUNK_SIZE)
+            .zip(dst.par_chunks_mut(PAR_CHUNK_SIZE))
+            .for_each(|(s, d)| convert_slice(s, d));
+    }
+
+    /// Returns a new vector with each color in `src` converted in parallel.
+    ///
+    /// The work is split in chunks of at least [`PAR_CHUNK_SIZE`] colors.
+    pub fn par_convert_vec<A, B>(src: &[A]) -> Vec<B>
+    where
+        A: Copy + Sync,
+        B: From<A> + Send,
+    {
+        src.par_iter()
+            .with_min_len(PAR_CHUNK_SIZE)
+            .map(|c| B::from(*c))
+            .collect()
+    }
+}
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+
+/* iterators */
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+mod iter {
+    use crate::{
+        any::AnyColor,
+        oklab::{Oklab32, Oklch32},
+        srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8},
+        Color, FromColor,
+    };
+    use core::{iter::FusedIterator, marker::PhantomData};
+
+    /// A color, or a reference to one, as yielded by an iterator.
+    ///
+    /// Allows the [`ColorIterator`] adapters over both `slice.iter()` and owned colors.
+    pub trait ColorItem {
+        /// The type of the color.
+        type Color: Color;
+
+        /// Returns the color.
+        fn into_item_color(self) -> Self::Color;
+    }
+
+    macro_rules! impl_color_item {
+        ($($type:ty),+) => { $(
+            impl ColorItem for $type {
+                type Color = $type;
+                #[inline]
+                fn into_item_color(self) -> $type { self }
+            }
+            impl ColorItem for &$type {
+                type Color = $type;
+                #[inline]
+                fn into_item_color(self) -> $type { *self }
+            }
+        )+ };
+    }
+    impl_color_item![
+        Srgb8,
+        Srgba8,
+        Srgb32,
+        Srgba32,
+        LinearSrgb32,
+        LinearSrgba32,
+        Oklab32,
+        Oklch32,
+        AnyColor
+    ];
+
+    /// Lazy color adapters for iterators of colors, without intermediate buffers.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{batch::ColorIterator, oklab::Oklab32, srgb::Srgb8};
+    ///
+    /// let pixels = [Srgb8::new(255, 0, 0), Srgb8::new(0, 0, 255)];
+    /// let lightest = pixels.iter().convert::<Oklab32>().map(|c| c.l).fold(0., f32::max);
+    /// assert![lightest > 0.6];
+    ///
+    /// let mut darker = pixels.iter().map_color(|c: Oklab32| Oklab32 { l: c.l * 0.5, ..c });
+    /// assert![darker.next().unwrap().l < 0.32];
+    /// ```
+    pub trait ColorIterator: Iterator + Sized {
+        /// Converts each color into `C`.
+        #[inline]
+        fn convert<C>(self) -> Convert<Self, C>
+        where
+            Self::Item: ColorItem,
+            C: FromColor<<Self::Item as ColorItem>::Color>,
+        {
+            Convert {
+                iter: self,
+                _color: PhantomData,
+            }
+        }
+
+        /// Converts each color into `A` and maps it with `f`.
+        #[inline]
+        fn map_color<A, B, F>(self, f: F) -> MapColor<Self, A, F>
+        where
+            Self::Item: ColorItem,
+            A: FromColor<<Self::Item as ColorItem>::Color>,
+            F: FnMut(A) -> B,
+        {
+            MapColor {
+                iter: self,
+                f,
+                _color: PhantomData,
+            }
+        }
+    }
+    impl<I: Iterator> ColorIterator for I {}
+
+    /// An iterator converting the colors of another one.
+    ///
+    /// Created by [`ColorIterator::convert`].
+    #[derive(Debug, Clone)]
+    pub struct Convert<I, C> {
+        iter: I,
+        _color: PhantomData<fn() -> C>,
+    }
+
+    impl<I, C> Iterator for Convert<I, C>
+    where
+        I: Iterator,
+        I::Item: ColorItem,
+        C: FromColor<<I::Item as ColorItem>::Color>,
+    {
+        type Item = C;
+        #[inline]
+        fn next(&mut self) -> Option<C> {
+            self.iter.next().map(|c| C::from_color(c.into_item_color()))
+        }
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+    impl<I, C> DoubleEndedIterator for Convert<I, C>
+    where
+        I: DoubleEndedIterator,
+        I::Item: ColorItem,
+        C: FromColor<<I::Item as ColorItem>::Color>,
+    {
+        #[inline]
+        fn next_back(&mut self) -> Option<C> {
+            self.iter
+                .next_back()
+                .map(|c| C::from_color(c.into_item_color()))
+        }
+    }
+    impl<I, C> ExactSizeIterator for Convert<I, C>
+    where
+        I: ExactSizeIterator,
+        I::Item: ColorItem,
+        C: FromColor<<I::Item as ColorItem>::Color>,
+    {
+    }
+    impl<I, C> FusedIterator for Convert<I, C>
+    where
+        I: FusedIterator,
+        I::Item: ColorItem,
+        C: FromColor<<I::Item as ColorItem>::Color>,
+    {
+    }
+
+    /// An iterator converting and mapping the colors of another one.
+    ///
+    /// Created by [`ColorIterator::map_color`].
+    #[derive(Debug, Clone)]
+    pub struct MapColor<I, A, F> {
+        iter: I,
+        f: F,
+        _color: PhantomData<fn() -> A>,
+    }
+
+    impl<I, A, B, F> Iterator for MapColor<I, A, F>
+    where
+        I: Iterator,
+        I::Item: ColorItem,
+        A: FromColor<<I::Item as ColorItem>::Color>,
+        F: FnMut(A) -> B,
+    {
+        type Item = B;
+        #[inline]
+        fn next(&mut self) -> Option<B> {
+            let c = self.iter.next()?;
+            Some((self.f)(A::from_color(c.into_item_color())))
+        }
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+    impl<I, A, B, F> DoubleEndedIterator for MapColor<I, A, F>
+    where
+        I: DoubleEndedIterator,
+        I::Item: ColorItem,
+        A: FromColor<<I::Item as ColorItem>::Color>,
+        F: FnMut(A) -> B,
+    {
+        #[inline]
+        fn next_back(&mut self) -> Option<B> {
+            let c = self.iter.next_back()?;
+            Some((self.f)(A::from_color(c.into_item_color())))
+        }
+    }
+    impl<I, A, B, F> ExactSizeIterator for MapColor<I, A, F>
+    where
+        I: ExactSizeIterator,
+        I::Item: ColorItem,
+        A: FromColor<<I::Item as ColorItem>::Color>,
+        F: FnMut(A) -> B,
+    {
+    }
+    impl<I, A, B, F> FusedIterator for MapColor<I, A, F>
+    where
+        I: FusedIterator,
+        I::Item: ColorItem,
+        A: FromColor<<I::Item as ColorItem>::Color>,
+        F: FnMut(A) -> B,
+    {
+    }
+}
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub use iter::*;
+
+/* averages */
+
+/// Returns the average of the `colors`, computed in linear light.
+///
+/// Averaging gamma encoded sRGB components gives results that are too dark,
+/// so the colors are first converted to linear sRGB. The components are weighted
+/// by their alpha, so that transparent colors don't contribute to the result.
+///
+/// Returns `None` if `colors` is empty.
+///
+/// # Example
+/// ```
+/// use acolor::{batch::average_color, srgb::Srgb8};
+///
+/// let avg = average_color(&[Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255)]).unwrap();
+/// assert_eq![avg.to_srgb8(), Srgb8::new(188, 188, 188)];
+/// ```
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn average_color<C: Color>(colors: &[C]) -> Option<LinearSrgba32> {
+    weighted_average_color_iter(colors, core::iter::repeat(1.))
+}
+
+/// Returns the average of the `colors`, computed in linear light and weighted by `weights`.
+///
+/// See [`average_color`]. Returns `None` if the weights add up to `0` or less.
+///
+/// # Panics
+/// Panics if both slices have different lengths.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn weighted_average_color<C: Color>(colors: &[C], weights: &[f32]) -> Option<LinearSrgba32> {
+    assert_eq![
+        colors.len(),
+        weights.len(),
+        "colors and weights lengths differ"
+    ];
+    weighted_average_color_iter(colors, weights.iter().copied())
+}
+
+/// Returns the average of the `colors`, computed in Oklab.
+///
+/// The components are weighted by their alpha,
+/// so that transparent colors don't contribute to the result.
+///
+/// Returns `None` if `colors` is empty.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn average_color_oklab<C: Color>(colors: &[C]) -> Option<Oklab32> {
+    weighted_average_color_oklab_iter(colors, core::iter::repeat(1.))
+}
+
+/// Returns the average of the `colors`, computed in Oklab and weighted by `weights`.
+///
+/// See [`average_color_oklab`]. Returns `None` if the weights add up to `0` or less.
+///
+/// # Panics
+/// Panics if both slices have different lengths.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn weighted_average_color_oklab<C: Color>(colors: &[C], weights: &[f32]) -> Option<Oklab32> {
+    assert_eq![
+        colors.len(),
+        weights.len(),
+        "colors and weights lengths differ"
+    ];
+    weighted_average_color_oklab_iter(colors, weights.iter().copied())
+}
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn weighted_average_color_iter<C: Color>(
+    colors: &[C],
+    weights: impl Iterator<Item = f32>,
+) -> Option<LinearSrgba32> {
+    let ([r, g, b], a) = weighted_average(colors, weights, |c| {
+        let c = c.color_to_linear_srgba32();
+        ([c.r, c.g, c.b], c.a)
+    })?;
+    Some(LinearSrgba32::new(r, g, b, a))
+}
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn weighted_average_color_oklab_iter<C: Color>(
+    colors: &[C],
+    weights: impl Iterator<Item = f32>,
+) -> Option<Oklab32> {
+    let (lab, _) = weighted_average(colors, weights, |c| {
+        (
+            Oklab32::to_array(c.color_to_oklab32()),
+            c.color_to_srgba32().a,
+        )
+    })?;
+    Some(Oklab32::from_array(lab))
+}
+
+// Returns the weighted average of the components returned by `f`, also weighted
+// by the alpha returned along them, and the weighted average of the alpha.
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn weighted_average<C>(
+    colors: &[C],
+    weights: impl Iterator<Item = f32>,
+    f: impl Fn(&C) -> ([f32; 3], f32),
+) -> Option<([f32; 3], f32)> {
+    let (mut sum, mut alpha, mut total) = ([0.; 3], 0., 0.);
+    for (c, w) in colors.iter().zip(weights) {
+        let (components, a) = f(c);
+        for (s, x) in sum.iter_mut().zip(components) {
+            *s += x * a * w;
+        }
+        alpha += a * w;
+        total += w;
+    }
+    if total <= 0. {
+        return None;
+    }
+    let components = if alpha > 0. {
+        sum.map(|s| s / alpha)
+    } else {
+        [0.; 3]
+    };
+    Some((components, alpha / total))
+}
+
+/* common pairs */
+
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(all(feature = "alloc", any(feature = "std", feature = "no_std"))))
+)]
+mod pairs {
+    use super::*;
+
+    /// Converts a slice of [`Srgb8`] into a vector of [`LinearSrgb32`].
+    pub fn srgb8_slice_to_linear(src: &[Srgb8]) -> Vec<LinearSrgb32> {
+        srgb8_slice_to_linear_with(src, ColorConfig::DEFAULT)
+    }
+    /// Converts a slice of [`LinearSrgb32`] into a vector of [`Srgb8`].
+    pub fn linear_slice_to_srgb8(src: &[LinearSrgb32]) -> Vec<Srgb8> {
+        linear_slice_to_srgb8_with(src, ColorConfig::DEFAULT)
+    }
+
+    /// Converts a slice of [`Srgba8`] into a vector of [`LinearSrgba32`].
+    pub fn srgba8_slice_to_linear(src: &[Srgba8]) -> Vec<LinearSrgba32> {
+        srgba8_slice_to_linear_with(src, ColorConfig::DEFAULT)
+    }
+    /// Converts a slice of [`LinearSrgba32`] into a vector of [`Srgba8`].
+    pub fn linear_slice_to_srgba8(src: &[LinearSrgba32]) -> Vec<Srgba8> {
+        linear_slice_to_srgba8_with(src, ColorConfig::DEFAULT)
+    }
+
+    /// Converts a slice of [`Srgb8`] into a vector of [`Oklab32`].
+    pub fn srgb8_slice_to_oklab(src: &[Srgb8]) -> Vec<Oklab32> {
+        srgb8_slice_to_oklab_with(src, ColorConfig::DEFAULT)
+    }
+    /// Converts a slice of [`Oklab32`] into a vector of [`Srgb8`].
+    pub fn oklab_slice_to_srgb8(src: &[Oklab32]) -> Vec<Srgb8> {
+        oklab_slice_to_srgb8_with(src, ColorConfig::DEFAULT)
+    }
+
+    /// Converts a slice of [`Srgb8`] into a vector of [`LinearSrgb32`], using `config`.
+    pub fn srgb8_slice_to_linear_with(src: &[Srgb8], config: ColorConfig) -> Vec<LinearSrgb32> {
+        src.iter()
+            .map(|c| c.to_linear_srgb32_with(config.transfer))
+            .collect()
+    }
+    /// Converts a slice of [`LinearSrgb32`] into a vector of [`Srgb8`], using `config`.
+    pub fn linear_slice_to_srgb8_with(src: &[LinearSrgb32], config: ColorConfig) -> Vec<Srgb8> {
+        src.iter().map(|c| encode_srgb8(*c, config)).collect()
+    }
+
+    /// Converts a slice of [`Srgba8`] into a vector of [`LinearSrgba32`], using `config`.
+    pub fn srgba8_slice_to_linear_with(src: &[Srgba8], config: ColorConfig) -> Vec<LinearSrgba32> {
+        src.iter()
+            .map(|c| c.to_linear_srgba32_with(config.transfer))
+            .collect()
+    }
+    /// Converts a slice of [`LinearSrgba32`] into a vector of [`Srgba8`], using `config`.
+    pub fn linear_slice_to_srgba8_with(src: &[LinearSrgba32], config: ColorConfig) -> Vec<Srgba8> {
+        src.iter().map(|c| encode_srgba8(*c, config)).collect()
+    }
+
+    /// Converts a slice of [`Srgb8`] into a vector of [`Oklab32`], using `config`.
+    pub fn srgb8_slice_to_oklab_with(src: &[Srgb8], config: ColorConfig) -> Vec<Oklab32> {
+        src.iter()
+            .map(|c| c.to_linear_srgb32_with(config.transfer).to_oklab32())
+            .collect()
+    }
+    /// Converts a slice of [`Oklab32`] into a vector of [`Srgb8`], using `config`.
+    pub fn oklab_slice_to_srgb8_with(src: &[Oklab32], config: ColorConfig) -> Vec<Srgb8> {
+        src.iter()
+            .map(|c| encode_srgb8(c.to_linear_srgb32(), config))
+            .collect()
+    }
+
+    /// Converts a slice of [`LinearSrgb32`] into a vector of [`Oklab32`].
+    pub fn linear_slice_to_oklab(src: &[LinearSrgb32]) -> Vec<Oklab32> {
+        src.iter().map(|c| c.to_oklab32()).collect()
+    }
+    /// Converts a slice of [`Oklab32`] into a vector of [`LinearSrgb32`].
+    pub fn oklab_slice_to_linear(src: &[Oklab32]) -> Vec<LinearSrgb32> {
+        src.iter().map(|c| c.to_linear_srgb32()).collect()
+    }
+
+    /// Converts a slice of [`Oklab32`] into a vector of [`Oklch32`].
+    pub fn oklab_slice_to_oklch(src: &[Oklab32]) -> Vec<Oklch32> {
+        src.iter().map(|c| c.to_oklch32()).collect()
+    }
+    /// Converts a slice of [`Oklch32`] into a vector of [`Oklab32`].
+    pub fn oklch_slice_to_oklab(src: &[Oklch32]) -> Vec<Oklab32> {
+        src.iter().map(|c| c.to_oklab32()).collect()
+    }
+}
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+pub use pairs::*;
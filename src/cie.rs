@@ -0,0 +1,186 @@
+// acolor::cie
+//
+//! CIE XYZ and chromaticity coordinates.
+//!
+//! # Example
+//! ```
+//! use acolor::{cie::ColorChromaticity, srgb::Srgb8};
+//!
+//! let [x, y] = Srgb8::new(255, 0, 0).chromaticity_xy();
+//! assert![(x - 0.64).abs() < 1e-4 && (y - 0.33).abs() < 1e-4];
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/CIE_1931_color_space>
+//! - <https://en.wikipedia.org/wiki/CIELUV>
+//! - <https://jcgt.org/published/0002/02/01/>
+//
+// # TOC
+//
+// - ColorChromaticity
+// - xy_to_uv
+// - uv_to_xy
+// - Observer
+// - spectral:
+//   - wavelength_to_xy
+//   - spectrum_to_xyz
+//
+
+use crate::{
+    math::{exp, ln, mat_mul_vec},
+    parse::matrices::SRGB_TO_XYZ_D65,
+    Color,
+};
+
+/// The `xy` chromaticity of the D65 white point.
+pub const D65_XY: [f32; 2] = [0.3127, 0.3290];
+
+/// CIE XYZ and chromaticity coordinates of any [`Color`].
+///
+/// The coordinates are relative to the D65 white point of sRGB,
+/// with the CIE 1931 2° standard observer that defines it.
+pub trait ColorChromaticity: Color {
+    /// Returns the CIE XYZ components, where `Y` is the relative luminance in `0..=1`.
+    ///
+    /// The color will be converted to [`LinearSrgb32`][crate::srgb::LinearSrgb32]
+    /// for the operation, if needed.
+    fn color_to_xyz(&self) -> [f32; 3] {
+        let c = self.color_to_linear_srgb32();
+        mat_mul_vec(SRGB_TO_XYZ_D65, [c.r, c.g, c.b])
+    }
+
+    /// Returns the CIE 1931 `xy` chromaticity coordinates.
+    ///
+    /// Black, which has no chromaticity, returns the D65 white point.
+    fn chromaticity_xy(&self) -> [f32; 2] {
+        let [x, y, z] = self.color_to_xyz();
+        let sum = x + y + z;
+        if sum > 0. {
+            [x / sum, y / sum]
+        } else {
+            D65_XY
+        }
+    }
+
+    /// Returns the CIE 1976 `u′v′` chromaticity coordinates,
+    /// where distances are more perceptually uniform than in `xy`.
+    ///
+    /// Black, which has no chromaticity, returns the D65 white point.
+    fn chromaticity_uv(&self) -> [f32; 2] {
+        xy_to_uv(self.chromaticity_xy())
+    }
+}
+
+impl<C: Color> ColorChromaticity for C {}
+
+/// Converts CIE 1931 `xy` chromaticity coordinates into CIE 1976 `u′v′` ones.
+pub fn xy_to_uv(xy: [f32; 2]) -> [f32; 2] {
+    let [x, y] = xy;
+    let d = -2. * x + 12. * y + 3.;
+    [4. * x / d, 9. * y / d]
+}
+
+/// Converts CIE 1976 `u′v′` chromaticity coordinates into CIE 1931 `xy` ones.
+pub fn uv_to_xy(uv: [f32; 2]) -> [f32; 2] {
+    let [u, v] = uv;
+    let d = 6. * u - 16. * v + 12.;
+    [9. * u / d, 4. * v / d]
+}
+
+/* observer */
+
+/// A CIE standard colorimetric observer, given by its color matching functions.
+///
+/// It only affects the conversions from spectral data, since the RGB color spaces
+/// are defined for the 2° observer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Observer {
+    /// The CIE 1931 2° standard observer, for small fields of view.
+    #[default]
+    Cie1931,
+    /// The CIE 1964 10° supplementary standard observer, for large fields of view,
+    /// used by many industrial color measurements.
+    Cie1964,
+}
+
+impl Observer {
+    /// Returns the `[x̄, ȳ, z̄]` color matching functions at a `wavelength` in nanometers.
+    ///
+    /// Uses the analytic approximations of Wyman, Sloan & Shirley (2013), which are
+    /// within a few hundredths of the tabulated functions, and closer for the 2° observer.
+    pub fn color_matching(self, wavelength: f32) -> [f32; 3] {
+        let l = wavelength;
+        match self {
+            Observer::Cie1931 => {
+                // a gaussian with different deviations at each side of the mean
+                let g = |mean: f32, below: f32, above: f32| {
+                    let t = (l - mean) / if l < mean { below } else { above };
+                    exp(-0.5 * t * t)
+                };
+                [
+                    1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7)
+                        - 0.065 * g(501.1, 20.4, 26.2),
+                    0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1),
+                    1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8),
+                ]
+            }
+            Observer::Cie1964 => {
+                // a gaussian over the logarithm of the wavelength
+                let g = |scale: f32, arg: f32| {
+                    if arg > 0. {
+                        let t = ln(arg);
+                        exp(-scale * t * t)
+                    } else {
+                        0.
+                    }
+                };
+                let t = (l - 556.1) / 46.14;
+                [
+                    0.398 * g(1250., (l + 570.1) / 1014.) + 1.132 * g(234., (1338. - l) / 743.5),
+                    1.011 * exp(-0.5 * t * t),
+                    2.060 * g(32., (l - 265.8) / 180.4),
+                ]
+            }
+        }
+    }
+}
+
+/* spectral */
+
+/// Returns the `xy` chromaticity of a monochromatic light of the given `wavelength`
+/// in nanometers, as seen by an `observer`.
+///
+/// Tracing the visible wavelengths draws the spectral locus of the CIE diagrams.
+pub fn wavelength_to_xy(wavelength: f32, observer: Observer) -> [f32; 2] {
+    let [x, y, z] = observer.color_matching(wavelength);
+    let sum = x + y + z;
+    if sum > 0. {
+        [x / sum, y / sum]
+    } else {
+        D65_XY
+    }
+}
+
+/// Returns the CIE XYZ components of a spectrum, as seen by an `observer`.
+///
+/// The `samples` are taken every `step` nanometers from the `start` wavelength,
+/// for example every 10 nm from 380 nm. The result is normalized so that
+/// a constant spectrum of `1`, like a perfect reflector under an equal energy
+/// illuminant, has `Y = 1`.
+///
+/// Returns zeros if there are no samples.
+pub fn spectrum_to_xyz(samples: &[f32], start: f32, step: f32, observer: Observer) -> [f32; 3] {
+    let (mut xyz, mut norm) = ([0.; 3], 0.);
+    for (i, s) in samples.iter().enumerate() {
+        let cmf = observer.color_matching(start + step * i as f32);
+        for k in 0..3 {
+            xyz[k] += s * cmf[k];
+        }
+        norm += cmf[1];
+    }
+    if norm > 0. {
+        xyz.map(|c| c / norm)
+    } else {
+        [0.; 3]
+    }
+}
@@ -0,0 +1,148 @@
+// acolor::codegen
+//
+//! Shader source code generation.
+//!
+//! Emits colors and palettes as constant declarations for GLSL, WGSL or HLSL,
+//! so that colors defined in Rust can be kept in sync with the shaders.
+//!
+//! # Example
+//! ```
+//! use acolor::{codegen::{ShaderCodegen, ShaderEncoding, ShaderLang}, srgb::Srgb8};
+//!
+//! let gen = ShaderCodegen::new(ShaderLang::Glsl, ShaderEncoding::Srgb);
+//! let brand = Srgb8::new(255, 0, 0);
+//! assert_eq![gen.color("BRAND", brand), "const vec3 BRAND = vec3(1.0, 0.0, 0.0);"];
+//!
+//! let gen = ShaderCodegen::new(ShaderLang::Wgsl, ShaderEncoding::Linear).with_alpha();
+//! assert_eq![
+//!     gen.color("BRAND", brand),
+//!     "const BRAND: vec4<f32> = vec4<f32>(1.0, 0.0, 0.0, 1.0);"
+//! ];
+//! ```
+//
+// # TOC
+//
+// - ShaderLang
+// - ShaderEncoding
+// - ShaderCodegen
+//
+
+use crate::{image::Palette, Color};
+use alloc::{format, string::String, vec::Vec};
+
+/// A shading language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderLang {
+    /// The OpenGL Shading Language.
+    Glsl,
+    /// The WebGPU Shading Language.
+    Wgsl,
+    /// The High-Level Shading Language of Direct3D.
+    Hlsl,
+}
+
+/// The encoding of the emitted color components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ShaderEncoding {
+    /// Linear sRGB, for shaders that blend and light colors before
+    /// writing into an sRGB framebuffer.
+    #[default]
+    Linear,
+    /// Gamma-encoded sRGB, as the colors are usually written down.
+    Srgb,
+}
+
+/// A generator of shader constant declarations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderCodegen {
+    /// The shading language.
+    pub lang: ShaderLang,
+    /// The encoding of the components.
+    pub encoding: ShaderEncoding,
+    /// Whether to emit 4 component vectors, including the alpha.
+    pub alpha: bool,
+}
+
+impl ShaderCodegen {
+    /// Returns a new generator of 3 component vectors.
+    pub const fn new(lang: ShaderLang, encoding: ShaderEncoding) -> ShaderCodegen {
+        ShaderCodegen {
+            lang,
+            encoding,
+            alpha: false,
+        }
+    }
+
+    /// Returns the same generator, emitting 4 component vectors with the alpha.
+    pub const fn with_alpha(self) -> ShaderCodegen {
+        ShaderCodegen {
+            alpha: true,
+            ..self
+        }
+    }
+
+    /// Returns the declaration of a constant vector called `name` with a `color`.
+    pub fn color<C: Color>(&self, name: &str, color: C) -> String {
+        let (ty, value) = (self.vector_type(), self.vector(color));
+        match self.lang {
+            ShaderLang::Glsl => format!["const {} {} = {};", ty, name, value],
+            ShaderLang::Wgsl => format!["const {}: {} = {};", name, ty, value],
+            ShaderLang::Hlsl => format!["static const {} {} = {};", ty, name, value],
+        }
+    }
+
+    /// Returns the declaration of a constant array of vectors called `name` with the `colors`.
+    pub fn colors<C: Color>(&self, name: &str, colors: &[C]) -> String {
+        let (ty, n) = (self.vector_type(), colors.len());
+        let values: Vec<String> = colors.iter().map(|c| self.vector(*c)).collect();
+        let values = values.join(", ");
+        match self.lang {
+            ShaderLang::Glsl => format!["const {0} {1}[{2}] = {0}[{2}]({3});", ty, name, n, values],
+            ShaderLang::Wgsl => {
+                format![
+                    "const {1}: array<{0}, {2}> = array<{0}, {2}>({3});",
+                    ty, name, n, values
+                ]
+            }
+            ShaderLang::Hlsl => format!["static const {} {}[{}] = {{ {} }};", ty, name, n, values],
+        }
+    }
+
+    /// Returns the declaration of a constant array of vectors called `name`
+    /// with the colors of a `palette`.
+    pub fn palette(&self, name: &str, palette: &Palette) -> String {
+        self.colors(name, palette.colors())
+    }
+
+    // Returns the name of the vector type.
+    fn vector_type(&self) -> &'static str {
+        match (self.lang, self.alpha) {
+            (ShaderLang::Glsl, false) => "vec3",
+            (ShaderLang::Glsl, true) => "vec4",
+            (ShaderLang::Wgsl, false) => "vec3<f32>",
+            (ShaderLang::Wgsl, true) => "vec4<f32>",
+            (ShaderLang::Hlsl, false) => "float3",
+            (ShaderLang::Hlsl, true) => "float4",
+        }
+    }
+
+    // Returns the constructor expression of a vector with the color components.
+    fn vector<C: Color>(&self, color: C) -> String {
+        let [r, g, b, a] = match self.encoding {
+            ShaderEncoding::Linear => {
+                let c = color.color_to_linear_srgba32();
+                [c.r, c.g, c.b, c.a]
+            }
+            ShaderEncoding::Srgb => {
+                let c = color.color_to_srgba32();
+                [c.r, c.g, c.b, c.a]
+            }
+        };
+        // the debug format always has a decimal point or an exponent
+        if self.alpha {
+            format!["{}({:?}, {:?}, {:?}, {:?})", self.vector_type(), r, g, b, a]
+        } else {
+            format!["{}({:?}, {:?}, {:?})", self.vector_type(), r, g, b]
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // acolor::color
 //
-//!
+//! The common color trait, and the generic conversion traits.
 //
 
 use crate::{
@@ -11,38 +11,79 @@ use core::fmt::Debug;
 
 /// Common color trait for all supported color formats.
 pub trait Color: Copy + Debug + PartialEq {
-    /// The type of the inner color components.
-    type Inner;
+    /// The type of the color components.
+    type Component;
 
     /// Returns the red luminosity.
-    fn color_red(&self) -> Self::Inner;
+    fn color_red(&self) -> Self::Component;
     /// Returns the green luminosity.
-    fn color_green(&self) -> Self::Inner;
+    fn color_green(&self) -> Self::Component;
     /// Returns the blue luminosity.
-    fn color_blue(&self) -> Self::Inner;
+    fn color_blue(&self) -> Self::Component;
     /// Returns the alpha luminosity.
-    fn color_alpha(&self) -> Self::Inner;
+    fn color_alpha(&self) -> Self::Component;
 
-    /// Returns the overall luminosity.
+    /* float accessors */
+
+    /// Returns the perceived lightness, as the [`Oklab32`] `l`, in `0..=1`.
     ///
-    /// If the color is not in [`Oklab32`] or [`Oklch32`] format,
-    /// it will be converted to `Oklab32` for the operation.
-    fn color_luminosity(&self) -> Self::Inner;
+    /// The color will be converted to `Oklab32` for the operation, if needed.
+    fn color_luminosity_f32(&self) -> f32 {
+        self.color_to_oklab32().l
+    }
+
+    /// Returns the hue angle in degrees, as the [`Oklch32`] `h`.
+    ///
+    /// The color will be converted to `Oklch32` for the operation, if needed.
+    fn color_hue_f32(&self) -> f32 {
+        self.color_to_oklch32().h
+    }
 
-    /// Returns the hue.
+    /// Returns the chroma, as the [`Oklch32`] `c`.
     ///
-    /// If the color is not in [`Oklch32`] format
-    /// it will be converted to it for the operation.
-    fn color_hue(&self) -> Self::Inner;
+    /// The color will be converted to `Oklch32` for the operation, if needed.
+    fn color_chroma_f32(&self) -> f32 {
+        self.color_to_oklch32().c
+    }
+
+    /// Returns the saturation, as in HSV, of the gamma encoded components, in `0..=1`.
+    ///
+    /// The color will be converted to [`Srgb32`] for the operation, if needed.
+    fn color_saturation_f32(&self) -> f32 {
+        let Srgb32 { r, g, b } = self.color_to_srgb32();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        if max > 0. {
+            (max - min) / max
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the whiteness, as in HWB, of the gamma encoded components, in `0..=1`.
+    ///
+    /// The color will be converted to [`Srgb32`] for the operation, if needed.
+    fn color_whiteness_f32(&self) -> f32 {
+        let Srgb32 { r, g, b } = self.color_to_srgb32();
+        r.min(g).min(b)
+    }
+
+    /// Returns the blackness, as in HWB, of the gamma encoded components, in `0..=1`.
+    ///
+    /// The color will be converted to [`Srgb32`] for the operation, if needed.
+    fn color_blackness_f32(&self) -> f32 {
+        let Srgb32 { r, g, b } = self.color_to_srgb32();
+        1. - r.max(g).max(b)
+    }
 
     /* conversions */
 
     /// Returns the 3 components, without alpha.
-    fn color_to_array3(&self) -> [Self::Inner; 3];
+    fn color_to_array3(&self) -> [Self::Component; 3];
     /// Returns the 4 components, with alpha.
     ///
     /// If the specific color type has no alpha the maximum value is returned.
-    fn color_to_array4(&self) -> [Self::Inner; 4];
+    fn color_to_array4(&self) -> [Self::Component; 4];
 
     /// Conversion to `Srgb8`.
     fn color_to_srgb8(&self) -> Srgb8;
@@ -62,6 +103,73 @@ pub trait Color: Copy + Debug + PartialEq {
     fn color_to_oklch32(&self) -> Oklch32;
 }
 
+/// Conversion from another color.
+///
+/// Implemented for every supported type from any [`Color`],
+/// which allows writing functions generic over the input color:
+/// ```
+/// # #[cfg(any(feature = "std", feature = "no_std"))] {
+/// use acolor::{oklab::Oklab32, srgb::Srgb8, IntoColor};
+///
+/// fn lighten<C: IntoColor<Oklab32>>(c: C) -> Oklab32 {
+///     let mut lab = c.into_color();
+///     lab.l += 0.1;
+///     lab
+/// }
+/// assert![lighten(Srgb8::new(20, 40, 60)).l > Srgb8::new(20, 40, 60).to_oklab32().l];
+/// # }
+/// ```
+pub trait FromColor<T>: Sized {
+    /// Converts `color` into `Self`.
+    fn from_color(color: T) -> Self;
+}
+
+/// Conversion into another color.
+///
+/// It's the reciprocal of [`FromColor`], which should be implemented instead.
+pub trait IntoColor<T>: Sized {
+    /// Converts `self` into `T`.
+    fn into_color(self) -> T;
+}
+
+impl<T, U: FromColor<T>> IntoColor<U> for T {
+    #[inline]
+    fn into_color(self) -> U {
+        U::from_color(self)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+mod impl_from_color {
+    use super::{
+        Color, FromColor, LinearSrgb32, LinearSrgba32, Oklab32, Oklch32, Srgb32, Srgb8, Srgba32,
+        Srgba8,
+    };
+
+    macro_rules! impl_from_color {
+        ($($type:ty: $fn:ident),+) => { $(
+            impl<C: Color> FromColor<C> for $type {
+                #[inline]
+                fn from_color(color: C) -> $type { color.$fn() }
+            }
+        )+ };
+    }
+    impl_from_color![
+        Srgb8: color_to_srgb8,
+        Srgba8: color_to_srgba8,
+        Srgb32: color_to_srgb32,
+        Srgba32: color_to_srgba32,
+        LinearSrgb32: color_to_linear_srgb32,
+        LinearSrgba32: color_to_linear_srgba32,
+        Oklab32: color_to_oklab32,
+        Oklch32: color_to_oklch32
+    ];
+}
+
 #[cfg(any(feature = "std", feature = "no_std"))]
 #[cfg_attr(
     feature = "nightly",
@@ -71,24 +179,21 @@ mod impl_color {
     use super::{
         Color, LinearSrgb32, LinearSrgba32, Oklab32, Oklch32, Srgb32, Srgb8, Srgba32, Srgba8,
     };
-    use iunorm::Unorm8;
 
     #[rustfmt::skip]
     impl Color for Srgb8 {
-        type Inner = u8;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, u8::MAX] }
+        type Component = u8;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, u8::MAX] }
 
         /// Returns the gamma corrected red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the gamma corrected green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the gamma corrected blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the maximum opacity alpha.
-        fn color_alpha(&self) -> Self::Inner { u8::MAX }
-        fn color_luminosity(&self) -> Self::Inner { Unorm8::from_f32(self.to_oklab32().l).0 }
-        fn color_hue(&self) -> Self::Inner { Unorm8::from_f32(self.to_oklch32().h).0 }
+        fn color_alpha(&self) -> Self::Component { u8::MAX }
 
         /// no-op.
         fn color_to_srgb8(&self) -> Srgb8 { *self }
@@ -102,20 +207,18 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for Srgba8 {
-        type Inner = u8;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, self.a] }
+        type Component = u8;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, self.a] }
 
         /// Returns the gamma corrected red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the gamma corrected green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the gamma corrected blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the linear alpha.
-        fn color_alpha(&self) -> Self::Inner { self.a }
-        fn color_luminosity(&self) -> Self::Inner { Unorm8::from_f32(self.to_oklab32().l).0 }
-        fn color_hue(&self) -> Self::Inner { Unorm8::from_f32(self.to_oklch32().h).0 }
+        fn color_alpha(&self) -> Self::Component { self.a }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         /// no-op.
@@ -129,20 +232,18 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for Srgb32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, 1.] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, 1.] }
 
         /// Returns the gamma corrected red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the gamma corrected green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the gamma corrected blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the maximum opacity alpha.
-        fn color_alpha(&self) -> Self::Inner { 1. }
-        fn color_luminosity(&self) -> Self::Inner { self.to_oklab32().l }
-        fn color_hue(&self) -> Self::Inner { self.to_oklch32().h }
+        fn color_alpha(&self) -> Self::Component { 1. }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8(u8::MAX) }
@@ -156,20 +257,18 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for Srgba32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, self.a] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, self.a] }
 
         /// Returns the gamma corrected red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the gamma corrected green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the gamma corrected blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the linear alpha.
-        fn color_alpha(&self) -> Self::Inner { self.a }
-        fn color_luminosity(&self) -> Self::Inner { self.to_oklab32().l }
-        fn color_hue(&self) -> Self::Inner { self.to_oklch32().h }
+        fn color_alpha(&self) -> Self::Component { self.a }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8() }
@@ -183,20 +282,18 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for LinearSrgb32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, 1.] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, 1.] }
 
         /// Returns the linear red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the linear green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the linear blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the maximum opacity alpha.
-        fn color_alpha(&self) -> Self::Inner { 1. }
-        fn color_luminosity(&self) -> Self::Inner { self.to_oklab32().l }
-        fn color_hue(&self) -> Self::Inner { self.to_oklch32().h }
+        fn color_alpha(&self) -> Self::Component { 1. }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8(u8::MAX) }
@@ -210,20 +307,18 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for LinearSrgba32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.r, self.g, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.r, self.g, self.b, self.a] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.r, self.g, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.r, self.g, self.b, self.a] }
 
         /// Returns the linear red luminosity.
-        fn color_red(&self) -> Self::Inner { self.r }
+        fn color_red(&self) -> Self::Component { self.r }
         /// Returns the linear green luminosity.
-        fn color_green(&self) -> Self::Inner { self.g }
+        fn color_green(&self) -> Self::Component { self.g }
         /// Returns the linear blue luminosity.
-        fn color_blue(&self) -> Self::Inner { self.b }
+        fn color_blue(&self) -> Self::Component { self.b }
         /// Returns the linear alpha.
-        fn color_alpha(&self) -> Self::Inner { self.a }
-        fn color_luminosity(&self) -> Self::Inner { self.to_oklab32().l }
-        fn color_hue(&self) -> Self::Inner { self.to_oklch32().h }
+        fn color_alpha(&self) -> Self::Component { self.a }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8() }
@@ -237,20 +332,19 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for Oklab32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.l, self.a, self.b] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.l, self.a, self.b, 1.] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.l, self.a, self.b] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.l, self.a, self.b, 1.] }
 
         /// Returns the linear red luminosity, after converting to [`LinearSrgb32`].
-        fn color_red(&self) -> Self::Inner { self.color_to_linear_srgb32().r }
+        fn color_red(&self) -> Self::Component { self.color_to_linear_srgb32().r }
         /// Returns the linear green luminosity, after converting to [`LinearSrgb32`].
-        fn color_green(&self) -> Self::Inner { self.color_to_linear_srgb32().g }
+        fn color_green(&self) -> Self::Component { self.color_to_linear_srgb32().g }
         /// Returns the linear blue luminosity, after converting to [`LinearSrgb32`].
-        fn color_blue(&self) -> Self::Inner { self.color_to_linear_srgb32().b }
+        fn color_blue(&self) -> Self::Component { self.color_to_linear_srgb32().b }
         /// Returns the maximum opacity alpha.
-        fn color_alpha(&self) -> Self::Inner { 1. }
-        fn color_luminosity(&self) -> Self::Inner { self.l }
-        fn color_hue(&self) -> Self::Inner { self.to_oklch32().h }
+        fn color_alpha(&self) -> Self::Component { 1. }
+        fn color_luminosity_f32(&self) -> f32 { self.l }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8(u8::MAX) }
@@ -264,20 +358,21 @@ mod impl_color {
     }
     #[rustfmt::skip]
     impl Color for Oklch32 {
-        type Inner = f32;
-        fn color_to_array3(&self) -> [Self::Inner; 3] { [self.l, self.c, self.h] }
-        fn color_to_array4(&self) -> [Self::Inner; 4] { [self.l, self.c, self.h, 1.] }
+        type Component = f32;
+        fn color_to_array3(&self) -> [Self::Component; 3] { [self.l, self.c, self.h] }
+        fn color_to_array4(&self) -> [Self::Component; 4] { [self.l, self.c, self.h, 1.] }
 
         /// Returns the red luminosity, after converting to [`LinearSrgb32`].
-        fn color_red(&self) -> Self::Inner { self.color_to_linear_srgb32().r }
+        fn color_red(&self) -> Self::Component { self.color_to_linear_srgb32().r }
         /// Returns the green luminosity, after converting to [`LinearSrgb32`].
-        fn color_green(&self) -> Self::Inner { self.color_to_linear_srgb32().g }
+        fn color_green(&self) -> Self::Component { self.color_to_linear_srgb32().g }
         /// Returns the blue luminosity, after converting to [`LinearSrgb32`].
-        fn color_blue(&self) -> Self::Inner { self.color_to_linear_srgb32().b }
+        fn color_blue(&self) -> Self::Component { self.color_to_linear_srgb32().b }
         /// Returns the maximum opacity alpha.
-        fn color_alpha(&self) -> Self::Inner { 1. }
-        fn color_luminosity(&self) -> Self::Inner { self.l }
-        fn color_hue(&self) -> Self::Inner { self.h }
+        fn color_alpha(&self) -> Self::Component { 1. }
+        fn color_luminosity_f32(&self) -> f32 { self.l }
+        fn color_hue_f32(&self) -> f32 { self.h }
+        fn color_chroma_f32(&self) -> f32 { self.c }
 
         fn color_to_srgb8(&self) -> Srgb8 { self.to_srgb8() }
         fn color_to_srgba8(&self) -> Srgba8 { self.to_srgba8(u8::MAX) }
@@ -0,0 +1,60 @@
+// acolor::config
+//
+//! Conversion configuration.
+//
+
+use crate::{
+    depth::{f32_to_unorm_with, Rounding},
+    transfer::TransferFunction,
+};
+
+/// Configuration for the conversions that accept one, like the batch functions
+/// ending in `_with`.
+///
+/// The `From` impls and the [`Color`][crate::Color] trait conversions
+/// always use the default configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ColorConfig {
+    /// The transfer function between encoded and linear RGB.
+    pub transfer: TransferFunction,
+    /// The rounding used when quantizing into `u8` components.
+    pub rounding: Rounding,
+}
+
+impl ColorConfig {
+    /// The default configuration, using the sRGB piecewise curve.
+    pub const DEFAULT: ColorConfig = ColorConfig::new(TransferFunction::SrgbPiecewise);
+
+    /// A configuration using a pure 2.2 gamma curve.
+    pub const GAMMA_22: ColorConfig = ColorConfig::new(TransferFunction::GAMMA_22);
+
+    /// Returns a new configuration with the given `transfer` function,
+    /// and the default [`Rounding::Uniform`].
+    #[inline]
+    pub const fn new(transfer: TransferFunction) -> ColorConfig {
+        ColorConfig {
+            transfer,
+            rounding: Rounding::Uniform,
+        }
+    }
+
+    /// Returns the same configuration with the given `rounding`.
+    #[inline]
+    pub const fn with_rounding(self, rounding: Rounding) -> ColorConfig {
+        ColorConfig { rounding, ..self }
+    }
+
+    /// Encodes a linear `f32` channel into a `u8` one,
+    /// with the transfer function and the rounding.
+    #[inline]
+    pub fn encode8(self, linear: f32) -> u8 {
+        self.transfer.encode8_with(linear, self.rounding)
+    }
+
+    /// Quantizes a normalized `f32` channel, like the alpha, into a `u8` one,
+    /// with the rounding.
+    #[inline]
+    pub const fn quantize8(self, value: f32) -> u8 {
+        f32_to_unorm_with(value, 8, self.rounding) as u8
+    }
+}
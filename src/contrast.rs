@@ -0,0 +1,233 @@
+// acolor::contrast
+//
+//! Contrast between colors.
+//!
+//! # Example
+//! ```
+//! use acolor::{contrast::wcag_contrast, srgb::Srgb8};
+//!
+//! let ratio = wcag_contrast(Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+//! assert![(ratio - 21.).abs() < 1e-3];
+//! ```
+//!
+//! # Links
+//! - <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+//! - <https://github.com/Myndex/apca-w3>
+//
+// # TOC
+//
+// - wcag
+// - apca
+// - ramp
+//
+
+use crate::{math::powf, oklab::Oklch32, srgb::Srgb8, Color};
+use core::fmt;
+
+/* wcag */
+
+/// The minimum WCAG contrast ratio for normal text, at the AA level.
+pub const WCAG_AA: f32 = 4.5;
+/// The minimum WCAG contrast ratio for large text and user interface components,
+/// at the AA level.
+pub const WCAG_AA_LARGE: f32 = 3.;
+/// The minimum WCAG contrast ratio for normal text, at the AAA level.
+pub const WCAG_AAA: f32 = 7.;
+
+/// Returns the WCAG relative luminance of a color, in `0..=1`.
+pub fn relative_luminance<C: Color>(c: C) -> f32 {
+    let c = c.color_to_linear_srgb32();
+    0.2126 * c.r + 0.7152 * c.g + 0.0722 * c.b
+}
+
+/// Returns the WCAG contrast ratio between two colors, in `1..=21`.
+///
+/// The result is the same regardless of the order of the colors.
+pub fn wcag_contrast<A: Color, B: Color>(a: A, b: B) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (hi, lo) = if la > lb { (la, lb) } else { (lb, la) };
+    (hi + 0.05) / (lo + 0.05)
+}
+
+/* apca */
+
+/// Returns the APCA lightness contrast (_Lc_) of a `text` color over a `background`,
+/// following the APCA-W3 0.0.98G-4g constants.
+///
+/// The result is roughly in `-108..=106`: positive for dark text on a light background,
+/// and negative for light text on a dark background.
+///
+/// # Example
+/// ```
+/// # #[cfg(not(feature = "micromath"))] {
+/// use acolor::{contrast::apca_contrast, srgb::Srgb8};
+///
+/// let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+/// assert![(apca_contrast(black, white) - 106.04).abs() < 0.01];
+/// assert![(apca_contrast(white, black) + 107.88).abs() < 0.01];
+/// # }
+/// ```
+pub fn apca_contrast<T: Color, B: Color>(text: T, background: B) -> f32 {
+    let (text, bg) = (apca_luminance(text), apca_luminance(background));
+    if (bg - text).abs() < 0.0005 {
+        return 0.;
+    }
+    let lc = if bg > text {
+        let sapc = (powf(bg, 0.56) - powf(text, 0.57)) * 1.14;
+        if sapc < 0.1 {
+            0.
+        } else {
+            sapc - 0.027
+        }
+    } else {
+        let sapc = (powf(bg, 0.65) - powf(text, 0.62)) * 1.14;
+        if sapc > -0.1 {
+            0.
+        } else {
+            sapc + 0.027
+        }
+    };
+    lc * 100.
+}
+
+// Returns the APCA screen luminance of a color, with the soft clamp of near blacks.
+fn apca_luminance<C: Color>(c: C) -> f32 {
+    let c = c.color_to_srgb32();
+    let y = 0.2126729 * powf(c.r, 2.4) + 0.7151522 * powf(c.g, 2.4) + 0.072175 * powf(c.b, 2.4);
+    if y < 0.022 {
+        y + powf(0.022 - y, 1.414)
+    } else {
+        y
+    }
+}
+
+/* ramp */
+
+/// A minimum contrast requirement between two colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MinContrast {
+    /// A minimum [`wcag_contrast`] ratio.
+    Wcag(f32),
+    /// A minimum absolute [`apca_contrast`], in both polarities.
+    Apca(f32),
+}
+
+impl MinContrast {
+    /// Returns `true` if the contrast between `a` and `b` meets the requirement.
+    ///
+    /// The check doesn't depend on the order of the colors.
+    pub fn is_met_by<A: Color, B: Color>(self, a: A, b: B) -> bool {
+        match self {
+            MinContrast::Wcag(min) => wcag_contrast(a, b) >= min,
+            MinContrast::Apca(min) => {
+                let lc = apca_contrast(a, b).abs();
+                lc.min(apca_contrast(b, a).abs()) >= min
+            }
+        }
+    }
+}
+
+/// An error generating a [`contrast_ramp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContrastRampError {
+    /// The steps at this index and the next one don't meet the adjacent contrast.
+    Adjacent(usize),
+    /// The step at this index doesn't meet the contrast against the background.
+    Background(usize),
+}
+
+impl fmt::Display for ContrastRampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContrastRampError::Adjacent(i) => {
+                write!(f, "insufficient contrast between steps {} and {}", i, i + 1)
+            }
+            ContrastRampError::Background(i) => {
+                write!(
+                    f,
+                    "insufficient contrast between step {} and the background",
+                    i
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ContrastRampError {}
+
+/// Returns a ramp of `N` steps from `start` to `end`, where each pair of adjacent
+/// steps meets the `adjacent` contrast, and each step meets the contrast
+/// against the optional `background`.
+///
+/// The hue and chroma are interpolated in Oklch, while the lightness is chosen so that
+/// the luminance of the steps grows geometrically, which evens out the contrast
+/// between adjacent steps. The first and last steps are `start` and `end`.
+///
+/// # Errors
+/// Returns [`ContrastRampError`] with the first step that doesn't meet a contrast.
+///
+/// # Example
+/// ```
+/// use acolor::{contrast::{contrast_ramp, MinContrast}, srgb::Srgb8};
+///
+/// let (light, dark) = (Srgb8::new(220, 235, 250), Srgb8::new(10, 40, 120));
+/// let white = Srgb8::new(255, 255, 255);
+/// let ramp: [Srgb8; 5] = contrast_ramp(light, dark, MinContrast::Wcag(1.4), None).unwrap();
+/// assert_eq![(ramp[0], ramp[4]), (light, dark)];
+///
+/// let background = Some((white, MinContrast::Wcag(1.5)));
+/// assert![contrast_ramp::<5>(light, dark, MinContrast::Wcag(1.4), background).is_err()];
+/// ```
+pub fn contrast_ramp<const N: usize>(
+    start: impl Color,
+    end: impl Color,
+    adjacent: MinContrast,
+    background: Option<(Srgb8, MinContrast)>,
+) -> Result<[Srgb8; N], ContrastRampError> {
+    let (first, last) = (start.color_to_srgb8(), end.color_to_srgb8());
+    let (s, e) = (first.to_oklch32(), last.to_oklch32());
+    let (ys, ye) = (
+        relative_luminance(first) + 0.05,
+        relative_luminance(last) + 0.05,
+    );
+    let ramp: [Srgb8; N] = core::array::from_fn(|i| {
+        if i == 0 {
+            return first;
+        } else if i == N - 1 {
+            return last;
+        }
+        let t = i as f32 / (N - 1) as f32;
+        let (c, h) = (s.c + (e.c - s.c) * t, s.hue().lerp(e.hue(), t).degrees());
+        let target = ys * powf(ye / ys, t) - 0.05;
+        with_luminance(c, h, target)
+    });
+
+    for i in 0..N {
+        if let Some((bg, min)) = background {
+            if !min.is_met_by(ramp[i], bg) {
+                return Err(ContrastRampError::Background(i));
+            }
+        }
+        if i + 1 < N && !adjacent.is_met_by(ramp[i], ramp[i + 1]) {
+            return Err(ContrastRampError::Adjacent(i));
+        }
+    }
+    Ok(ramp)
+}
+
+// Returns the gamut mapped color with the given chroma and hue whose
+// Oklch lightness gives the nearest relative luminance to `target`.
+fn with_luminance(c: f32, h: f32, target: f32) -> Srgb8 {
+    let color = |l| Oklch32 { l, c, h }.gamut_map_srgb();
+    let (mut lo, mut hi) = (0., 1.);
+    for _ in 0..24 {
+        let mid = (lo + hi) / 2.;
+        if relative_luminance(color(mid)) < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    color((lo + hi) / 2.).to_srgb8()
+}
@@ -0,0 +1,245 @@
+// acolor::cvd
+//
+//! Color vision deficiency simulation and correction.
+//!
+//! # Example
+//! ```
+//! use acolor::{cvd::Deficiency, difference::{ColorDifference, EuclidOklab}, srgb::Srgb8};
+//!
+//! let (red, green) = (Srgb8::new(200, 60, 50), Srgb8::new(90, 140, 40));
+//! let simulated = |c| Deficiency::Deutan.simulate(c, 1.);
+//! let corrected = |c| simulated(Deficiency::Deutan.daltonize(c, 1.));
+//!
+//! let before = EuclidOklab.difference(simulated(red), simulated(green));
+//! let after = EuclidOklab.difference(corrected(red), corrected(green));
+//! assert![after > before];
+//! ```
+//!
+//! # Links
+//! - <https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html>
+//! - <https://daltonize.org/>
+//
+// # TOC
+//
+// - Deficiency
+// - cvd_safe_palette
+//
+
+use crate::{
+    batch::{map_bytes_in_place, ByteLayout},
+    math::mat_mul_vec,
+    srgb::LinearSrgba32,
+    Color, FromColor,
+};
+#[cfg(feature = "alloc")]
+use {
+    crate::{image::Palette, math::sqrt, oklab::Oklab32, srgb::LinearSrgb32},
+    alloc::vec::Vec,
+};
+
+/// A kind of dichromatic color vision deficiency, or its anomalous trichromacy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deficiency {
+    /// Missing or anomalous long-wavelength (red) cones.
+    Protan,
+    /// Missing or anomalous medium-wavelength (green) cones.
+    Deutan,
+    /// Missing or anomalous short-wavelength (blue) cones.
+    Tritan,
+}
+
+// The simulation matrices in linear sRGB, by Machado, Oliveira & Fernandes (2009),
+// at full severity.
+const PROTAN: [[f32; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTAN: [[f32; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+const TRITAN: [[f32; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+// The matrices that shift the error of the simulation into the visible channels,
+// by Fidaner, Lin & Ozguven (2005).
+const SHIFT_RED_GREEN: [[f32; 3]; 3] = [[0., 0., 0.], [0.7, 1., 0.], [0.7, 0., 1.]];
+const SHIFT_BLUE_YELLOW: [[f32; 3]; 3] = [[1., 0., 0.7], [0., 1., 0.7], [0., 0., 0.]];
+
+impl Deficiency {
+    /// Returns how a `color` is seen with this deficiency at a `severity` in `0..=1`,
+    /// where `1` is dichromacy. The alpha is preserved.
+    ///
+    /// Intermediate severities interpolate the matrix of the dichromacy with the identity,
+    /// which approximates the anomalous trichromacies.
+    pub fn simulate<C: Color + FromColor<LinearSrgba32>>(self, color: C, severity: f32) -> C {
+        let c = color.color_to_linear_srgba32();
+        let [r, g, b] = self.simulate_rgb([c.r, c.g, c.b], severity);
+        C::from_color(LinearSrgba32::new(r, g, b, c.a))
+    }
+
+    /// Returns a `color` corrected for this deficiency at a `severity` in `0..=1`,
+    /// redistributing the contrast that would be lost into the channels that remain visible.
+    /// The alpha is preserved.
+    ///
+    /// Colors that are seen unchanged, like the grays, are not modified.
+    pub fn daltonize<C: Color + FromColor<LinearSrgba32>>(self, color: C, severity: f32) -> C {
+        let c = color.color_to_linear_srgba32();
+        let rgb = [c.r, c.g, c.b];
+        let simulated = self.simulate_rgb(rgb, severity);
+        let error = [
+            rgb[0] - simulated[0],
+            rgb[1] - simulated[1],
+            rgb[2] - simulated[2],
+        ];
+        let shift = match self {
+            Deficiency::Protan | Deficiency::Deutan => mat_mul_vec(SHIFT_RED_GREEN, error),
+            Deficiency::Tritan => mat_mul_vec(SHIFT_BLUE_YELLOW, error),
+        };
+        let [r, g, b] = [0, 1, 2].map(|i| (rgb[i] + shift[i]).clamp(0., 1.));
+        C::from_color(LinearSrgba32::new(r, g, b, c.a))
+    }
+
+    /// Corrects in place each of the `colors` for this deficiency, like [`daltonize`].
+    ///
+    /// [`daltonize`]: Deficiency::daltonize
+    pub fn daltonize_in_place<C: Color + FromColor<LinearSrgba32>>(
+        self,
+        colors: &mut [C],
+        severity: f32,
+    ) {
+        for c in colors {
+            *c = self.daltonize(*c, severity);
+        }
+    }
+
+    /// Corrects in place each pixel of an interleaved byte buffer for this deficiency,
+    /// like [`daltonize`]. The alpha channel, if any, is left untouched.
+    ///
+    /// # Panics
+    /// Panics if the length of `buf` is not a multiple of the layout channels.
+    ///
+    /// [`daltonize`]: Deficiency::daltonize
+    pub fn daltonize_bytes_in_place(self, buf: &mut [u8], layout: ByteLayout, severity: f32) {
+        map_bytes_in_place(buf, layout, |c| self.daltonize(c, severity));
+    }
+
+    // Returns the simulated linear sRGB components.
+    fn simulate_rgb(self, rgb: [f32; 3], severity: f32) -> [f32; 3] {
+        let m = match self {
+            Deficiency::Protan => PROTAN,
+            Deficiency::Deutan => DEUTAN,
+            Deficiency::Tritan => TRITAN,
+        };
+        let s = severity.clamp(0., 1.);
+        let m = core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                let identity = if i == j { 1. } else { 0. };
+                identity + (m[i][j] - identity) * s
+            })
+        });
+        mat_mul_vec(m, rgb).map(|c| c.clamp(0., 1.))
+    }
+}
+
+/* palette */
+
+// The Oklab step by which the colors of a palette are perturbed.
+#[cfg(feature = "alloc")]
+const PALETTE_STEP: f32 = 0.005;
+// The weight of the distance to the original colors, against the missing distinction.
+#[cfg(feature = "alloc")]
+const PALETTE_DRIFT_WEIGHT: f32 = 0.1;
+// The maximum number of perturbations.
+#[cfg(feature = "alloc")]
+const PALETTE_ITERATIONS: usize = 10_000;
+
+/// Returns a `palette` minimally perturbed in Oklab so that every pair of its colors
+/// differs by at least `min_delta` (ΔE OK) as seen with each [`Deficiency`] at full severity.
+///
+/// The colors are moved one small step at a time, choosing each time the step
+/// that most reduces the missing distinction while staying near the original colors,
+/// and always within the sRGB gamut.
+///
+/// Returns `None` if no further step improves the palette before every pair is distinct.
+///
+/// # Example
+/// ```
+/// use acolor::{cvd::{cvd_safe_palette, Deficiency}, image::Palette, srgb::Srgb8};
+/// use acolor::difference::{ColorDifference, EuclidOklab};
+///
+/// let palette = Palette::new(vec![Srgb8::new(200, 60, 50), Srgb8::new(90, 140, 40)]).unwrap();
+/// let safe = cvd_safe_palette(&palette, 0.1).unwrap();
+/// let (a, b) = (safe.get(0).unwrap(), safe.get(1).unwrap());
+/// let seen = |c| Deficiency::Deutan.simulate(c, 1.);
+/// assert![EuclidOklab.difference(seen(a), seen(b)) >= 0.1];
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub fn cvd_safe_palette(palette: &Palette, min_delta: f32) -> Option<Palette> {
+    const DEFICIENCIES: [Deficiency; 3] =
+        [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan];
+    let seen = |c: Oklab32| DEFICIENCIES.map(|d| d.simulate(c, 1.));
+    // the missing distinction between two colors, as seen by their simulations
+    let shortfall = |a: &[Oklab32; 3], b: &[Oklab32; 3]| {
+        (0..3)
+            .map(|k| (min_delta - sqrt(a[k].squared_distance(&b[k]))).max(0.))
+            .sum::<f32>()
+    };
+
+    let original: Vec<Oklab32> = palette.colors().iter().map(|c| c.to_oklab32()).collect();
+    let mut colors = original.clone();
+    let mut simulated: Vec<[Oklab32; 3]> = colors.iter().map(|c| seen(*c)).collect();
+    // the cost of the color at index `i` with its `simulated` colors
+    let cost = |i: usize, c: Oklab32, sim: &[Oklab32; 3], simulated: &[[Oklab32; 3]]| {
+        let missing: f32 = (0..simulated.len())
+            .filter(|j| *j != i)
+            .map(|j| shortfall(sim, &simulated[j]))
+            .sum();
+        missing + PALETTE_DRIFT_WEIGHT * sqrt(c.squared_distance(&original[i]))
+    };
+
+    for _ in 0..PALETTE_ITERATIONS {
+        let n = colors.len();
+        let distinct =
+            (0..n).all(|i| (i + 1..n).all(|j| shortfall(&simulated[i], &simulated[j]) == 0.));
+        if distinct {
+            let colors = colors.iter().map(|c| c.to_srgb8()).collect();
+            return Some(Palette::new(colors).expect("the same length as the original palette"));
+        }
+
+        // the step with the largest reduction of the cost
+        let mut best: Option<(usize, Oklab32, [Oklab32; 3], f32)> = None;
+        for i in 0..n {
+            let current = cost(i, colors[i], &simulated[i], &simulated);
+            for axis in 0..3 {
+                for step in [-PALETTE_STEP, PALETTE_STEP] {
+                    let mut lab = [colors[i].l, colors[i].a, colors[i].b];
+                    lab[axis] += step;
+                    let rgb = Oklab32 {
+                        l: lab[0],
+                        a: lab[1],
+                        b: lab[2],
+                    }
+                    .to_linear_srgb32();
+                    let [r, g, b] = [rgb.r, rgb.g, rgb.b].map(|c| c.clamp(0., 1.));
+                    let moved = LinearSrgb32::new(r, g, b).to_oklab32();
+                    let sim = seen(moved);
+                    let gain = current - cost(i, moved, &sim, &simulated);
+                    if gain > best.map_or(0., |b| b.3) {
+                        best = Some((i, moved, sim, gain));
+                    }
+                }
+            }
+        }
+        let (i, moved, sim, _) = best?;
+        colors[i] = moved;
+        simulated[i] = sim;
+    }
+    None
+}
@@ -0,0 +1,162 @@
+// acolor::depth
+//
+//! Bit depth scaling of channel values.
+//!
+//! The conversions map the maximum value of one depth to the maximum of the other,
+//! rounding to the nearest value, so that white stays white. Naive bit shifts instead
+//! leave the brightest values short of the maximum, which shows as banding near white.
+//!
+//! # Example
+//! ```
+//! use acolor::depth::{scale_u8_to_u10, scale_u10_to_u8, unorm_to_f32};
+//!
+//! assert_eq![scale_u8_to_u10(255), 1023];
+//! assert_eq![255_u16 << 2, 1020]; // the naive shift
+//! assert_eq![scale_u10_to_u8(scale_u8_to_u10(128)), 128];
+//! assert_eq![unorm_to_f32(1023, 10), 1.];
+//! ```
+//
+// # TOC
+//
+// - scale_bits
+// - scale_u*_to_u*
+// - unorm_to_f32
+// - f32_to_unorm[_with]
+// - Rounding
+//
+
+use crate::math::fclamp;
+
+// Returns the maximum value of a depth of `bits`.
+const fn max_value(bits: u32) -> u32 {
+    assert![bits >= 1 && bits <= 16, "the bit depth must be in 1..=16"];
+    (1 << bits) - 1
+}
+
+/// Rescales a `value` from a depth of `from_bits` to a depth of `to_bits`,
+/// rounding to the nearest value.
+///
+/// A `value` above the maximum of `from_bits` is clamped to it.
+///
+/// # Panics
+/// Panics if any bit depth is out of `1..=16`.
+pub const fn scale_bits(value: u16, from_bits: u32, to_bits: u32) -> u16 {
+    let (from, to) = (max_value(from_bits), max_value(to_bits));
+    let value = if value as u32 > from {
+        from
+    } else {
+        value as u32
+    };
+    ((value * to + from / 2) / from) as u16
+}
+
+// Defines the rescaling functions between named bit depths.
+macro_rules! scale_fns {
+    ($($name:ident: $from:ty, $from_bits:literal => $to:ty, $to_bits:literal);+ $(;)?) => { $(
+        #[doc = concat!("Rescales a ", stringify!($from_bits), "-bit value to ",
+            stringify!($to_bits), " bits, rounding to the nearest value.")]
+        ///
+        /// A value above the maximum of its depth is clamped to it.
+        #[inline]
+        pub const fn $name(value: $from) -> $to {
+            scale_bits(value as u16, $from_bits, $to_bits) as $to
+        }
+    )+ };
+}
+scale_fns![
+    scale_u8_to_u10: u8, 8 => u16, 10;
+    scale_u10_to_u8: u16, 10 => u8, 8;
+    scale_u8_to_u12: u8, 8 => u16, 12;
+    scale_u12_to_u8: u16, 12 => u8, 8;
+    scale_u8_to_u16: u8, 8 => u16, 16;
+    scale_u16_to_u8: u16, 16 => u8, 8;
+    scale_u10_to_u12: u16, 10 => u16, 12;
+    scale_u12_to_u10: u16, 12 => u16, 10;
+    scale_u10_to_u16: u16, 10 => u16, 16;
+    scale_u16_to_u10: u16, 16 => u16, 10;
+    scale_u12_to_u16: u16, 12 => u16, 16;
+    scale_u16_to_u12: u16, 16 => u16, 12;
+];
+
+/// Returns a `value` of a depth of `bits` normalized to `0..=1`.
+///
+/// A `value` above the maximum of `bits` is clamped to it.
+///
+/// # Panics
+/// Panics if `bits` is out of `1..=16`.
+pub const fn unorm_to_f32(value: u16, bits: u32) -> f32 {
+    let max = max_value(bits);
+    let value = if value as u32 > max {
+        max
+    } else {
+        value as u32
+    };
+    value as f32 / max as f32
+}
+
+/// Returns a normalized `value` as a depth of `bits`, rounding to the nearest value.
+///
+/// The `value` is clamped to `0..=1`, and `NaN` maps to `0`.
+/// Halfway values round away from zero, like [`Rounding::NearestAway`].
+///
+/// # Panics
+/// Panics if `bits` is out of `1..=16`.
+pub const fn f32_to_unorm(value: f32, bits: u32) -> u16 {
+    f32_to_unorm_with(value, bits, Rounding::NearestAway)
+}
+
+/// Returns a normalized `value` as a depth of `bits`, using the given `rounding`.
+///
+/// The `value` is clamped to `0..=1`, and `NaN` maps to `0`.
+///
+/// # Panics
+/// Panics if `bits` is out of `1..=16`.
+pub const fn f32_to_unorm_with(value: f32, bits: u32, rounding: Rounding) -> u16 {
+    let max = max_value(bits);
+    let value = fclamp(value, 0., 1.);
+    if let Rounding::Uniform = rounding {
+        let v = (value * (max + 1) as f32) as u32;
+        return if v > max { max as u16 } else { v as u16 };
+    }
+    let x = value * max as f32;
+    let floor = x as u32;
+    let frac = x - floor as f32;
+    let up = match rounding {
+        Rounding::Uniform | Rounding::Floor => false,
+        Rounding::NearestEven => frac > 0.5 || (frac == 0.5 && floor % 2 == 1),
+        Rounding::NearestAway => frac >= 0.5,
+        Rounding::Stochastic(noise) => frac > (noise >> 8) as f32 / (1 << 24) as f32,
+    };
+    if up && floor < max {
+        (floor + 1) as u16
+    } else {
+        floor as u16
+    }
+}
+
+/// The rounding used when quantizing a normalized `f32` into an integer depth.
+///
+/// All the modes give the same results on every platform,
+/// so they can be relied on by golden-image tests.
+///
+/// See [`f32_to_unorm_with`] and [`ColorConfig`][crate::ColorConfig].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Splits `0..=1` into equally sized ranges, one per integer value.
+    ///
+    /// It's the quantization used by the `From` impls and the [`Color`][crate::Color]
+    /// trait conversions into `u8` components.
+    #[default]
+    Uniform,
+    /// Rounds to the nearest value, and halfway values to the even one.
+    NearestEven,
+    /// Rounds to the nearest value, and halfway values away from zero.
+    NearestAway,
+    /// Rounds down.
+    Floor,
+    /// Rounds up with a probability equal to the fractional part, given a uniformly
+    /// distributed `noise` value, like one from a random number generator.
+    ///
+    /// Only the 24 most significant bits of the noise are used.
+    Stochastic(u32),
+}
@@ -0,0 +1,314 @@
+// acolor::difference
+//
+//! Color difference metrics.
+//!
+//! The [`ColorDifference`] trait is implemented by each metric, so that
+//! algorithms like [`nearest`] can be parameterized by the one to use.
+//!
+//! # Example
+//! ```
+//! # #[cfg(not(feature = "micromath"))] {
+//! use acolor::{
+//!     difference::{nearest, Ciede2000, ColorDifference, EuclidOklab},
+//!     srgb::Srgb8,
+//! };
+//!
+//! let (red, orange) = (Srgb8::new(255, 0, 0), Srgb8::new(255, 128, 0));
+//! assert_eq![Ciede2000.difference(red, red), 0.];
+//! assert![EuclidOklab.difference(red, orange) > 0.];
+//!
+//! let palette = [Srgb8::new(0, 0, 0), red, orange];
+//! assert_eq![nearest(&EuclidOklab, Srgb8::new(240, 20, 10), &palette), Some(1)];
+//! # }
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/Color_difference>
+//! - <https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/>
+//
+// # TOC
+//
+// - ColorDifference
+// - EuclidOklab
+// - WeightedOklab
+// - WeightedOklch
+// - Cie76
+// - Ciede2000
+// - ApcaLightness
+// - nearest
+// - cielab
+//
+
+use crate::{
+    contrast::apca_contrast,
+    math::{atan2_const, exp, mat_mul_vec, powf, sin_cos_deg, sqrt},
+    parse::matrices::SRGB_TO_XYZ_D65,
+    Color,
+};
+
+/// A metric of the difference between two colors.
+///
+/// The result is never negative, and it's `0` for equal colors.
+pub trait ColorDifference {
+    /// Returns the difference between the colors `a` and `b`.
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32;
+}
+
+/// The Euclidean distance in Oklab, as used by CSS Color 4.
+///
+/// A difference of `0.02` is roughly the threshold of perception.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EuclidOklab;
+
+impl ColorDifference for EuclidOklab {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        sqrt(a.color_to_oklab32().squared_distance(&b.color_to_oklab32()))
+    }
+}
+
+/// The Euclidean distance in Oklab, with a weight for each component difference.
+///
+/// With all the weights at `1`, which is the default, it equals [`EuclidOklab`].
+///
+/// # Example
+/// ```
+/// use acolor::{difference::{ColorDifference, WeightedOklab}, srgb::Srgb8};
+///
+/// let lightness = WeightedOklab::new(2., 1., 1.);
+/// let (gray, light_gray) = (Srgb8::new(100, 100, 100), Srgb8::new(120, 120, 120));
+/// assert![lightness.difference(gray, light_gray) > WeightedOklab::default().difference(gray, light_gray)];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedOklab {
+    /// The weight of the lightness difference.
+    pub l: f32,
+    /// The weight of the green-red difference.
+    pub a: f32,
+    /// The weight of the blue-yellow difference.
+    pub b: f32,
+}
+
+impl WeightedOklab {
+    /// Returns a new metric with the given weights.
+    pub const fn new(l: f32, a: f32, b: f32) -> WeightedOklab {
+        WeightedOklab { l, a, b }
+    }
+}
+
+impl Default for WeightedOklab {
+    fn default() -> Self {
+        WeightedOklab::new(1., 1., 1.)
+    }
+}
+
+impl ColorDifference for WeightedOklab {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        let (x, y) = (a.color_to_oklab32(), b.color_to_oklab32());
+        let d = [
+            self.l * (x.l - y.l),
+            self.a * (x.a - y.a),
+            self.b * (x.b - y.b),
+        ];
+        sqrt(d[0] * d[0] + d[1] * d[1] + d[2] * d[2])
+    }
+}
+
+/// The Euclidean distance in Oklab, decomposed into lightness, chroma and hue
+/// differences, with a weight for each.
+///
+/// The hue difference is measured as a distance, proportional to the chroma,
+/// so that the hues of near neutral colors barely count. With all the weights at `1`,
+/// which is the default, it equals [`EuclidOklab`].
+///
+/// Matching palettes for text, for example, can weight the lightness the most,
+/// since it determines the legibility, and the hue the least.
+///
+/// # Example
+/// ```
+/// use acolor::{difference::{nearest, WeightedOklch}, srgb::Srgb8};
+///
+/// let text = WeightedOklch::new(4., 1., 0.25);
+/// let palette = [Srgb8::new(40, 40, 40), Srgb8::new(40, 20, 160)];
+/// assert_eq![nearest(&text, Srgb8::new(20, 10, 90), &palette), Some(0)];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedOklch {
+    /// The weight of the lightness difference.
+    pub l: f32,
+    /// The weight of the chroma difference.
+    pub c: f32,
+    /// The weight of the hue difference.
+    pub h: f32,
+}
+
+impl WeightedOklch {
+    /// Returns a new metric with the given weights.
+    pub const fn new(l: f32, c: f32, h: f32) -> WeightedOklch {
+        WeightedOklch { l, c, h }
+    }
+}
+
+impl Default for WeightedOklch {
+    fn default() -> Self {
+        WeightedOklch::new(1., 1., 1.)
+    }
+}
+
+impl ColorDifference for WeightedOklch {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        let (x, y) = (a.color_to_oklab32(), b.color_to_oklab32());
+        let (dl, da, db) = (x.l - y.l, x.a - y.a, x.b - y.b);
+        let dc = sqrt(x.a * x.a + x.b * x.b) - sqrt(y.a * y.a + y.b * y.b);
+        // the rest of the chromatic distance is due to the hue
+        let dh2 = (da * da + db * db - dc * dc).max(0.);
+        let (wl, wc, wh) = (self.l * self.l, self.c * self.c, self.h * self.h);
+        sqrt(wl * dl * dl + wc * dc * dc + wh * dh2)
+    }
+}
+
+/// The CIE 1976 ΔE\*ab, the Euclidean distance in CIELAB.
+///
+/// A difference of `2.3` is roughly the threshold of perception.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cie76;
+
+impl ColorDifference for Cie76 {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        let (a, b) = (cielab(a), cielab(b));
+        let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+        sqrt(d[0] * d[0] + d[1] * d[1] + d[2] * d[2])
+    }
+}
+
+/// The CIEDE2000 ΔE\*00, with the unity parametric factors.
+///
+/// A difference of `1` is roughly the threshold of perception.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Ciede2000;
+
+impl ColorDifference for Ciede2000 {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        ciede2000(cielab(a), cielab(b))
+    }
+}
+
+/// The absolute APCA lightness contrast between two colors.
+///
+/// Since APCA depends on the polarity, the lowest contrast of both orders is returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ApcaLightness;
+
+impl ColorDifference for ApcaLightness {
+    fn difference<A: Color, B: Color>(&self, a: A, b: B) -> f32 {
+        apca_contrast(a, b).abs().min(apca_contrast(b, a).abs())
+    }
+}
+
+/// Returns the index of the nearest color to `color` among the `candidates`,
+/// according to the `metric`, or `None` if there are no candidates.
+///
+/// On ties the first candidate wins.
+pub fn nearest<M: ColorDifference, C: Color, D: Color>(
+    metric: &M,
+    color: C,
+    candidates: &[D],
+) -> Option<usize> {
+    let mut best: Option<(usize, f32)> = None;
+    for (i, c) in candidates.iter().enumerate() {
+        let d = metric.difference(color, *c);
+        if best.is_none_or(|(_, b)| d < b) {
+            best = Some((i, d));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// Returns the CIEDE2000 difference between two colors given as CIELAB components.
+///
+/// # Example
+/// ```
+/// # #[cfg(not(feature = "micromath"))] {
+/// use acolor::difference::ciede2000;
+///
+/// let d = ciede2000([50., 2.6772, -79.7751], [50., 0., -82.7485]);
+/// assert![(d - 2.0425).abs() < 1e-3];
+/// # }
+/// ```
+pub fn ciede2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    const POW25_7: f32 = 6_103_515_625.;
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+
+    let c_bar = (sqrt(a1 * a1 + b1 * b1) + sqrt(a2 * a2 + b2 * b2)) / 2.;
+    let c_bar7 = powf(c_bar, 7.);
+    let g = 0.5 * (1. - sqrt(c_bar7 / (c_bar7 + POW25_7)));
+    let (a1, a2) = ((1. + g) * a1, (1. + g) * a2);
+    let (c1, c2) = (sqrt(a1 * a1 + b1 * b1), sqrt(a2 * a2 + b2 * b2));
+    let (h1, h2) = (hue_deg(b1, a1), hue_deg(b2, a2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0. {
+        0.
+    } else if h2 - h1 > 180. {
+        h2 - h1 - 360.
+    } else if h2 - h1 < -180. {
+        h2 - h1 + 360.
+    } else {
+        h2 - h1
+    };
+    let dh = 2. * sqrt(c1 * c2) * sin_cos_deg(dh / 2.).0;
+
+    let l_bar = (l1 + l2) / 2.;
+    let c_bar = (c1 + c2) / 2.;
+    let h_bar = if c1 * c2 == 0. {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180. {
+        (h1 + h2) / 2.
+    } else if h1 + h2 < 360. {
+        (h1 + h2 + 360.) / 2.
+    } else {
+        (h1 + h2 - 360.) / 2.
+    };
+
+    let cos = |deg: f32| sin_cos_deg(deg).1;
+    let t = 1. - 0.17 * cos(h_bar - 30.) + 0.24 * cos(2. * h_bar) + 0.32 * cos(3. * h_bar + 6.)
+        - 0.20 * cos(4. * h_bar - 63.);
+    let d_theta = 30. * exp(-powf((h_bar - 275.) / 25., 2.));
+    let c_bar7 = powf(c_bar, 7.);
+    let rc = 2. * sqrt(c_bar7 / (c_bar7 + POW25_7));
+    let l50 = (l_bar - 50.) * (l_bar - 50.);
+    let sl = 1. + 0.015 * l50 / sqrt(20. + l50);
+    let sc = 1. + 0.045 * c_bar;
+    let sh = 1. + 0.015 * c_bar * t;
+    let rt = -sin_cos_deg(2. * d_theta).0 * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    sqrt((l * l + c * c + h * h + rt * c * h).max(0.))
+}
+
+/// Returns the CIELAB components of a color, relative to the D65 white point.
+pub fn cielab<C: Color>(c: C) -> [f32; 3] {
+    const WHITE: [f32; 3] = mat_mul_vec(SRGB_TO_XYZ_D65, [1., 1., 1.]);
+    let c = c.color_to_linear_srgb32();
+    let xyz = mat_mul_vec(SRGB_TO_XYZ_D65, [c.r, c.g, c.b]);
+    let [x, y, z] = [0, 1, 2].map(|i| {
+        let t = xyz[i] / WHITE[i];
+        if t > 216. / 24389. {
+            powf(t, 1. / 3.)
+        } else {
+            (24389. / 27. * t + 16.) / 116.
+        }
+    });
+    [116. * y - 16., 500. * (x - y), 200. * (y - z)]
+}
+
+// Returns the hue angle of the `(a, b)` chroma plane in `0..360` degrees.
+fn hue_deg(b: f32, a: f32) -> f32 {
+    let h = atan2_const(b, a).to_degrees();
+    if h < 0. {
+        h + 360.
+    } else {
+        h
+    }
+}
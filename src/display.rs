@@ -0,0 +1,299 @@
+// acolor::display
+//
+//! Textual representations of colors.
+//!
+//! All the color types implement [`Display`] using the CSS syntax.
+//! The float components honor the formatting precision, e.g. `{:.3}`.
+//!
+//! With the `alloc` feature there are also the `to_*_string` methods.
+//!
+//! # Example
+//! ```
+//! use acolor::srgb::{Srgb8, Srgba8, LinearSrgb32};
+//!
+//! let c = Srgb8::new(255, 128, 0);
+//! assert_eq![c.to_string(), "rgb(255 128 0)"];
+//! assert_eq![c.display_hex().to_string(), "#ff8000"];
+//! assert_eq![c.display_tuple().to_string(), "(255, 128, 0)"];
+//! assert_eq![Srgba8::new(0, 0, 0, 0).display_hex().to_string(), "#00000000"];
+//! assert_eq![Srgba8::new(255, 0, 0, 128).display_canvas().to_string(), "rgba(255, 0, 0, 0.502)"];
+//!
+//! let l = LinearSrgb32::new(1., 0.5, 0.);
+//! assert_eq![format!["{l:.2}"], "color(srgb-linear 1.00 0.50 0.00)"];
+//! ```
+//
+// # TOC
+//
+// - DisplayCss
+// - DisplayTuple
+// - DisplayHex
+// - DisplayCanvas
+//
+
+use crate::{
+    math::u8_to_f32,
+    oklab::{Oklab, Oklch},
+    srgb::{LinearSrgb, LinearSrgba, Srgb, Srgb32, Srgba, Srgba32},
+};
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display};
+
+/// Displays a color using the CSS syntax.
+///
+/// Returned by the `display_css` methods, and used by the [`Display`] impls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayCss<C>(pub C);
+
+/// Displays the components of a color as a tuple, e.g. `(1, 2, 3)`.
+///
+/// Returned by the `display_tuple` methods.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayTuple<C>(pub C);
+
+/// Displays an sRGB color as a hexadecimal string, e.g. `#ff8000`.
+///
+/// Colors with alpha are displayed with 8 digits.
+/// Returned by the `display_hex` methods.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayHex<C>(pub C);
+
+/// Displays an sRGB color as a style accepted by the HTML canvas `fillStyle`
+/// and `strokeStyle` properties.
+///
+/// Opaque colors are displayed as `#rrggbb`, and translucent ones as
+/// `rgba(r, g, b, a)`, with the alpha rounded to 3 decimals.
+/// Returned by the `display_canvas` methods.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayCanvas<C>(pub C);
+
+/* css */
+
+// Implements `DisplayCss` and `Display` for a float color type.
+macro_rules! impl_css_float {
+    ($($type:ident<$t:ty>: $prefix:literal, $first:ident $(, $c:ident)+ $(/ $a:ident)?);+ $(;)?) => { $(
+        impl Display for DisplayCss<$type<$t>> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let c = &self.0;
+                f.write_str($prefix)?;
+                Display::fmt(&c.$first, f)?;
+                $(
+                    f.write_str(" ")?;
+                    Display::fmt(&c.$c, f)?;
+                )+
+                $(
+                    f.write_str(" / ")?;
+                    Display::fmt(&c.$a, f)?;
+                )?
+                f.write_str(")")
+            }
+        }
+        impl Display for $type<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&DisplayCss(*self), f)
+            }
+        }
+    )+ };
+}
+impl_css_float![
+    Srgb<f32>: "color(srgb ", r, g, b;
+    Srgba<f32>: "color(srgb ", r, g, b / a;
+    LinearSrgb<f32>: "color(srgb-linear ", r, g, b;
+    LinearSrgba<f32>: "color(srgb-linear ", r, g, b / a;
+    Oklab<f32>: "oklab(", l, a, b;
+    Oklch<f32>: "oklch(", l, c, h;
+    Srgb<f64>: "color(srgb ", r, g, b;
+    Srgba<f64>: "color(srgb ", r, g, b / a;
+    LinearSrgb<f64>: "color(srgb-linear ", r, g, b;
+    LinearSrgba<f64>: "color(srgb-linear ", r, g, b / a;
+    Oklab<f64>: "oklab(", l, a, b;
+    Oklch<f64>: "oklch(", l, c, h;
+];
+
+impl Display for DisplayCss<Srgb<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgb { r, g, b } = self.0;
+        write!(f, "rgb({r} {g} {b})")
+    }
+}
+impl Display for Srgb<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayCss(*self), f)
+    }
+}
+
+/// The alpha is displayed normalized to `0..=1`.
+impl Display for DisplayCss<Srgba<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgba { r, g, b, a } = self.0;
+        write!(f, "rgb({r} {g} {b} / ")?;
+        Display::fmt(&u8_to_f32(a), f)?;
+        f.write_str(")")
+    }
+}
+impl Display for Srgba<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayCss(*self), f)
+    }
+}
+
+/* tuple */
+
+// Implements `DisplayTuple` for a color type.
+macro_rules! impl_tuple {
+    ($($type:ident: $first:ident $(, $c:ident)+);+ $(;)?) => { $(
+        impl<T: Display> Display for DisplayTuple<$type<T>> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let c = &self.0;
+                f.write_str("(")?;
+                Display::fmt(&c.$first, f)?;
+                $(
+                    f.write_str(", ")?;
+                    Display::fmt(&c.$c, f)?;
+                )+
+                f.write_str(")")
+            }
+        }
+    )+ };
+}
+impl_tuple![
+    Srgb: r, g, b;
+    Srgba: r, g, b, a;
+    LinearSrgb: r, g, b;
+    LinearSrgba: r, g, b, a;
+    Oklab: l, a, b;
+    Oklch: l, c, h;
+];
+
+/* hex */
+
+impl Display for DisplayHex<Srgb<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgb { r, g, b } = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+impl Display for DisplayHex<Srgba<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgba { r, g, b, a } = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+/// The components are first converted to [`u8`].
+impl Display for DisplayHex<Srgb32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayHex(self.0.to_srgb8()), f)
+    }
+}
+/// The components are first converted to [`u8`].
+impl Display for DisplayHex<Srgba32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayHex(self.0.to_srgba8()), f)
+    }
+}
+
+/* canvas */
+
+impl Display for DisplayCanvas<Srgb<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayHex(self.0), f)
+    }
+}
+impl Display for DisplayCanvas<Srgba<u8>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Srgba { r, g, b, a } = self.0;
+        if a == u8::MAX {
+            Display::fmt(&DisplayHex(Srgb { r, g, b }), f)
+        } else {
+            write!(f, "rgba({r}, {g}, {b}, {:.3})", u8_to_f32(a))
+        }
+    }
+}
+/// The components are first converted to [`u8`].
+impl Display for DisplayCanvas<Srgb32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayCanvas(self.0.to_srgb8()), f)
+    }
+}
+/// The components are first converted to [`u8`].
+impl Display for DisplayCanvas<Srgba32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&DisplayCanvas(self.0.to_srgba8()), f)
+    }
+}
+
+/* methods */
+
+// Implements the methods returning the display adapters.
+macro_rules! impl_methods {
+    ($($type:ident),+) => { $(
+        /// # Display
+        impl<T: Copy> $type<T> {
+            /// Returns an adapter that displays the color using the CSS syntax.
+            #[inline]
+            pub const fn display_css(&self) -> DisplayCss<$type<T>> {
+                DisplayCss(*self)
+            }
+            /// Returns an adapter that displays the components as a tuple.
+            #[inline]
+            pub const fn display_tuple(&self) -> DisplayTuple<$type<T>> {
+                DisplayTuple(*self)
+            }
+        }
+
+        /// # String conversions
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+        impl<T: Copy> $type<T> {
+            /// Returns a string with the color using the CSS syntax.
+            #[inline]
+            pub fn to_css_string(&self) -> String
+            where
+                DisplayCss<$type<T>>: Display,
+            {
+                self.display_css().to_string()
+            }
+            /// Returns a string with the components as a tuple.
+            #[inline]
+            pub fn to_tuple_string(&self) -> String
+            where
+                T: Display,
+            {
+                self.display_tuple().to_string()
+            }
+        }
+    )+ };
+    (hex: $($type:ty),+) => { $(
+        /// # Display
+        impl $type {
+            /// Returns an adapter that displays the color as a hexadecimal string.
+            #[inline]
+            pub const fn display_hex(&self) -> DisplayHex<$type> {
+                DisplayHex(*self)
+            }
+            /// Returns a string with the color in hexadecimal.
+            #[inline]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+            pub fn to_hex_string(&self) -> String {
+                self.display_hex().to_string()
+            }
+
+            /// Returns an adapter that displays the color as an HTML canvas style.
+            #[inline]
+            pub const fn display_canvas(&self) -> DisplayCanvas<$type> {
+                DisplayCanvas(*self)
+            }
+            /// Returns a string with the color as an HTML canvas style,
+            /// for the `fillStyle` and `strokeStyle` properties.
+            #[inline]
+            #[cfg(feature = "alloc")]
+            #[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+            pub fn to_canvas_style(&self) -> String {
+                self.display_canvas().to_string()
+            }
+        }
+    )+ };
+}
+impl_methods![Srgb, Srgba, LinearSrgb, LinearSrgba, Oklab, Oklch];
+impl_methods![hex: Srgb<u8>, Srgba<u8>, Srgb32, Srgba32];
@@ -0,0 +1,122 @@
+// acolor::dither
+//
+//! Dithering, for reducing images to a palette.
+//!
+//! Pairs with the [`quantize`][crate::quantize] module for producing indexed images.
+//!
+//! # Example
+//! ```
+//! use acolor::{dither::floyd_steinberg, srgb::Srgb8};
+//!
+//! let palette = [Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255)];
+//! let image = [Srgb8::new(188, 188, 188); 16];
+//! let indices = floyd_steinberg(&image, 4, &palette);
+//! assert![indices.contains(&0) && indices.contains(&1)];
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/Floyd%E2%80%93Steinberg_dithering>
+//
+// # TOC
+//
+// - DitherSpace
+// - floyd_steinberg[_with]
+//
+
+use crate::srgb::Srgb8;
+use alloc::{vec, vec::Vec};
+
+/// The color space where the quantization error is measured and diffused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DitherSpace {
+    /// Linear sRGB, which preserves the average light intensity.
+    Linear,
+    /// Oklab, which picks the perceptually nearest palette colors.
+    #[default]
+    Oklab,
+}
+
+impl DitherSpace {
+    // Converts a color into the components of this space.
+    fn to_components(self, c: Srgb8) -> [f32; 3] {
+        match self {
+            DitherSpace::Linear => {
+                let c = c.to_linear_srgb32();
+                [c.r, c.g, c.b]
+            }
+            DitherSpace::Oklab => {
+                let c = c.to_oklab32();
+                [c.l, c.a, c.b]
+            }
+        }
+    }
+}
+
+/// Reduces an `image` to the colors of a `palette` using Floyd–Steinberg
+/// error diffusion in Oklab space, returning the palette index of each pixel.
+///
+/// The `image` is read in rows of `width` pixels.
+///
+/// # Panics
+/// Panics if `width` is `0` or if the `palette` is empty.
+pub fn floyd_steinberg(image: &[Srgb8], width: usize, palette: &[Srgb8]) -> Vec<usize> {
+    floyd_steinberg_with(image, width, palette, DitherSpace::Oklab)
+}
+
+/// Reduces an `image` to the colors of a `palette` using Floyd–Steinberg
+/// error diffusion in the given `space`, returning the palette index of each pixel.
+///
+/// The `image` is read in rows of `width` pixels.
+///
+/// # Panics
+/// Panics if `width` is `0` or if the `palette` is empty.
+pub fn floyd_steinberg_with(
+    image: &[Srgb8],
+    width: usize,
+    palette: &[Srgb8],
+    space: DitherSpace,
+) -> Vec<usize> {
+    assert![width > 0, "the width must not be 0"];
+    assert![!palette.is_empty(), "the palette must not be empty"];
+    let palette: Vec<[f32; 3]> = palette.iter().map(|c| space.to_components(*c)).collect();
+
+    // the errors of the current and the next row, with a pixel of padding at each side
+    let mut current = vec![[0.; 3]; width + 2];
+    let mut next = vec![[0.; 3]; width + 2];
+
+    let mut indices = Vec::with_capacity(image.len());
+    for row in image.chunks(width) {
+        for (x, c) in row.iter().enumerate() {
+            let mut value = space.to_components(*c);
+            for (v, e) in value.iter_mut().zip(current[x + 1]) {
+                *v += e;
+            }
+            let index = nearest(&palette, value);
+            indices.push(index);
+
+            for i in 0..3 {
+                let e = value[i] - palette[index][i];
+                current[x + 2][i] += e * 7. / 16.;
+                next[x][i] += e * 3. / 16.;
+                next[x + 1][i] += e * 5. / 16.;
+                next[x + 2][i] += e / 16.;
+            }
+        }
+        core::mem::swap(&mut current, &mut next);
+        next.fill([0.; 3]);
+    }
+    indices
+}
+
+// Returns the index of the nearest palette color to `c`.
+fn nearest(palette: &[[f32; 3]], c: [f32; 3]) -> usize {
+    let mut best = (0, f32::INFINITY);
+    for (i, p) in palette.iter().enumerate() {
+        let d = [p[0] - c[0], p[1] - c[1], p[2] - c[2]];
+        let d = d[0] * d[0] + d[1] * d[1] + d[2] * d[2];
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best.0
+}
@@ -7,13 +7,21 @@
 // - sdl2
 // - tiny-skia
 // - notcurses
+// - ggez
+// - plotters
+// - skia-safe
+// - gdk4
+// - cairo-rs
+// - web-sys
+// - palette
 // - approx
+// - defmt
 //
 
 #[cfg(feature = "rgb")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "rgb")))]
 mod rgb {
-    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use crate::srgb::{LinearSrgb32, LinearSrgba32, Srgb, Srgb32, Srgb8, Srgba, Srgba32, Srgba8};
     use rgb::{RGB, RGBA};
 
     /// Convert rust-rgb's `RGB<u8>` type into `Srgb8`.
@@ -69,6 +77,74 @@ mod rgb {
             Self::new(item.r, item.g, item.b, item.a)
         }
     }
+
+    //
+
+    /// Convert rust-rgb's `RGB<u16>` type into `Srgb<u16>`.
+    impl From<RGB<u16>> for Srgb<u16> {
+        fn from(item: RGB<u16>) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+    /// Convert `Srgb<u16>` into rust-rgb's `RGB<u16>` type.
+    impl From<Srgb<u16>> for RGB<u16> {
+        fn from(item: Srgb<u16>) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+
+    /// Convert rust-rgb's `RGBA<u16>` type into `Srgba<u16>`.
+    impl From<RGBA<u16>> for Srgba<u16> {
+        fn from(item: RGBA<u16>) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
+    /// Convert `Srgba<u16>` into rust-rgb's `RGBA<u16>` type.
+    impl From<Srgba<u16>> for RGBA<u16> {
+        fn from(item: Srgba<u16>) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
+
+    //
+
+    /// Convert rust-rgb's `RGB<f32>` type into `LinearSrgb32`.
+    ///
+    /// The components are copied as they are, so they must already be linear.
+    /// Convert into [`Srgb32`] instead for gamma-encoded components.
+    impl From<RGB<f32>> for LinearSrgb32 {
+        fn from(item: RGB<f32>) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+    /// Convert `LinearSrgb32` into rust-rgb's `RGB<f32>` type.
+    ///
+    /// The components are copied as they are, so they stay linear.
+    /// Convert from [`Srgb32`] instead for gamma-encoded components.
+    impl From<LinearSrgb32> for RGB<f32> {
+        fn from(item: LinearSrgb32) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+
+    /// Convert rust-rgb's `RGBA<f32>` type into `LinearSrgba32`.
+    ///
+    /// The components are copied as they are, so they must already be linear.
+    /// Convert into [`Srgba32`] instead for gamma-encoded components.
+    impl From<RGBA<f32>> for LinearSrgba32 {
+        fn from(item: RGBA<f32>) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
+    /// Convert `LinearSrgba32` into rust-rgb's `RGBA<f32>` type.
+    ///
+    /// The components are copied as they are, so they stay linear.
+    /// Convert from [`Srgba32`] instead for gamma-encoded components.
+    impl From<LinearSrgba32> for RGBA<f32> {
+        fn from(item: LinearSrgba32) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
 }
 
 #[cfg(feature = "macroquad")]
@@ -147,14 +223,59 @@ mod macroquad {
             Srgba32::new(c.r, c.g, c.b, c.a)
         }
     }
+
+    // linear and Oklab, through the sRGB transfer function
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    mod float {
+        use super::Color;
+        use crate::{
+            color::Color as _,
+            oklab::{Oklab32, Oklch32},
+            srgb::{LinearSrgb32, LinearSrgba32, Srgba32},
+        };
+
+        // Implements the conversions between macroquad's `Color`, which is
+        // gamma-encoded sRGB, and a color type, through `Srgba32`.
+        macro_rules! impl_through_srgba32 {
+            ($($type:ty: $from_srgba32:ident),+) => { $(
+                impl From<$type> for Color {
+                    /// Into [macroquad's `Color`][0], encoding the components into sRGB.
+                    ///
+                    /// [0]: https://docs.rs/macroquad/latest/macroquad/color/struct.Color.html
+                    fn from(c: $type) -> Color {
+                        let c = c.color_to_srgba32();
+                        Color::new(c.r, c.g, c.b, c.a)
+                    }
+                }
+                impl From<Color> for $type {
+                    /// From [macroquad's `Color`][0], decoding the components from sRGB.
+                    ///
+                    /// [0]: https://docs.rs/macroquad/latest/macroquad/color/struct.Color.html
+                    fn from(c: Color) -> $type {
+                        Srgba32::new(c.r, c.g, c.b, c.a).$from_srgba32()
+                    }
+                }
+            )+ };
+        }
+        impl_through_srgba32![
+            LinearSrgb32: color_to_linear_srgb32,
+            LinearSrgba32: color_to_linear_srgba32,
+            Oklab32: color_to_oklab32,
+            Oklch32: color_to_oklch32
+        ];
+    }
 }
 
 #[cfg(feature = "sdl2")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "sdl2")))]
 mod sdl2 {
-    use crate::srgb::{Srgb8, Srgba8};
+    use crate::{
+        depth::scale_bits,
+        srgb::{Srgb8, Srgba8},
+    };
     use sdl2::gfx::primitives::ToColor;
-    use sdl2::pixels::Color;
+    use sdl2::pixels::{Color, PixelFormatEnum};
 
     impl From<Srgb8> for Color {
         /// Into [sdl2's `Color`][0].
@@ -210,6 +331,100 @@ mod sdl2 {
             (self.r, self.g, self.b, self.a)
         }
     }
+
+    // pixel formats
+
+    // Returns the `(shift, bits)` of the red, green, blue and alpha components
+    // of a packed pixel format, with `0` bits for a missing alpha.
+    fn layout(format: PixelFormatEnum) -> Option<[(u32, u32); 4]> {
+        use PixelFormatEnum as F;
+        Some(match format {
+            F::RGB332 => [(5, 3), (2, 3), (0, 2), (0, 0)],
+            F::RGB444 => [(8, 4), (4, 4), (0, 4), (0, 0)],
+            F::RGB555 => [(10, 5), (5, 5), (0, 5), (0, 0)],
+            F::BGR555 => [(0, 5), (5, 5), (10, 5), (0, 0)],
+            F::ARGB4444 => [(8, 4), (4, 4), (0, 4), (12, 4)],
+            F::RGBA4444 => [(12, 4), (8, 4), (4, 4), (0, 4)],
+            F::ABGR4444 => [(0, 4), (4, 4), (8, 4), (12, 4)],
+            F::BGRA4444 => [(4, 4), (8, 4), (12, 4), (0, 4)],
+            F::ARGB1555 => [(10, 5), (5, 5), (0, 5), (15, 1)],
+            F::RGBA5551 => [(11, 5), (6, 5), (1, 5), (0, 1)],
+            F::ABGR1555 => [(0, 5), (5, 5), (10, 5), (15, 1)],
+            F::BGRA5551 => [(1, 5), (6, 5), (11, 5), (0, 1)],
+            F::RGB565 => [(11, 5), (5, 6), (0, 5), (0, 0)],
+            F::BGR565 => [(0, 5), (5, 6), (11, 5), (0, 0)],
+            F::RGB888 => [(16, 8), (8, 8), (0, 8), (0, 0)],
+            F::RGBX8888 => [(24, 8), (16, 8), (8, 8), (0, 0)],
+            F::BGR888 => [(0, 8), (8, 8), (16, 8), (0, 0)],
+            F::BGRX8888 => [(8, 8), (16, 8), (24, 8), (0, 0)],
+            F::ARGB8888 => [(16, 8), (8, 8), (0, 8), (24, 8)],
+            F::RGBA8888 => [(24, 8), (16, 8), (8, 8), (0, 8)],
+            F::ABGR8888 => [(0, 8), (8, 8), (16, 8), (24, 8)],
+            F::BGRA8888 => [(8, 8), (16, 8), (24, 8), (0, 8)],
+            F::ARGB2101010 => [(20, 10), (10, 10), (0, 10), (30, 2)],
+            _ => return None,
+        })
+    }
+
+    // Packs the components into a pixel.
+    fn pack(c: [u8; 4], format: PixelFormatEnum) -> Option<u32> {
+        let layout = layout(format)?;
+        let mut pixel = 0;
+        for (v, (shift, bits)) in c.into_iter().zip(layout) {
+            if bits > 0 {
+                pixel |= (scale_bits(v as u16, 8, bits) as u32) << shift;
+            }
+        }
+        Some(pixel)
+    }
+
+    // Unpacks the components of a pixel, with a missing alpha at max opacity.
+    fn unpack(pixel: u32, format: PixelFormatEnum) -> Option<[u8; 4]> {
+        let layout = layout(format)?;
+        Some(layout.map(|(shift, bits)| {
+            if bits > 0 {
+                let v = (pixel >> shift) & ((1 << bits) - 1);
+                scale_bits(v as u16, bits, 8) as u8
+            } else {
+                u8::MAX
+            }
+        }))
+    }
+
+    /// # SDL pixels
+    impl Srgb8 {
+        /// Packs the color into a pixel of an SDL packed pixel `format`,
+        /// like `RGB565` or `ARGB8888`, with the alpha at max opacity.
+        ///
+        /// Returns `None` for the byte array, indexed and YUV formats.
+        pub fn to_pixel(&self, format: PixelFormatEnum) -> Option<u32> {
+            pack([self.r, self.g, self.b, u8::MAX], format)
+        }
+        /// Unpacks the color from a `pixel` of an SDL packed pixel `format`,
+        /// discarding the alpha.
+        ///
+        /// Returns `None` for the byte array, indexed and YUV formats.
+        pub fn from_pixel(pixel: u32, format: PixelFormatEnum) -> Option<Srgb8> {
+            unpack(pixel, format).map(|[r, g, b, _]| Srgb8::new(r, g, b))
+        }
+    }
+    /// # SDL pixels
+    impl Srgba8 {
+        /// Packs the color into a pixel of an SDL packed pixel `format`,
+        /// like `RGB565` or `ARGB8888`, discarding the alpha if it has none.
+        ///
+        /// Returns `None` for the byte array, indexed and YUV formats.
+        pub fn to_pixel(&self, format: PixelFormatEnum) -> Option<u32> {
+            pack([self.r, self.g, self.b, self.a], format)
+        }
+        /// Unpacks the color from a `pixel` of an SDL packed pixel `format`,
+        /// with the alpha at max opacity if it has none.
+        ///
+        /// Returns `None` for the byte array, indexed and YUV formats.
+        pub fn from_pixel(pixel: u32, format: PixelFormatEnum) -> Option<Srgba8> {
+            unpack(pixel, format).map(|[r, g, b, a]| Srgba8::new(r, g, b, a))
+        }
+    }
 }
 
 // NOTE: tiny-skia fails to compile before we reach this point.
@@ -295,7 +510,7 @@ mod tiny_skia {
 
     impl From<Srgba32> for Color {
         fn from(c: Srgba32) -> Color {
-            Color::from_rgba(c.r, c.g, c.b, 1.).unwrap_or_else(|| {
+            Color::from_rgba(c.r, c.g, c.b, c.a).unwrap_or_else(|| {
                 Color::from_rgba(
                     pclamp(c.r, 0., 1.),
                     pclamp(c.g, 0., 1.),
@@ -335,13 +550,44 @@ mod tiny_skia {
             c.demultiply().into()
         }
     }
+
+    // linear light (premultiplied)
+
+    /// # tiny-skia linear premultiplication
+    ///
+    /// The `From` conversions into the premultiplied types follow the tiny-skia
+    /// convention of multiplying the gamma-encoded sRGB components by the alpha.
+    ///
+    /// These methods multiply the linear components instead, and return them linear,
+    /// for pixmaps that store linear light, where blending is physically correct.
+    impl Srgba8 {
+        /// Returns the linear components premultiplied by the alpha.
+        pub fn to_tiny_skia_premultiplied_linear(&self) -> PmColorU8 {
+            let c = self.to_linear_srgba32();
+            let a = self.a as f32;
+            let [r, g, b] = [c.r, c.g, c.b].map(|v| (v * a + 0.5) as u8);
+            PmColorU8::from_rgba(r.min(self.a), g.min(self.a), b.min(self.a), self.a).unwrap()
+        }
+    }
+    /// # tiny-skia linear premultiplication
+    ///
+    /// See [`Srgba8::to_tiny_skia_premultiplied_linear`].
+    impl Srgba32 {
+        /// Returns the linear components premultiplied by the alpha.
+        ///
+        /// The components are clamped to `0..=1`.
+        pub fn to_tiny_skia_premultiplied_linear(&self) -> PmColor {
+            let c = self.to_linear_srgba32();
+            Color::from(Srgba32::new(c.r, c.g, c.b, c.a)).premultiply()
+        }
+    }
 }
 
 #[cfg(feature = "notcurses")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "notcurses")))]
 mod notcurses {
-    use crate::srgb::{Srgb8, Srgba8};
-    use notcurses::{Rgb, Rgba};
+    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use notcurses::{Alpha, Channel, Channels, Rgb, Rgba};
 
     impl Srgba8 {
         pub fn to_notcurses(&self) -> Rgba {
@@ -377,73 +623,844 @@ mod notcurses {
             Rgba::new(c.r, c.g, c.b, c.a)
         }
     }
+
+    // f32
+
+    impl From<Srgb32> for Rgb {
+        fn from(c: Srgb32) -> Rgb {
+            c.to_srgb8().into()
+        }
+    }
+    impl From<Rgb> for Srgb32 {
+        fn from(c: Rgb) -> Srgb32 {
+            Srgb8::from(c).to_srgb32()
+        }
+    }
+
+    impl From<Srgba32> for Rgba {
+        fn from(c: Srgba32) -> Rgba {
+            c.to_srgba8().into()
+        }
+    }
+    impl From<Rgba> for Srgba32 {
+        fn from(c: Rgba) -> Srgba32 {
+            Srgba8::from(c).to_srgba32()
+        }
+    }
+
+    // channels
+
+    /// The channel is opaque.
+    impl From<Srgb8> for Channel {
+        fn from(c: Srgb8) -> Channel {
+            Channel::from_rgb(c)
+        }
+    }
+    /// The alpha is mapped to [`Alpha::Opaque`] at max opacity,
+    /// to [`Alpha::Transparent`] at `0`, and to [`Alpha::Blend`] otherwise.
+    impl From<Srgba8> for Channel {
+        fn from(c: Srgba8) -> Channel {
+            let alpha = match c.a {
+                u8::MAX => Alpha::Opaque,
+                0 => Alpha::Transparent,
+                _ => Alpha::Blend,
+            };
+            Channel::from_rgb_alpha(Srgb8::new(c.r, c.g, c.b), alpha)
+        }
+    }
+
+    impl Srgb8 {
+        /// Returns the color of a notcurses `channel`,
+        /// or `None` if it uses the default color or a palette index.
+        pub fn from_notcurses_channel(channel: Channel) -> Option<Srgb8> {
+            channel.is_rgb().then(|| channel.rgb().into())
+        }
+
+        /// Returns notcurses channels with the `fg` foreground and `bg` background colors.
+        pub fn to_notcurses_channels(fg: Srgb8, bg: Srgb8) -> Channels {
+            Channels::from_rgb(fg, bg)
+        }
+
+        /// Returns the foreground and background colors of notcurses `channels`.
+        ///
+        /// Each one is `None` if it uses the default color or a palette index.
+        pub fn from_notcurses_channels(channels: Channels) -> (Option<Srgb8>, Option<Srgb8>) {
+            (
+                Srgb8::from_notcurses_channel(channels.fg()),
+                Srgb8::from_notcurses_channel(channels.bg()),
+            )
+        }
+
+        /// Returns a notcurses channel using the index of the perceptually
+        /// nearest color of a `palette`, for terminals with limited colors.
+        ///
+        /// The distance is measured in Oklab, and only the first 256 colors are used.
+        ///
+        /// # Panics
+        /// Panics if the `palette` is empty.
+        #[cfg(any(feature = "std", feature = "no_std"))]
+        #[cfg_attr(
+            feature = "nightly",
+            doc(cfg(any(feature = "std", feature = "no_std")))
+        )]
+        pub fn to_notcurses_palindex(&self, palette: &[Srgb8]) -> Channel {
+            use crate::difference::{nearest, EuclidOklab};
+            let palette = &palette[..palette.len().min(256)];
+            let index = nearest(&EuclidOklab, *self, palette).expect("empty palette");
+            let mut channel = Channel::new();
+            channel.set_palindex(index as u8);
+            channel
+        }
+    }
 }
 
-#[cfg(feature = "approx")]
-#[cfg_attr(feature = "nightly", doc(cfg(feature = "approx")))]
-mod impl_approx {
+#[cfg(feature = "ggez")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "ggez")))]
+mod ggez {
+    use crate::srgb::{LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8};
+    use ggez::graphics::{Color, LinearColor};
+
+    // u8
+
+    impl From<Srgb8> for Color {
+        /// Into [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Srgb8) -> Color {
+            Color::from_rgba(c.r, c.g, c.b, 255)
+        }
+    }
+    impl From<Color> for Srgb8 {
+        /// From [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Color) -> Srgb8 {
+            Srgb32::new(c.r, c.g, c.b).to_srgb8()
+        }
+    }
+
+    impl From<Srgba8> for Color {
+        /// Into [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Srgba8) -> Color {
+            Color::from_rgba(c.r, c.g, c.b, c.a)
+        }
+    }
+    impl From<Color> for Srgba8 {
+        /// From [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Color) -> Srgba8 {
+            Srgba32::new(c.r, c.g, c.b, c.a).to_srgba8()
+        }
+    }
+    // f32
+
+    impl From<Srgb32> for Color {
+        /// Into [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Srgb32) -> Color {
+            Color::new(c.r, c.g, c.b, 1.)
+        }
+    }
+    impl From<Color> for Srgb32 {
+        /// From [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Color) -> Srgb32 {
+            Srgb32::new(c.r, c.g, c.b)
+        }
+    }
+
+    impl From<Srgba32> for Color {
+        /// Into [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Srgba32) -> Color {
+            Color::new(c.r, c.g, c.b, c.a)
+        }
+    }
+    impl From<Color> for Srgba32 {
+        /// From [ggez's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+        fn from(c: Color) -> Srgba32 {
+            Srgba32::new(c.r, c.g, c.b, c.a)
+        }
+    }
+
+    // linear
+
+    impl From<LinearSrgba32> for LinearColor {
+        /// Into [ggez's `LinearColor`][0], copying the linear components.
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.LinearColor.html
+        fn from(c: LinearSrgba32) -> LinearColor {
+            LinearColor {
+                r: c.r,
+                g: c.g,
+                b: c.b,
+                a: c.a,
+            }
+        }
+    }
+    impl From<LinearColor> for LinearSrgba32 {
+        /// From [ggez's `LinearColor`][0], copying the linear components.
+        ///
+        /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.LinearColor.html
+        fn from(c: LinearColor) -> LinearSrgba32 {
+            LinearSrgba32::new(c.r, c.g, c.b, c.a)
+        }
+    }
+
+    // linear and Oklab, through the sRGB transfer function
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    mod float {
+        use super::Color;
+        use crate::{
+            color::Color as _,
+            oklab::{Oklab32, Oklch32},
+            srgb::{LinearSrgb32, LinearSrgba32, Srgba32},
+        };
+
+        // Implements the conversions between ggez's `Color`, which is
+        // gamma-encoded sRGB, and a color type, through `Srgba32`.
+        macro_rules! impl_through_srgba32 {
+            ($($type:ty: $from_srgba32:ident),+) => { $(
+                impl From<$type> for Color {
+                    /// Into [ggez's `Color`][0], encoding the components into sRGB.
+                    ///
+                    /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+                    fn from(c: $type) -> Color {
+                        let c = c.color_to_srgba32();
+                        Color::new(c.r, c.g, c.b, c.a)
+                    }
+                }
+                impl From<Color> for $type {
+                    /// From [ggez's `Color`][0], decoding the components from sRGB.
+                    ///
+                    /// [0]: https://docs.rs/ggez/latest/ggez/graphics/struct.Color.html
+                    fn from(c: Color) -> $type {
+                        Srgba32::new(c.r, c.g, c.b, c.a).$from_srgba32()
+                    }
+                }
+            )+ };
+        }
+        impl_through_srgba32![
+            LinearSrgb32: color_to_linear_srgb32,
+            LinearSrgba32: color_to_linear_srgba32,
+            Oklab32: color_to_oklab32,
+            Oklch32: color_to_oklch32
+        ];
+    }
+}
+
+#[cfg(feature = "plotters")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "plotters")))]
+mod plotters {
     use crate::{
-        color::Color,
+        math::{f32_to_u8, u8_to_f32},
+        srgb::{Srgb32, Srgb8, Srgba32, Srgba8},
+    };
+    use plotters::style::{RGBAColor, RGBColor};
+
+    // u8
+
+    impl From<Srgb8> for RGBColor {
+        /// Into [plotters' `RGBColor`][0].
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBColor.html
+        fn from(c: Srgb8) -> RGBColor {
+            RGBColor(c.r, c.g, c.b)
+        }
+    }
+    impl From<RGBColor> for Srgb8 {
+        /// From [plotters' `RGBColor`][0].
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBColor.html
+        fn from(c: RGBColor) -> Srgb8 {
+            Srgb8::new(c.0, c.1, c.2)
+        }
+    }
+
+    impl From<Srgba8> for RGBAColor {
+        /// Into [plotters' `RGBAColor`][0], with the alpha scaled into `0..=1`.
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBAColor.html
+        fn from(c: Srgba8) -> RGBAColor {
+            RGBAColor(c.r, c.g, c.b, u8_to_f32(c.a) as f64)
+        }
+    }
+    impl From<RGBAColor> for Srgba8 {
+        /// From [plotters' `RGBAColor`][0], with the alpha saturated into `0..=255`.
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBAColor.html
+        fn from(c: RGBAColor) -> Srgba8 {
+            Srgba8::new(c.0, c.1, c.2, f32_to_u8(c.3 as f32))
+        }
+    }
+
+    // f32
+
+    impl From<Srgb32> for RGBColor {
+        /// Into [plotters' `RGBColor`][0].
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBColor.html
+        fn from(c: Srgb32) -> RGBColor {
+            c.to_srgb8().into()
+        }
+    }
+    impl From<RGBColor> for Srgb32 {
+        /// From [plotters' `RGBColor`][0].
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBColor.html
+        fn from(c: RGBColor) -> Srgb32 {
+            Srgb8::from(c).to_srgb32()
+        }
+    }
+
+    impl From<Srgba32> for RGBAColor {
+        /// Into [plotters' `RGBAColor`][0], keeping the alpha unquantized.
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBAColor.html
+        fn from(c: Srgba32) -> RGBAColor {
+            let rgb = c.to_srgb8();
+            RGBAColor(rgb.r, rgb.g, rgb.b, c.a as f64)
+        }
+    }
+    impl From<RGBAColor> for Srgba32 {
+        /// From [plotters' `RGBAColor`][0], keeping the alpha unquantized.
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBAColor.html
+        fn from(c: RGBAColor) -> Srgba32 {
+            let rgb = Srgb8::new(c.0, c.1, c.2).to_srgb32();
+            Srgba32::new(rgb.r, rgb.g, rgb.b, c.3 as f32)
+        }
+    }
+
+    // palette
+
+    /// # plotters
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+    impl crate::image::Palette {
+        /// Returns the color at `index`, wrapping around the number of colors,
+        /// like [plotters' `Palette::pick`][0] does.
+        ///
+        /// # Panics
+        /// Panics if the palette is empty.
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/trait.Palette.html#method.pick
+        pub fn pick_plotters(&self, index: usize) -> RGBColor {
+            self.colors()[index % self.len()].into()
+        }
+
+        /// Returns the colors as a vector of [plotters' `RGBColor`][0].
+        ///
+        /// [0]: https://docs.rs/plotters/latest/plotters/style/struct.RGBColor.html
+        pub fn to_plotters(&self) -> alloc::vec::Vec<RGBColor> {
+            self.colors().iter().map(|&c| c.into()).collect()
+        }
+    }
+}
+
+#[cfg(feature = "skia-safe")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "skia-safe")))]
+mod skia_safe {
+    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use skia_safe::{Color, Color4f};
+
+    // u8
+
+    impl From<Srgb8> for Color {
+        /// Into [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Srgb8) -> Color {
+            Color::from_rgb(c.r, c.g, c.b)
+        }
+    }
+    impl From<Color> for Srgb8 {
+        /// From [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Color) -> Srgb8 {
+            Srgb8::new(c.r(), c.g(), c.b())
+        }
+    }
+
+    impl From<Srgba8> for Color {
+        /// Into [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Srgba8) -> Color {
+            Color::from_argb(c.a, c.r, c.g, c.b)
+        }
+    }
+    impl From<Color> for Srgba8 {
+        /// From [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Color) -> Srgba8 {
+            Srgba8::new(c.r(), c.g(), c.b(), c.a())
+        }
+    }
+
+    // f32, taken as gamma-encoded sRGB, like skia does unless the color space is linear
+
+    impl From<Srgb32> for Color4f {
+        /// Into [skia's `Color4f`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color4f.html
+        fn from(c: Srgb32) -> Color4f {
+            Color4f::new(c.r, c.g, c.b, 1.)
+        }
+    }
+    impl From<Color4f> for Srgb32 {
+        /// From [skia's `Color4f`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color4f.html
+        fn from(c: Color4f) -> Srgb32 {
+            Srgb32::new(c.r, c.g, c.b)
+        }
+    }
+
+    impl From<Srgba32> for Color4f {
+        /// Into [skia's `Color4f`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color4f.html
+        fn from(c: Srgba32) -> Color4f {
+            Color4f::new(c.r, c.g, c.b, c.a)
+        }
+    }
+    impl From<Color4f> for Srgba32 {
+        /// From [skia's `Color4f`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color4f.html
+        fn from(c: Color4f) -> Srgba32 {
+            Srgba32::new(c.r, c.g, c.b, c.a)
+        }
+    }
+
+    // u8 and f32, quantizing
+
+    impl From<Srgb32> for Color {
+        /// Into [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Srgb32) -> Color {
+            c.to_srgb8().into()
+        }
+    }
+    impl From<Color> for Srgb32 {
+        /// From [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Color) -> Srgb32 {
+            Srgb8::from(c).to_srgb32()
+        }
+    }
+
+    impl From<Srgba32> for Color {
+        /// Into [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Srgba32) -> Color {
+            c.to_srgba8().into()
+        }
+    }
+    impl From<Color> for Srgba32 {
+        /// From [skia's `Color`][0].
+        ///
+        /// [0]: https://docs.rs/skia-safe/latest/skia_safe/struct.Color.html
+        fn from(c: Color) -> Srgba32 {
+            Srgba8::from(c).to_srgba32()
+        }
+    }
+}
+
+#[cfg(feature = "gdk4")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "gdk4")))]
+mod gdk4 {
+    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use gdk4::RGBA;
+
+    // f32
+
+    impl From<Srgb32> for RGBA {
+        /// Into [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: Srgb32) -> RGBA {
+            RGBA::new(c.r, c.g, c.b, 1.)
+        }
+    }
+    impl From<RGBA> for Srgb32 {
+        /// From [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: RGBA) -> Srgb32 {
+            Srgb32::new(c.red(), c.green(), c.blue())
+        }
+    }
+
+    impl From<Srgba32> for RGBA {
+        /// Into [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: Srgba32) -> RGBA {
+            RGBA::new(c.r, c.g, c.b, c.a)
+        }
+    }
+    impl From<RGBA> for Srgba32 {
+        /// From [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: RGBA) -> Srgba32 {
+            Srgba32::new(c.red(), c.green(), c.blue(), c.alpha())
+        }
+    }
+
+    // u8
+
+    impl From<Srgb8> for RGBA {
+        /// Into [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: Srgb8) -> RGBA {
+            c.to_srgb32().into()
+        }
+    }
+    impl From<RGBA> for Srgb8 {
+        /// From [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: RGBA) -> Srgb8 {
+            Srgb32::from(c).to_srgb8()
+        }
+    }
+
+    impl From<Srgba8> for RGBA {
+        /// Into [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: Srgba8) -> RGBA {
+            c.to_srgba32().into()
+        }
+    }
+    impl From<RGBA> for Srgba8 {
+        /// From [gdk4's `RGBA`][0].
+        ///
+        /// [0]: https://docs.rs/gdk4/latest/gdk4/struct.RGBA.html
+        fn from(c: RGBA) -> Srgba8 {
+            Srgba32::from(c).to_srgba8()
+        }
+    }
+}
+
+#[cfg(feature = "cairo-rs")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "cairo-rs")))]
+mod cairo {
+    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use cairo::{Context, Error, SolidPattern};
+
+    // f64 components, which cairo takes as gamma-encoded sRGB
+
+    impl From<Srgb32> for SolidPattern {
+        /// Into [cairo's `SolidPattern`][0].
+        ///
+        /// [0]: https://docs.rs/cairo-rs/latest/cairo/struct.SolidPattern.html
+        fn from(c: Srgb32) -> SolidPattern {
+            SolidPattern::from_rgb(c.r as f64, c.g as f64, c.b as f64)
+        }
+    }
+    impl From<Srgba32> for SolidPattern {
+        /// Into [cairo's `SolidPattern`][0].
+        ///
+        /// [0]: https://docs.rs/cairo-rs/latest/cairo/struct.SolidPattern.html
+        fn from(c: Srgba32) -> SolidPattern {
+            SolidPattern::from_rgba(c.r as f64, c.g as f64, c.b as f64, c.a as f64)
+        }
+    }
+    impl From<Srgb8> for SolidPattern {
+        /// Into [cairo's `SolidPattern`][0].
+        ///
+        /// [0]: https://docs.rs/cairo-rs/latest/cairo/struct.SolidPattern.html
+        fn from(c: Srgb8) -> SolidPattern {
+            c.to_srgb32().into()
+        }
+    }
+    impl From<Srgba8> for SolidPattern {
+        /// Into [cairo's `SolidPattern`][0].
+        ///
+        /// [0]: https://docs.rs/cairo-rs/latest/cairo/struct.SolidPattern.html
+        fn from(c: Srgba8) -> SolidPattern {
+            c.to_srgba32().into()
+        }
+    }
+
+    /// # cairo
+    impl Srgb32 {
+        /// Sets the opaque source color of a cairo `context`.
+        pub fn set_cairo_source(&self, context: &Context) {
+            context.set_source_rgb(self.r as f64, self.g as f64, self.b as f64);
+        }
+    }
+    /// # cairo
+    impl Srgba32 {
+        /// Sets the source color of a cairo `context`.
+        pub fn set_cairo_source(&self, context: &Context) {
+            context.set_source_rgba(self.r as f64, self.g as f64, self.b as f64, self.a as f64);
+        }
+
+        /// Returns the color of a cairo solid `pattern`.
+        ///
+        /// # Errors
+        /// Returns the cairo [`Error`] of an invalid `pattern`.
+        pub fn from_cairo_pattern(pattern: &SolidPattern) -> Result<Srgba32, Error> {
+            let (r, g, b, a) = pattern.rgba()?;
+            Ok(Srgba32::new(r as f32, g as f32, b as f32, a as f32))
+        }
+    }
+    /// # cairo
+    impl Srgb8 {
+        /// Sets the opaque source color of a cairo `context`.
+        pub fn set_cairo_source(&self, context: &Context) {
+            self.to_srgb32().set_cairo_source(context);
+        }
+    }
+    /// # cairo
+    impl Srgba8 {
+        /// Sets the source color of a cairo `context`.
+        pub fn set_cairo_source(&self, context: &Context) {
+            self.to_srgba32().set_cairo_source(context);
+        }
+
+        /// Returns the color of a cairo solid `pattern`.
+        ///
+        /// # Errors
+        /// Returns the cairo [`Error`] of an invalid `pattern`.
+        pub fn from_cairo_pattern(pattern: &SolidPattern) -> Result<Srgba8, Error> {
+            Srgba32::from_cairo_pattern(pattern).map(|c| c.to_srgba8())
+        }
+    }
+}
+
+#[cfg(feature = "web-sys")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "web-sys")))]
+mod web_sys {
+    use crate::srgb::{Srgb32, Srgb8, Srgba32, Srgba8};
+    use web_sys::CanvasRenderingContext2d;
+
+    macro_rules! impl_canvas_style {
+        ($($type:ty),+) => { $(
+            /// # web-sys
+            impl $type {
+                /// Sets the color as the `fillStyle` of an HTML canvas 2D `context`.
+                pub fn set_canvas_fill_style(&self, context: &CanvasRenderingContext2d) {
+                    context.set_fill_style_str(&self.to_canvas_style());
+                }
+                /// Sets the color as the `strokeStyle` of an HTML canvas 2D `context`.
+                pub fn set_canvas_stroke_style(&self, context: &CanvasRenderingContext2d) {
+                    context.set_stroke_style_str(&self.to_canvas_style());
+                }
+            }
+        )+ };
+    }
+    impl_canvas_style![Srgb8, Srgba8, Srgb32, Srgba32];
+}
+
+#[cfg(feature = "palette")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "palette")))]
+mod palette {
+    use crate::{
+        hue::Hue,
         oklab::{Oklab32, Oklch32},
-        srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgba32},
+        srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8},
     };
-    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+    use palette::{LinSrgb, LinSrgba, Oklab, OklabHue, Oklch, Srgb, Srgba};
+
+    // gamma-encoded
+
+    /// Convert palette's `Srgb<u8>` type into `Srgb8`.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgb<u8>> for Srgb8 {
+        fn from(item: Srgb<u8>) -> Self {
+            Self::new(item.red, item.green, item.blue)
+        }
+    }
+    /// Convert `Srgb8` into palette's `Srgb<u8>` type.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgb8> for Srgb<u8> {
+        fn from(item: Srgb8) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+
+    /// Convert palette's `Srgba<u8>` type into `Srgba8`.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgba<u8>> for Srgba8 {
+        fn from(item: Srgba<u8>) -> Self {
+            let c = item.color;
+            Self::new(c.red, c.green, c.blue, item.alpha)
+        }
+    }
+    /// Convert `Srgba8` into palette's `Srgba<u8>` type.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgba8> for Srgba<u8> {
+        fn from(item: Srgba8) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
 
-    // MAYBE add generic versions. E.g. `fn abs<T>(n: T)`.
-    #[cfg(not(feature = "std"))]
-    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
-    #[inline(always)]
-    fn abs(n: f32) -> f32 {
-        libm::fabsf(n)
+    /// Convert palette's `Srgb<f32>` type into `Srgb32`.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgb<f32>> for Srgb32 {
+        fn from(item: Srgb<f32>) -> Self {
+            Self::new(item.red, item.green, item.blue)
+        }
     }
-    #[cfg(feature = "std")]
-    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
-    #[inline(always)]
-    fn abs(n: f32) -> f32 {
-        f32::abs(n)
+    /// Convert `Srgb32` into palette's `Srgb<f32>` type.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgb32> for Srgb<f32> {
+        fn from(item: Srgb32) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
     }
 
-    #[cfg(not(feature = "std"))]
-    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
-    #[inline(always)]
-    fn signum(n: f32) -> f32 {
-        libm::copysignf(n, 1.0)
+    /// Convert palette's `Srgba<f32>` type into `Srgba32`.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgba<f32>> for Srgba32 {
+        fn from(item: Srgba<f32>) -> Self {
+            let c = item.color;
+            Self::new(c.red, c.green, c.blue, item.alpha)
+        }
     }
-    #[cfg(feature = "std")]
-    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
-    #[inline(always)]
-    fn signum(n: f32) -> f32 {
-        f32::signum(n)
+    /// Convert `Srgba32` into palette's `Srgba<f32>` type.
+    ///
+    /// Both sides are gamma-encoded sRGB.
+    impl From<Srgba32> for Srgba<f32> {
+        fn from(item: Srgba32) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
     }
 
+    // linear
+
+    /// Convert palette's `LinSrgb<f32>` type into `LinearSrgb32`.
+    ///
+    /// Both sides are linear, so the components are copied as they are.
+    impl From<LinSrgb<f32>> for LinearSrgb32 {
+        fn from(item: LinSrgb<f32>) -> Self {
+            Self::new(item.red, item.green, item.blue)
+        }
+    }
+    /// Convert `LinearSrgb32` into palette's `LinSrgb<f32>` type.
+    ///
+    /// Both sides are linear, so the components are copied as they are.
+    impl From<LinearSrgb32> for LinSrgb<f32> {
+        fn from(item: LinearSrgb32) -> Self {
+            Self::new(item.r, item.g, item.b)
+        }
+    }
+
+    /// Convert palette's `LinSrgba<f32>` type into `LinearSrgba32`.
+    ///
+    /// Both sides are linear, so the components are copied as they are.
+    impl From<LinSrgba<f32>> for LinearSrgba32 {
+        fn from(item: LinSrgba<f32>) -> Self {
+            let c = item.color;
+            Self::new(c.red, c.green, c.blue, item.alpha)
+        }
+    }
+    /// Convert `LinearSrgba32` into palette's `LinSrgba<f32>` type.
+    ///
+    /// Both sides are linear, so the components are copied as they are.
+    impl From<LinearSrgba32> for LinSrgba<f32> {
+        fn from(item: LinearSrgba32) -> Self {
+            Self::new(item.r, item.g, item.b, item.a)
+        }
+    }
+
+    // Oklab
+
+    /// Convert palette's `Oklab<f32>` type into `Oklab32`, without clamping.
+    impl From<Oklab<f32>> for Oklab32 {
+        fn from(item: Oklab<f32>) -> Self {
+            Self::new_unchecked(item.l, item.a, item.b)
+        }
+    }
+    /// Convert `Oklab32` into palette's `Oklab<f32>` type.
+    impl From<Oklab32> for Oklab<f32> {
+        fn from(item: Oklab32) -> Self {
+            Self::new(item.l, item.a, item.b)
+        }
+    }
+
+    /// Convert palette's `Oklch<f32>` type into `Oklch32`, without clamping.
+    ///
+    /// The hue is wrapped into `0.0..360.0`.
+    impl From<Oklch<f32>> for Oklch32 {
+        fn from(item: Oklch<f32>) -> Self {
+            let hue = Hue::new(item.hue.into_raw_degrees());
+            Self::new_unchecked(item.l, item.chroma, hue.degrees())
+        }
+    }
+    /// Convert `Oklch32` into palette's `Oklch<f32>` type.
+    impl From<Oklch32> for Oklch<f32> {
+        fn from(item: Oklch32) -> Self {
+            Self::new_const(item.l, item.c, OklabHue::new(item.h))
+        }
+    }
+}
+
+#[cfg(feature = "approx")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "approx")))]
+mod impl_approx {
+    use crate::{
+        color::Color,
+        math::{abs, signum},
+        oklab::{Oklab32, Oklch32},
+        srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgba32},
+    };
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
     // Implements approx traits
     //
     // # Args
     // * $T: the color type (e.g. Srgb32)
     // * $t: the type of each component (e.g. f32)
-    //
-    // MAYBE divide in two versions, one with just 3 components, for performance.
+    // * $n: the number of components, so that the types without alpha
+    //   don't compare the fabricated one
+    // * $to_array: the method that returns the `$n` components
     macro_rules! impl_approx {
-        // implements for a series of color types with the same component type
-        (all $t:ty: $( $T:ty ),+) => {
-            $( impl_approx![$T, $t]; )+
+        // implements for a series of color types with the same components
+        (all $t:ty, $n:literal, $to_array:ident: $( $T:ty ),+) => {
+            $( impl_approx![$T, $t, $n, $to_array]; )+
         };
 
         // implements for a single color type
-        ($T:ty, $t:ty) => {
+        ($T:ty, $t:ty, $n:literal, $to_array:ident) => {
             impl AbsDiffEq for $T {
                 type Epsilon = $t;
                 fn default_epsilon() -> $t {
                     <$t>::EPSILON
                 }
                 fn abs_diff_eq(&self, other: &Self, epsilon: $t) -> bool {
-                    let s: [$t; 4] = self.color_to_array4();
-                    let o: [$t; 4] = other.color_to_array4();
+                    let s: [$t; $n] = self.$to_array();
+                    let o: [$t; $n] = other.$to_array();
 
-                    abs(s[0] - o[0]) <= epsilon
-                        && abs(s[1] - o[1]) <= epsilon
-                        && abs(s[2] - o[2]) <= epsilon
-                        && abs(s[3] - o[3]) <= epsilon
+                    s.iter().zip(&o).all(|(s, o)| abs(s - o) <= epsilon)
                 }
             }
             impl RelativeEq for $T {
@@ -451,77 +1468,29 @@ mod impl_approx {
                     <$t>::EPSILON
                 }
                 fn relative_eq(&self, other: &Self, epsilon: $t, max_relative: $t) -> bool {
-                    let s: [$t; 4] = self.color_to_array4();
-                    let o: [$t; 4] = other.color_to_array4();
+                    let s: [$t; $n] = self.$to_array();
+                    let o: [$t; $n] = other.$to_array();
 
                     // Handle same infinities
-                    if s[0] == o[0] && s[1] == o[1] && s[2] == o[2] && s[3] == o[3] {
+                    if s == o {
                         return true;
                     }
 
                     // Handle remaining infinities
-                    if s[0].is_infinite()
-                        || o[0].is_infinite()
-                        || s[1].is_infinite()
-                        || o[1].is_infinite()
-                        || s[2].is_infinite()
-                        || o[2].is_infinite()
-                        || s[3].is_infinite()
-                        || o[3].is_infinite()
-                    {
+                    if s.iter().chain(&o).any(|c| c.is_infinite()) {
                         return false;
                     }
 
-                    let abs_diff_0 = abs(s[0] - o[0]);
-                    let abs_diff_1 = abs(s[1] - o[1]);
-                    let abs_diff_2 = abs(s[2] - o[2]);
-                    let abs_diff_3 = abs(s[3] - o[3]);
-
                     // For when the numbers are really close together
-                    if abs_diff_0 <= epsilon
-                        && abs_diff_1 <= epsilon
-                        && abs_diff_2 <= epsilon
-                        && abs_diff_3 <= epsilon
-                    {
+                    if s.iter().zip(&o).all(|(s, o)| abs(s - o) <= epsilon) {
                         return true;
                     }
 
-                    let abs_self_0 = abs(s[0]);
-                    let abs_self_1 = abs(s[1]);
-                    let abs_self_2 = abs(s[2]);
-                    let abs_self_3 = abs(s[3]);
-
-                    let abs_other_0 = abs(o[0]);
-                    let abs_other_1 = abs(o[1]);
-                    let abs_other_2 = abs(o[2]);
-                    let abs_other_3 = abs(o[3]);
-
-                    let largest_0 = if abs_other_0 > abs_self_0 {
-                        abs_other_0
-                    } else {
-                        abs_self_0
-                    };
-                    let largest_1 = if abs_other_1 > abs_self_1 {
-                        abs_other_1
-                    } else {
-                        abs_self_1
-                    };
-                    let largest_2 = if abs_other_2 > abs_self_2 {
-                        abs_other_2
-                    } else {
-                        abs_self_2
-                    };
-                let largest_3 = if abs_other_3 > abs_self_3 {
-                        abs_other_3
-                    } else {
-                        abs_self_3
-                    };
-
                     // Use a relative difference comparison
-                    abs_diff_0 <= largest_0 * max_relative
-                        && abs_diff_1 <= largest_1 * max_relative
-                        && abs_diff_2 <= largest_2 * max_relative
-                        && abs_diff_3 <= largest_3 * max_relative
+                    s.iter().zip(&o).all(|(s, o)| {
+                        let largest = if abs(*o) > abs(*s) { abs(*o) } else { abs(*s) };
+                        abs(s - o) <= largest * max_relative
+                    })
                 }
             }
 
@@ -531,8 +1500,8 @@ mod impl_approx {
                 }
 
                 fn ulps_eq(&self, other: &Self, epsilon: $t, max_ulps: u32) -> bool {
-                    let s: [$t; 4] = self.color_to_array4();
-                    let o: [$t; 4] = other.color_to_array4();
+                    let s: [$t; $n] = self.$to_array();
+                    let o: [$t; $n] = other.$to_array();
 
                     // For when the numbers are really close together
                     if self.abs_diff_eq(other, epsilon) {
@@ -540,31 +1509,45 @@ mod impl_approx {
                     }
 
                     // Trivial negative sign check
-                    if signum(s[0]) != signum(o[0])
-                        && signum(s[1]) != signum(o[1])
-                        && signum(s[2]) != signum(o[2])
-                        && signum(s[3]) != signum(o[3])
-                    {
+                    if s.iter().zip(&o).all(|(s, o)| signum(*s) != signum(*o)) {
                         return false;
                     }
 
                     // ULPS difference comparison
-                    let int_self_0: u32 = s[0].to_bits();
-                    let int_self_1: u32 = s[1].to_bits();
-                    let int_self_2: u32 = s[2].to_bits();
-                    let int_self_3: u32 = s[3].to_bits();
-                    let int_other_0: u32 = o[0].to_bits();
-                    let int_other_1: u32 = o[1].to_bits();
-                    let int_other_2: u32 = o[2].to_bits();
-                    let int_other_3: u32 = o[3].to_bits();
-
-                    int_self_0.abs_diff(int_other_0) <= max_ulps
-                        && int_self_1.abs_diff(int_other_1) <= max_ulps
-                        && int_self_2.abs_diff(int_other_2) <= max_ulps
-                        && int_self_3.abs_diff(int_other_3) <= max_ulps
+                    s.iter()
+                        .zip(&o)
+                        .all(|(s, o)| s.to_bits().abs_diff(o.to_bits()) <= max_ulps)
                 }
             }
         };
     }
-    impl_approx![all f32: Srgb32, Srgba32, LinearSrgb32, LinearSrgba32, Oklab32, Oklch32];
+    impl_approx![all f32, 3, color_to_array3: Srgb32, LinearSrgb32, Oklab32, Oklch32];
+    impl_approx![all f32, 4, color_to_array4: Srgba32, LinearSrgba32];
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "defmt")))]
+mod impl_defmt {
+    use crate::srgb::{Srgb8, Srgba8};
+    use defmt::{Format, Formatter};
+
+    /// Formats as `rgb(r g b)`.
+    impl Format for Srgb8 {
+        fn format(&self, f: Formatter) {
+            defmt::write!(f, "rgb({=u8} {=u8} {=u8})", self.r, self.g, self.b);
+        }
+    }
+    /// Formats as `rgba(r g b a)`, with the alpha as a `u8`.
+    impl Format for Srgba8 {
+        fn format(&self, f: Formatter) {
+            defmt::write!(
+                f,
+                "rgba({=u8} {=u8} {=u8} {=u8})",
+                self.r,
+                self.g,
+                self.b,
+                self.a
+            );
+        }
+    }
 }
@@ -0,0 +1,466 @@
+// acolor::fixed
+//
+//! Fixed-point color components.
+//!
+//! The sRGB based types use [`U0F16`] components, normalized to `0..1`,
+//! where the maximum value `65535/65536` stands for `1.0`.
+//! The Oklab based types use [`I16F16`] components.
+//!
+//! The conversions from and into the `u8` types only use integer arithmetic,
+//! making them suitable for microcontrollers without an FPU.
+//!
+//! The *integer conversions* decode and encode sRGB and convert to and from
+//! Oklab also without floats, using tables generated at compile time:
+//! - sRGB decoding is rounded to the nearest `U0F16` step.
+//! - sRGB encoding returns the nearest `u8`, except maybe at an exact midpoint.
+//! - the Oklab components differ from the `f32` conversion by less than `2e-4`,
+//!   and the round trip from `Srgb8` to [`OklabI16F16`] and back is exact.
+//!
+//! # Example
+//! ```
+//! use acolor::{fixed::SrgbU0F16, srgb::Srgb8};
+//!
+//! let c = SrgbU0F16::from_srgb8(Srgb8::new(255, 128, 0));
+//! assert_eq![c.to_srgb8(), Srgb8::new(255, 128, 0)];
+//! ```
+//
+// # TOC
+//
+// - definitions
+// - conversions
+// - integer conversions
+//
+
+use crate::{
+    math::srgb_decode_const,
+    oklab::{Oklab, Oklab32, Oklch, Oklch32},
+    srgb::{LinearSrgb, LinearSrgb32, LinearSrgba, LinearSrgba32},
+    srgb::{Srgb, Srgb32, Srgb8, Srgba, Srgba32, Srgba8},
+};
+pub use ::fixed::types::{I16F16, U0F16};
+
+/* definitions */
+
+/// Non-linear sRGB color representation using `3` × [`U0F16`] components.
+pub type SrgbU0F16 = Srgb<U0F16>;
+
+/// Non-linear sRGB+A color representation using `4` × [`U0F16`] components.
+pub type SrgbaU0F16 = Srgba<U0F16>;
+
+/// Linear sRGB color representation using `3` × [`U0F16`] components.
+pub type LinearSrgbU0F16 = LinearSrgb<U0F16>;
+
+/// Linear sRGB+A color representation using `4` × [`U0F16`] components.
+pub type LinearSrgbaU0F16 = LinearSrgba<U0F16>;
+
+/// Oklab color representation using `3` × [`I16F16`] components.
+pub type OklabI16F16 = Oklab<I16F16>;
+
+/// Oklch color representation using `3` × [`I16F16`] components.
+pub type OklchI16F16 = Oklch<I16F16>;
+
+/* conversions */
+
+// Converts a `u8` channel into a `U0F16` one, mapping `255` to the maximum.
+#[inline]
+const fn u8_to_u0f16(c: u8) -> U0F16 {
+    U0F16::from_bits(c as u16 * 257)
+}
+
+// Converts a `U0F16` channel into a `u8` one, mapping the maximum to `255`.
+#[inline]
+const fn u0f16_to_u8(c: U0F16) -> u8 {
+    (c.to_bits() >> 8) as u8
+}
+
+// Converts an `f32` channel into a `U0F16` one, saturating.
+#[inline]
+fn f32_to_u0f16(c: f32) -> U0F16 {
+    U0F16::saturating_from_num(c)
+}
+
+// Converts a `U0F16` channel into an `f32` one, mapping the maximum to `1.0`.
+#[inline]
+fn u0f16_to_f32(c: U0F16) -> f32 {
+    if c == U0F16::MAX {
+        1.
+    } else {
+        c.to_num()
+    }
+}
+
+/// # Direct conversions
+impl SrgbU0F16 {
+    /// Direct conversion from [`Srgb8`].
+    #[inline]
+    pub const fn from_srgb8(c: Srgb8) -> SrgbU0F16 {
+        Srgb::new(u8_to_u0f16(c.r), u8_to_u0f16(c.g), u8_to_u0f16(c.b))
+    }
+    /// Direct conversion to [`Srgb8`].
+    #[inline]
+    pub const fn to_srgb8(&self) -> Srgb8 {
+        Srgb::new(
+            u0f16_to_u8(self.r),
+            u0f16_to_u8(self.g),
+            u0f16_to_u8(self.b),
+        )
+    }
+
+    /// Direct conversion from [`Srgb32`], saturating.
+    #[inline]
+    pub fn from_srgb32(c: Srgb32) -> SrgbU0F16 {
+        c.map(f32_to_u0f16)
+    }
+    /// Direct conversion to [`Srgb32`].
+    #[inline]
+    pub fn to_srgb32(&self) -> Srgb32 {
+        self.map(u0f16_to_f32)
+    }
+}
+
+/// # Direct conversions
+impl SrgbaU0F16 {
+    /// Direct conversion from [`Srgba8`].
+    #[inline]
+    pub const fn from_srgba8(c: Srgba8) -> SrgbaU0F16 {
+        Srgba::new(
+            u8_to_u0f16(c.r),
+            u8_to_u0f16(c.g),
+            u8_to_u0f16(c.b),
+            u8_to_u0f16(c.a),
+        )
+    }
+    /// Direct conversion to [`Srgba8`].
+    #[inline]
+    pub const fn to_srgba8(&self) -> Srgba8 {
+        Srgba::new(
+            u0f16_to_u8(self.r),
+            u0f16_to_u8(self.g),
+            u0f16_to_u8(self.b),
+            u0f16_to_u8(self.a),
+        )
+    }
+
+    /// Direct conversion from [`Srgba32`], saturating.
+    #[inline]
+    pub fn from_srgba32(c: Srgba32) -> SrgbaU0F16 {
+        c.map(f32_to_u0f16)
+    }
+    /// Direct conversion to [`Srgba32`].
+    #[inline]
+    pub fn to_srgba32(&self) -> Srgba32 {
+        self.map(u0f16_to_f32)
+    }
+}
+
+/// # Direct conversions
+impl LinearSrgbU0F16 {
+    /// Direct conversion from [`LinearSrgb32`], saturating.
+    #[inline]
+    pub fn from_linear_srgb32(c: LinearSrgb32) -> LinearSrgbU0F16 {
+        c.map(f32_to_u0f16)
+    }
+    /// Direct conversion to [`LinearSrgb32`].
+    #[inline]
+    pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
+        self.map(u0f16_to_f32)
+    }
+}
+
+/// # Direct conversions
+impl LinearSrgbaU0F16 {
+    /// Direct conversion from [`LinearSrgba32`], saturating.
+    #[inline]
+    pub fn from_linear_srgba32(c: LinearSrgba32) -> LinearSrgbaU0F16 {
+        c.map(f32_to_u0f16)
+    }
+    /// Direct conversion to [`LinearSrgba32`].
+    #[inline]
+    pub fn to_linear_srgba32(&self) -> LinearSrgba32 {
+        self.map(u0f16_to_f32)
+    }
+}
+
+/// # Direct conversions
+impl OklabI16F16 {
+    /// Direct conversion from [`Oklab32`], saturating.
+    #[inline]
+    pub fn from_oklab32(c: Oklab32) -> OklabI16F16 {
+        c.map(I16F16::saturating_from_num)
+    }
+    /// Direct conversion to [`Oklab32`].
+    #[inline]
+    pub fn to_oklab32(&self) -> Oklab32 {
+        self.map(I16F16::to_num)
+    }
+}
+
+/// # Direct conversions
+impl OklchI16F16 {
+    /// Direct conversion from [`Oklch32`], saturating.
+    #[inline]
+    pub fn from_oklch32(c: Oklch32) -> OklchI16F16 {
+        c.map(I16F16::saturating_from_num)
+    }
+    /// Direct conversion to [`Oklch32`].
+    #[inline]
+    pub fn to_oklch32(&self) -> Oklch32 {
+        self.map(I16F16::to_num)
+    }
+}
+
+// Implements `From` in both directions using the direct conversion methods.
+macro_rules! impl_from {
+    ($($type:ty: $other:ty, $from:ident, $to:ident);+ $(;)?) => { $(
+        impl From<$other> for $type {
+            #[inline]
+            fn from(c: $other) -> $type {
+                <$type>::$from(c)
+            }
+        }
+        impl From<$type> for $other {
+            #[inline]
+            fn from(c: $type) -> $other {
+                c.$to()
+            }
+        }
+    )+ };
+}
+impl_from![
+    SrgbU0F16: Srgb8, from_srgb8, to_srgb8;
+    SrgbU0F16: Srgb32, from_srgb32, to_srgb32;
+    SrgbaU0F16: Srgba8, from_srgba8, to_srgba8;
+    SrgbaU0F16: Srgba32, from_srgba32, to_srgba32;
+    LinearSrgbU0F16: LinearSrgb32, from_linear_srgb32, to_linear_srgb32;
+    LinearSrgbaU0F16: LinearSrgba32, from_linear_srgba32, to_linear_srgba32;
+    OklabI16F16: Oklab32, from_oklab32, to_oklab32;
+    OklchI16F16: Oklch32, from_oklch32, to_oklch32;
+];
+
+/* integer conversions */
+
+// The linear `U0F16` bits of each `u8` sRGB channel value.
+const DECODE_U8: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (srgb_decode_const(i as f64 / 255.) * 65535. + 0.5) as u16;
+        i += 1;
+    }
+    table
+};
+
+// The linear `U0F16` bits halfway between each pair of consecutive `u8` sRGB values.
+const ENCODE_THRESHOLDS: [u16; 255] = {
+    let mut table = [0; 255];
+    let mut i = 0;
+    while i < 255 {
+        table[i] = (srgb_decode_const((i as f64 + 0.5) / 255.) * 65535. + 0.5) as u16;
+        i += 1;
+    }
+    table
+};
+
+// Encodes a linear `U0F16` channel into the nearest `u8` sRGB one.
+#[inline]
+const fn linear_u0f16_to_srgb_u8(c: U0F16) -> u8 {
+    // binary search of the number of thresholds below the value
+    let bits = c.to_bits();
+    let (mut lo, mut hi) = (0, 255);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if ENCODE_THRESHOLDS[mid] <= bits {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo as u8
+}
+
+// The Oklab matrices in Q28, from linear sRGB to LMS, from LMS' to Oklab,
+// and their inverses.
+const LINEAR_TO_LMS: [[i64; 3]; 3] = [
+    [110654858, 143970669, 13809929],
+    [56882412, 182723893, 28829151],
+    [23703512, 75623325, 169108620],
+];
+const LMS_TO_OKLAB: [[i64; 3]; 3] = [
+    [56493384, 213035152, -1093082],
+    [530964928, -651920256, 120955328],
+    [6953562, 210123696, -217077248],
+];
+const OKLAB_TO_LMS: [[i64; 3]; 3] = [
+    [268435456, 106391112, 57929380],
+    [268435456, -28336408, -17140724],
+    [268435456, -24020726, -346680512],
+];
+const LMS_TO_LINEAR: [[i64; 3]; 3] = [
+    [1094342007, -887907069, 62000518],
+    [-340493734, 700551418, -91622228],
+    [-1126378, -188822497, 458384331],
+];
+
+// Multiplies a Q28 matrix by a Q28 vector, rounding.
+#[inline]
+const fn mat_mul_q28(m: &[[i64; 3]; 3], v: [i64; 3]) -> [i64; 3] {
+    let mut out = [0; 3];
+    let mut i = 0;
+    while i < 3 {
+        let sum = m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2];
+        out[i] = (sum + (1 << 27)) >> 28;
+        i += 1;
+    }
+    out
+}
+
+// Returns the cube root of a non-negative Q28 value, in Q28, rounded down.
+#[inline]
+const fn cbrt_q28(v: i64) -> i64 {
+    if v <= 0 {
+        return 0;
+    }
+    // cbrt(v / 2^28) * 2^28 = cbrt(v * 2^56), found bit by bit
+    let n = (v as u128) << 56;
+    let mut root: u128 = 0;
+    let mut bit = 1 << 30;
+    while bit > 0 {
+        let r = root | bit;
+        if r * r * r <= n {
+            root = r;
+        }
+        bit >>= 1;
+    }
+    root as i64
+}
+
+// Clamps a Q28 value between `-max` and `max`.
+#[inline]
+const fn clamp_q28(v: i64, max: i64) -> i64 {
+    if v < -max {
+        -max
+    } else if v > max {
+        max
+    } else {
+        v
+    }
+}
+
+// Converts a `U0F16` channel into a Q28 value, mapping the maximum to `1.0`.
+#[inline]
+const fn u0f16_to_q28(c: U0F16) -> i64 {
+    ((c.to_bits() as i64) << 28) / u16::MAX as i64
+}
+
+// Converts a Q28 value into a `U0F16` channel, mapping `1.0` to the maximum, clamping.
+#[inline]
+const fn q28_to_u0f16(v: i64) -> U0F16 {
+    let v = (v * u16::MAX as i64 + (1 << 27)) >> 28;
+    U0F16::from_bits(if v < 0 {
+        0
+    } else if v > u16::MAX as i64 {
+        u16::MAX
+    } else {
+        v as u16
+    })
+}
+
+/// # Integer conversions
+impl LinearSrgbU0F16 {
+    /// Decodes an [`Srgb8`], using only integer arithmetic.
+    #[inline]
+    pub const fn from_srgb8(c: Srgb8) -> LinearSrgbU0F16 {
+        LinearSrgb::new(
+            U0F16::from_bits(DECODE_U8[c.r as usize]),
+            U0F16::from_bits(DECODE_U8[c.g as usize]),
+            U0F16::from_bits(DECODE_U8[c.b as usize]),
+        )
+    }
+    /// Encodes into the nearest [`Srgb8`], using only integer arithmetic.
+    #[inline]
+    pub const fn to_srgb8(&self) -> Srgb8 {
+        Srgb::new(
+            linear_u0f16_to_srgb_u8(self.r),
+            linear_u0f16_to_srgb_u8(self.g),
+            linear_u0f16_to_srgb_u8(self.b),
+        )
+    }
+
+    /// Converts into [`OklabI16F16`], using only integer arithmetic.
+    #[inline]
+    pub const fn to_oklab_i16f16(&self) -> OklabI16F16 {
+        let c = [
+            u0f16_to_q28(self.r),
+            u0f16_to_q28(self.g),
+            u0f16_to_q28(self.b),
+        ];
+        let lms = mat_mul_q28(&LINEAR_TO_LMS, c);
+        let lms = [cbrt_q28(lms[0]), cbrt_q28(lms[1]), cbrt_q28(lms[2])];
+        let [l, a, b] = mat_mul_q28(&LMS_TO_OKLAB, lms);
+        Oklab {
+            l: I16F16::from_bits(((l + (1 << 11)) >> 12) as i32),
+            a: I16F16::from_bits(((a + (1 << 11)) >> 12) as i32),
+            b: I16F16::from_bits(((b + (1 << 11)) >> 12) as i32),
+        }
+    }
+    /// Converts from [`OklabI16F16`], using only integer arithmetic, clamping.
+    #[inline]
+    pub const fn from_oklab_i16f16(c: OklabI16F16) -> LinearSrgbU0F16 {
+        // the clamping ranges, far out of the gamut, avoid overflowing the products
+        let c = [
+            clamp_q28((c.l.to_bits() as i64) << 12, 4 << 28),
+            clamp_q28((c.a.to_bits() as i64) << 12, 4 << 28),
+            clamp_q28((c.b.to_bits() as i64) << 12, 4 << 28),
+        ];
+        let lms = mat_mul_q28(&OKLAB_TO_LMS, c);
+        let mut cubed = [0; 3];
+        let mut i = 0;
+        while i < 3 {
+            let v = clamp_q28(lms[i], 3 << 27);
+            cubed[i] = (((v * v) >> 28) * v) >> 28;
+            i += 1;
+        }
+        let [r, g, b] = mat_mul_q28(&LMS_TO_LINEAR, cubed);
+        LinearSrgb::new(q28_to_u0f16(r), q28_to_u0f16(g), q28_to_u0f16(b))
+    }
+}
+
+/// # Integer conversions
+impl LinearSrgbaU0F16 {
+    /// Decodes an [`Srgba8`], using only integer arithmetic.
+    ///
+    /// The alpha is not gamma-encoded, and only gets scaled.
+    #[inline]
+    pub const fn from_srgba8(c: Srgba8) -> LinearSrgbaU0F16 {
+        LinearSrgba::new(
+            U0F16::from_bits(DECODE_U8[c.r as usize]),
+            U0F16::from_bits(DECODE_U8[c.g as usize]),
+            U0F16::from_bits(DECODE_U8[c.b as usize]),
+            u8_to_u0f16(c.a),
+        )
+    }
+    /// Encodes into the nearest [`Srgba8`], using only integer arithmetic.
+    #[inline]
+    pub const fn to_srgba8(&self) -> Srgba8 {
+        Srgba::new(
+            linear_u0f16_to_srgb_u8(self.r),
+            linear_u0f16_to_srgb_u8(self.g),
+            linear_u0f16_to_srgb_u8(self.b),
+            u0f16_to_u8(self.a),
+        )
+    }
+}
+
+/// # Integer conversions
+impl OklabI16F16 {
+    /// Converts from [`Srgb8`], using only integer arithmetic.
+    #[inline]
+    pub const fn from_srgb8(c: Srgb8) -> OklabI16F16 {
+        LinearSrgbU0F16::from_srgb8(c).to_oklab_i16f16()
+    }
+    /// Converts into the nearest [`Srgb8`], using only integer arithmetic, clamping.
+    #[inline]
+    pub const fn to_srgb8(&self) -> Srgb8 {
+        LinearSrgbU0F16::from_oklab_i16f16(*self).to_srgb8()
+    }
+}
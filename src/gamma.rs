@@ -1,7 +1,18 @@
 // acolor::gamma
 //
-//!
+//! Gamma constants.
 //
 
-/// The default gamma value as an [`f32`].
+/// The exponent of the sRGB piecewise curve, as an [`f32`].
+///
+/// It's not a pure power gamma: the whole curve approximates a gamma of 2.2.
+/// See [`TransferFunction`][crate::transfer::TransferFunction] to choose the exact curve.
 pub const GAMMA_32: f32 = 2.4;
+
+/// The exponent of a pure power gamma curve, as an [`f32`].
+///
+/// Much legacy content, like many game textures, was authored assuming this curve
+/// instead of the sRGB piecewise one. Select it with [`TransferFunction::GAMMA_22`].
+///
+/// [`TransferFunction::GAMMA_22`]: crate::transfer::TransferFunction::GAMMA_22
+pub const GAMMA_22: f32 = 2.2;
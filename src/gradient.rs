@@ -0,0 +1,288 @@
+// acolor::gradient
+//
+//! Multi-stop gradients.
+//!
+//! # Example
+//! ```
+//! use acolor::{
+//!     gradient::{Ease, Gradient},
+//!     mix::{HueMode, MixSpace},
+//!     srgb::Srgb8,
+//! };
+//!
+//! let gradient = Gradient::builder()
+//!     .space(MixSpace::Oklch(HueMode::Shorter))
+//!     .hue(HueMode::Longer)
+//!     .ease(Ease::SmoothStep)
+//!     .stop(0.0, Srgb8::new(255, 0, 0))
+//!     .stop(1.0, Srgb8::new(0, 0, 255))
+//!     .build()
+//!     .unwrap();
+//! assert_eq![gradient.sample::<Srgb8>(0.), Srgb8::new(255, 0, 0)];
+//! assert_eq![gradient.colors::<Srgb8>(5).len(), 5];
+//! ```
+//
+// # TOC
+//
+// - Ease
+// - Gradient
+// - GradientBuilder
+// - GradientError
+//
+
+use crate::{
+    math::sqrt,
+    mix::{HueMode, MixSpace},
+    oklab::Oklab32,
+    srgb::LinearSrgba32,
+    Color, FromColor,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An easing curve, applied to the position between each pair of stops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Ease {
+    /// A constant speed.
+    #[default]
+    Linear,
+    /// Slow at both ends, with a cubic Hermite curve.
+    SmoothStep,
+    /// Slow at the start, with a quadratic curve.
+    EaseIn,
+    /// Slow at the end, with a quadratic curve.
+    EaseOut,
+}
+
+impl Ease {
+    /// Returns the eased value of `t`, in `0..=1`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Ease::Linear => t,
+            Ease::SmoothStep => t * t * (3. - 2. * t),
+            Ease::EaseIn => t * t,
+            Ease::EaseOut => 1. - (1. - t) * (1. - t),
+        }
+    }
+}
+
+/// A gradient of colors placed at increasing positions, called stops.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    space: MixSpace,
+    ease: Ease,
+    stops: Vec<(f32, LinearSrgba32)>,
+}
+
+impl Gradient {
+    /// Returns a new builder.
+    pub fn builder() -> GradientBuilder {
+        GradientBuilder::default()
+    }
+
+    /// Returns the interpolation space.
+    pub fn space(&self) -> MixSpace {
+        self.space
+    }
+    /// Returns the easing curve.
+    pub fn ease(&self) -> Ease {
+        self.ease
+    }
+    /// Returns the stops, as pairs of position and color.
+    pub fn stops(&self) -> &[(f32, LinearSrgba32)] {
+        &self.stops
+    }
+
+    /// Returns the color at the position `t`.
+    ///
+    /// Positions before the first stop or after the last one take their color.
+    /// At a position shared by several stops, the last one wins.
+    pub fn sample<C: FromColor<LinearSrgba32>>(&self, t: f32) -> C {
+        let next = self.stops.partition_point(|(p, _)| *p <= t);
+        let color = if next == 0 {
+            self.stops[0].1
+        } else if next == self.stops.len() {
+            self.stops[next - 1].1
+        } else {
+            let ((p0, c0), (p1, c1)) = (self.stops[next - 1], self.stops[next]);
+            let local = self.ease.apply((t - p0) / (p1 - p0));
+            self.space.mix(c0, c1, local)
+        };
+        C::from_color(color)
+    }
+
+    /// Returns `n` colors sampled at even intervals from the first stop to the last one.
+    pub fn colors<C: FromColor<LinearSrgba32>>(&self, n: usize) -> Vec<C> {
+        let (first, last) = (self.stops[0].0, self.stops[self.stops.len() - 1].0);
+        let step = if n > 1 {
+            (last - first) / (n - 1) as f32
+        } else {
+            0.
+        };
+        (0..n)
+            .map(|i| self.sample(first + step * i as f32))
+            .collect()
+    }
+
+    /// Returns `n` colors along the gradient where each pair of adjacent colors
+    /// differs by at least `min_delta` in Oklab (ΔE OK), for legends and classed maps.
+    ///
+    /// The colors are re-spaced at even perceptual intervals instead of even positions,
+    /// so that the regions where the gradient changes slowly don't produce lookalike steps.
+    /// A ΔE OK of `0.02` is about a just noticeable difference.
+    ///
+    /// Returns `None` if the gradient doesn't change enough to fit `n` such steps.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{gradient::Gradient, srgb::Srgb8};
+    ///
+    /// let gradient = Gradient::builder()
+    ///     .stop(0.0, Srgb8::new(255, 255, 255))
+    ///     .stop(0.9, Srgb8::new(250, 250, 250))
+    ///     .stop(1.0, Srgb8::new(0, 0, 0))
+    ///     .build()
+    ///     .unwrap();
+    /// let steps = gradient.steps::<Srgb8>(5, 0.1).unwrap();
+    /// assert_eq![steps.len(), 5];
+    /// assert![gradient.steps::<Srgb8>(20, 0.1).is_none()];
+    /// ```
+    pub fn steps<C: FromColor<LinearSrgba32>>(&self, n: usize, min_delta: f32) -> Option<Vec<C>> {
+        // the resolution of the measurement of the length of the gradient
+        const SAMPLES: usize = 1024;
+
+        let (first, last) = (self.stops[0].0, self.stops[self.stops.len() - 1].0);
+        let position = |i: usize| first + (last - first) * i as f32 / SAMPLES as f32;
+        let mut lengths = Vec::with_capacity(SAMPLES + 1);
+        let (mut length, mut prev) = (0., self.sample::<Oklab32>(first));
+        for i in 0..=SAMPLES {
+            let lab = self.sample::<Oklab32>(position(i));
+            length += sqrt(lab.squared_distance(&prev));
+            lengths.push(length);
+            prev = lab;
+        }
+
+        let mut steps = Vec::with_capacity(n);
+        for k in 0..n {
+            let target = if n > 1 {
+                length * k as f32 / (n - 1) as f32
+            } else {
+                0.
+            };
+            // the sample segment where the length reaches the target
+            let i = lengths.partition_point(|l| *l < target).min(SAMPLES);
+            let t = if i == 0 {
+                first
+            } else {
+                let (l0, l1) = (lengths[i - 1], lengths[i]);
+                let local = if l1 > l0 {
+                    (target - l0) / (l1 - l0)
+                } else {
+                    0.
+                };
+                position(i - 1) + (position(i) - position(i - 1)) * local
+            };
+            steps.push(self.sample::<LinearSrgba32>(t));
+        }
+
+        let labs: Vec<Oklab32> = steps.iter().map(|c| c.color_to_oklab32()).collect();
+        if labs
+            .windows(2)
+            .any(|w| sqrt(w[0].squared_distance(&w[1])) < min_delta)
+        {
+            return None;
+        }
+        Some(steps.into_iter().map(C::from_color).collect())
+    }
+}
+
+/// A builder of a [`Gradient`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GradientBuilder {
+    space: MixSpace,
+    hue: Option<HueMode>,
+    ease: Ease,
+    stops: Vec<(f32, LinearSrgba32)>,
+}
+
+impl GradientBuilder {
+    /// Sets the interpolation space. Defaults to [`MixSpace::Oklab`].
+    pub fn space(mut self, space: MixSpace) -> Self {
+        self.space = space;
+        self
+    }
+    /// Sets the hue path, overriding the one of an [`MixSpace::Oklch`] space.
+    ///
+    /// It's ignored by the rest of spaces.
+    pub fn hue(mut self, hue: HueMode) -> Self {
+        self.hue = Some(hue);
+        self
+    }
+    /// Sets the easing curve. Defaults to [`Ease::Linear`].
+    pub fn ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+    /// Adds a stop with a `color` at a `position`.
+    ///
+    /// The positions must not decrease from one stop to the next.
+    pub fn stop<C: Color>(mut self, position: f32, color: C) -> Self {
+        self.stops.push((position, color.color_to_linear_srgba32()));
+        self
+    }
+
+    /// Returns the gradient.
+    ///
+    /// # Errors
+    /// Returns [`GradientError`] if there are no stops, or if their positions
+    /// are not finite or decrease.
+    pub fn build(self) -> Result<Gradient, GradientError> {
+        if self.stops.is_empty() {
+            return Err(GradientError::NoStops);
+        }
+        for (i, (p, _)) in self.stops.iter().enumerate() {
+            if !p.is_finite() {
+                return Err(GradientError::InvalidPosition(i));
+            }
+            if i > 0 && *p < self.stops[i - 1].0 {
+                return Err(GradientError::DecreasingPosition(i));
+            }
+        }
+        let space = match (self.space, self.hue) {
+            (MixSpace::Oklch(_), Some(hue)) => MixSpace::Oklch(hue),
+            (space, _) => space,
+        };
+        Ok(Gradient {
+            space,
+            ease: self.ease,
+            stops: self.stops,
+        })
+    }
+}
+
+/// An error building a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GradientError {
+    /// There are no stops.
+    NoStops,
+    /// The stop at this index has a position that is not finite.
+    InvalidPosition(usize),
+    /// The stop at this index has a position below the previous one.
+    DecreasingPosition(usize),
+}
+
+impl fmt::Display for GradientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GradientError::NoStops => write!(f, "the gradient has no stops"),
+            GradientError::InvalidPosition(i) => write!(f, "stop {} has an invalid position", i),
+            GradientError::DecreasingPosition(i) => {
+                write!(f, "stop {} has a position below the previous one", i)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GradientError {}
@@ -0,0 +1,154 @@
+// acolor::hue
+//
+//! Hue angles.
+//!
+//! # Example
+//! ```
+//! use acolor::hue::Hue;
+//!
+//! let h = Hue::new(350.) + 20.;
+//! assert_eq![h.degrees(), 10.];
+//! assert_eq![Hue::new(350.).diff(Hue::new(10.)), 20.];
+//! assert_eq![Hue::new(-90.), Hue::new(270.)];
+//! ```
+//
+
+use core::{
+    f32::consts::PI,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// A hue angle in degrees, always wrapped into `0..360`.
+///
+/// The arithmetic with other hues, or with an `f32` amount of degrees, wraps around.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Hue(f32);
+
+/// # Constructors
+impl Hue {
+    /// The hue at `0` degrees.
+    pub const ZERO: Hue = Hue(0.);
+
+    /// Returns a new hue from an angle in `degrees`, wrapped into `0..360`.
+    pub const fn new(degrees: f32) -> Hue {
+        let mut h = degrees % 360.;
+        if h < 0. {
+            h += 360.;
+        }
+        // a tiny negative angle can round up to a full turn
+        if h >= 360. {
+            h = 0.;
+        }
+        Hue(h)
+    }
+
+    /// Returns a new hue from an angle in `radians`, wrapped into `0..360` degrees.
+    pub const fn from_radians(radians: f32) -> Hue {
+        Hue::new(radians * (180. / PI))
+    }
+}
+
+/// # Getters
+impl Hue {
+    /// Returns the angle in degrees, in `0..360`.
+    pub const fn degrees(self) -> f32 {
+        self.0
+    }
+    /// Returns the angle in radians, in `0..τ`.
+    pub const fn radians(self) -> f32 {
+        self.0 * (PI / 180.)
+    }
+}
+
+/// # Operations
+impl Hue {
+    /// Returns the shortest signed distance in degrees from this hue to `other`,
+    /// in `-180..=180`.
+    ///
+    /// Positive values go counterclockwise, increasing the angle.
+    pub const fn diff(self, other: Hue) -> f32 {
+        let d = other.0 - self.0;
+        if d > 180. {
+            d - 360.
+        } else if d <= -180. {
+            d + 360.
+        } else {
+            d
+        }
+    }
+
+    /// Returns the hue at `t` along the shortest path from this hue to `other`.
+    pub const fn lerp(self, other: Hue, t: f32) -> Hue {
+        Hue::new(self.0 + self.diff(other) * t)
+    }
+}
+
+// Wraps an `f64` angle in degrees into `0..360`, like `Hue::new`.
+pub(crate) const fn wrap_degrees_f64(degrees: f64) -> f64 {
+    let mut h = degrees % 360.;
+    if h < 0. {
+        h += 360.;
+    }
+    if h >= 360. {
+        h = 0.;
+    }
+    h
+}
+
+// Returns the shortest signed distance in degrees between two `f64` angles, like `Hue::diff`.
+pub(crate) const fn diff_degrees_f64(from: f64, to: f64) -> f64 {
+    let d = wrap_degrees_f64(to) - wrap_degrees_f64(from);
+    if d > 180. {
+        d - 360.
+    } else if d <= -180. {
+        d + 360.
+    } else {
+        d
+    }
+}
+
+impl From<f32> for Hue {
+    fn from(degrees: f32) -> Hue {
+        Hue::new(degrees)
+    }
+}
+impl From<Hue> for f32 {
+    fn from(h: Hue) -> f32 {
+        h.0
+    }
+}
+
+impl Add<f32> for Hue {
+    type Output = Hue;
+    fn add(self, degrees: f32) -> Hue {
+        Hue::new(self.0 + degrees)
+    }
+}
+impl Sub<f32> for Hue {
+    type Output = Hue;
+    fn sub(self, degrees: f32) -> Hue {
+        Hue::new(self.0 - degrees)
+    }
+}
+impl Add for Hue {
+    type Output = Hue;
+    fn add(self, other: Hue) -> Hue {
+        Hue::new(self.0 + other.0)
+    }
+}
+impl Sub for Hue {
+    type Output = Hue;
+    fn sub(self, other: Hue) -> Hue {
+        Hue::new(self.0 - other.0)
+    }
+}
+impl AddAssign<f32> for Hue {
+    fn add_assign(&mut self, degrees: f32) {
+        *self = *self + degrees;
+    }
+}
+impl SubAssign<f32> for Hue {
+    fn sub_assign(&mut self, degrees: f32) {
+        *self = *self - degrees;
+    }
+}
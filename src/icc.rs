@@ -0,0 +1,417 @@
+// acolor::icc
+//
+//! Minimal ICC profile support.
+//!
+//! Parses matrix/TRC RGB display profiles, version 2 and 4,
+//! which is what most monitor profiles are.
+//!
+//! # Links
+//! - <https://www.color.org/specification/ICC.1-2022-05.pdf>
+//! - <https://www.color.org/ICC_Minor_Revision_for_Web.pdf>
+//
+// # TOC
+//
+// - IccError
+// - IccCurve
+// - IccProfile
+// - IccTransform
+// - utils
+//
+
+use crate::{
+    math::{mat_mul_vec, powf},
+    srgb::LinearSrgb32,
+    Color,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An error parsing an ICC profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IccError {
+    /// The data is shorter than the profile needs.
+    TooShort,
+    /// The `acsp` file signature is missing.
+    InvalidSignature,
+    /// The data color space is not RGB.
+    UnsupportedColorSpace,
+    /// The profile connection space is not XYZ.
+    UnsupportedPcs,
+    /// A required tag is missing.
+    MissingTag([u8; 4]),
+    /// A tag has an invalid or unsupported type.
+    InvalidTag([u8; 4]),
+    /// The colorant matrix can't be inverted.
+    SingularMatrix,
+}
+
+impl fmt::Display for IccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IccError::TooShort => write!(f, "profile data too short"),
+            IccError::InvalidSignature => write!(f, "invalid profile signature"),
+            IccError::UnsupportedColorSpace => write!(f, "unsupported color space"),
+            IccError::UnsupportedPcs => write!(f, "unsupported profile connection space"),
+            IccError::MissingTag(t) => write!(f, "missing tag {}", TagName(*t)),
+            IccError::InvalidTag(t) => write!(f, "invalid tag {}", TagName(*t)),
+            IccError::SingularMatrix => write!(f, "singular colorant matrix"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IccError {}
+
+// Displays a tag signature.
+struct TagName([u8; 4]);
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{}", b as char)?;
+        }
+        Ok(())
+    }
+}
+
+/* curves */
+
+/// A tone reproduction curve, mapping encoded device values to linear ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IccCurve {
+    /// The identity curve.
+    Identity,
+    /// A pure power curve.
+    Gamma(f32),
+    /// A parametric curve, in the most general form:
+    /// `(a·x + b)^g + e` if `x >= d`, and `c·x + f` otherwise.
+    ///
+    /// The parameters are `[g, a, b, c, d, e, f]`.
+    Parametric([f32; 7]),
+    /// A sampled curve, evenly spaced over `0..=1`.
+    ///
+    /// An empty table is the identity, and a single entry is a constant.
+    Table(Vec<u16>),
+}
+
+impl IccCurve {
+    /// Evaluates the curve, from encoded to linear.
+    pub fn eval(&self, x: f32) -> f32 {
+        match self {
+            IccCurve::Identity => x,
+            IccCurve::Gamma(g) => powf(x.max(0.), *g),
+            IccCurve::Parametric([g, a, b, c, d, e, f]) => {
+                if x >= *d {
+                    powf((a * x + b).max(0.), *g) + e
+                } else {
+                    c * x + f
+                }
+            }
+            IccCurve::Table(t) if t.is_empty() => x,
+            IccCurve::Table(t) if t.len() == 1 => t[0] as f32 / u16::MAX as f32,
+            IccCurve::Table(t) => {
+                let max = (t.len() - 1) as f32;
+                let pos = x.clamp(0., 1.) * max;
+                let i = (pos as usize).min(t.len() - 2);
+                let frac = pos - i as f32;
+                let (y0, y1) = (t[i] as f32, t[i + 1] as f32);
+                (y0 + (y1 - y0) * frac) / u16::MAX as f32
+            }
+        }
+    }
+
+    /// Evaluates the inverse curve, from linear to encoded.
+    ///
+    /// Tables are expected to be monotonically increasing.
+    /// A single entry table returns `0` up to its value and `1` above it.
+    pub fn eval_inverse(&self, y: f32) -> f32 {
+        match self {
+            IccCurve::Identity => y,
+            IccCurve::Table(t) if t.is_empty() => y,
+            IccCurve::Gamma(g) => powf(y.max(0.), 1. / g),
+            IccCurve::Parametric([g, a, b, c, d, e, f]) => {
+                let yd = powf((a * d + b).max(0.), *g) + e;
+                if y >= yd {
+                    (powf((y - e).max(0.), 1. / g) - b) / a
+                } else if *c != 0. {
+                    (y - f) / c
+                } else {
+                    *d
+                }
+            }
+            IccCurve::Table(t) => {
+                let target = y.clamp(0., 1.) * u16::MAX as f32;
+                // the first sample not below the target
+                let hi = t.partition_point(|v| (*v as f32) < target);
+                if hi == 0 {
+                    return 0.;
+                } else if hi == t.len() {
+                    return 1.;
+                }
+                let (y0, y1) = (t[hi - 1] as f32, t[hi] as f32);
+                let frac = if y1 > y0 {
+                    (target - y0) / (y1 - y0)
+                } else {
+                    0.
+                };
+                ((hi - 1) as f32 + frac) / (t.len() - 1) as f32
+            }
+        }
+    }
+
+    // Parses a `curv` or `para` tag type.
+    fn parse(data: &[u8], tag: [u8; 4]) -> Result<IccCurve, IccError> {
+        let err = IccError::InvalidTag(tag);
+        match data.get(0..4) {
+            Some(b"curv") => {
+                let count = read_u32(data, 8).ok_or(err)? as usize;
+                match count {
+                    0 => Ok(IccCurve::Identity),
+                    1 => Ok(IccCurve::Gamma(
+                        read_u16(data, 12).ok_or(err)? as f32 / 256.,
+                    )),
+                    _ => {
+                        let t: Option<Vec<u16>> =
+                            (0..count).map(|i| read_u16(data, 12 + i * 2)).collect();
+                        Ok(IccCurve::Table(t.ok_or(err)?))
+                    }
+                }
+            }
+            Some(b"para") => {
+                let kind = read_u16(data, 8).ok_or(err)?;
+                let n = match kind {
+                    0 => 1,
+                    1 => 3,
+                    2 => 4,
+                    3 => 5,
+                    4 => 7,
+                    _ => return Err(err),
+                };
+                let mut p = [0.; 7];
+                for (i, v) in p.iter_mut().enumerate().take(n) {
+                    *v = read_s15f16(data, 12 + i * 4).ok_or(err)?;
+                }
+                let [g, a, b, c, d, e, f] = p;
+                Ok(match kind {
+                    0 => IccCurve::Gamma(g),
+                    // a zero `a` would make the threshold undefined
+                    _ if a == 0. => return Err(err),
+                    1 => IccCurve::Parametric([g, a, b, 0., -b / a, 0., 0.]),
+                    2 => IccCurve::Parametric([g, a, b, 0., -b / a, c, c]),
+                    3 => IccCurve::Parametric([g, a, b, c, d, 0., 0.]),
+                    _ => IccCurve::Parametric([g, a, b, c, d, e, f]),
+                })
+            }
+            _ => Err(err),
+        }
+    }
+}
+
+/* profile */
+
+/// A parsed matrix/TRC RGB ICC profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    version: (u8, u8),
+    white_point: [f32; 3],
+    matrix: [[f32; 3]; 3],
+    curves: [IccCurve; 3],
+}
+
+impl IccProfile {
+    /// Parses an ICC profile from its binary representation.
+    ///
+    /// # Errors
+    /// Returns an error if the profile is malformed,
+    /// or if it's not an RGB profile with colorant and TRC tags.
+    pub fn parse(data: &[u8]) -> Result<IccProfile, IccError> {
+        if data.len() < 132 {
+            return Err(IccError::TooShort);
+        }
+        if &data[36..40] != b"acsp" {
+            return Err(IccError::InvalidSignature);
+        }
+        if &data[16..20] != b"RGB " {
+            return Err(IccError::UnsupportedColorSpace);
+        }
+        if &data[20..24] != b"XYZ " {
+            return Err(IccError::UnsupportedPcs);
+        }
+        let version = (data[8], data[9] >> 4);
+
+        let count = read_u32(data, 128).ok_or(IccError::TooShort)? as usize;
+        let find = |sig: &[u8; 4]| -> Result<&[u8], IccError> {
+            for i in 0..count {
+                let entry = 132 + i * 12;
+                let found = data.get(entry..entry + 4).ok_or(IccError::TooShort)?;
+                if found == sig {
+                    let offset = read_u32(data, entry + 4).ok_or(IccError::TooShort)? as usize;
+                    let size = read_u32(data, entry + 8).ok_or(IccError::TooShort)? as usize;
+                    let end = offset.checked_add(size).ok_or(IccError::InvalidTag(*sig))?;
+                    return data.get(offset..end).ok_or(IccError::InvalidTag(*sig));
+                }
+            }
+            Err(IccError::MissingTag(*sig))
+        };
+        let xyz = |sig: &[u8; 4]| -> Result<[f32; 3], IccError> {
+            let tag = find(sig)?;
+            let err = IccError::InvalidTag(*sig);
+            if tag.get(0..4) != Some(b"XYZ ") {
+                return Err(err);
+            }
+            Ok([
+                read_s15f16(tag, 8).ok_or(err)?,
+                read_s15f16(tag, 12).ok_or(err)?,
+                read_s15f16(tag, 16).ok_or(err)?,
+            ])
+        };
+        let curve = |sig: &[u8; 4]| IccCurve::parse(find(sig)?, *sig);
+
+        let [r, g, b] = [xyz(b"rXYZ")?, xyz(b"gXYZ")?, xyz(b"bXYZ")?];
+        let matrix = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+        if mat_inverse(matrix).is_none() {
+            return Err(IccError::SingularMatrix);
+        }
+        // the media white point is informative, default to the D50 illuminant
+        let white_point = match xyz(b"wtpt") {
+            Err(IccError::MissingTag(_)) => D50,
+            w => w?,
+        };
+
+        Ok(IccProfile {
+            version,
+            white_point,
+            matrix,
+            curves: [curve(b"rTRC")?, curve(b"gTRC")?, curve(b"bTRC")?],
+        })
+    }
+
+    /// Returns the major and minor version.
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+    /// Returns the media white point, in XYZ.
+    pub fn white_point(&self) -> [f32; 3] {
+        self.white_point
+    }
+    /// Returns the matrix from linear device RGB to the D50 XYZ connection space.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        self.matrix
+    }
+    /// Returns the red, green and blue tone reproduction curves.
+    pub fn curves(&self) -> &[IccCurve; 3] {
+        &self.curves
+    }
+}
+
+/* transform */
+
+/// Converts colors between sRGB and the device space of an [`IccProfile`].
+///
+/// Device values are encoded with the profile curves, in the range `0..=1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccTransform {
+    to_device: [[f32; 3]; 3],
+    from_device: [[f32; 3]; 3],
+    curves: [IccCurve; 3],
+}
+
+impl IccTransform {
+    /// Returns a new transform for the given `profile`.
+    pub fn new(profile: &IccProfile) -> IccTransform {
+        // the matrix was already checked while parsing
+        let profile_inv = mat_inverse(profile.matrix).unwrap_or(IDENTITY);
+        let srgb_inv = mat_inverse(SRGB_TO_XYZ_D50).unwrap_or(IDENTITY);
+        IccTransform {
+            to_device: mat_mul(profile_inv, SRGB_TO_XYZ_D50),
+            from_device: mat_mul(srgb_inv, profile.matrix),
+            curves: profile.curves.clone(),
+        }
+    }
+
+    /// Converts a color to encoded device values.
+    pub fn to_device<C: Color>(&self, color: C) -> [f32; 3] {
+        let c = color.color_to_linear_srgb32();
+        let d = mat_mul_vec(self.to_device, [c.r, c.g, c.b]);
+        [
+            self.curves[0].eval_inverse(d[0]).clamp(0., 1.),
+            self.curves[1].eval_inverse(d[1]).clamp(0., 1.),
+            self.curves[2].eval_inverse(d[2]).clamp(0., 1.),
+        ]
+    }
+
+    /// Converts encoded device values to a linear sRGB color.
+    ///
+    /// Colors outside the sRGB gamut are not clamped.
+    pub fn from_device(&self, device: [f32; 3]) -> LinearSrgb32 {
+        let d = [
+            self.curves[0].eval(device[0]),
+            self.curves[1].eval(device[1]),
+            self.curves[2].eval(device[2]),
+        ];
+        let [r, g, b] = mat_mul_vec(self.from_device, d);
+        LinearSrgb32::new(r, g, b)
+    }
+}
+
+/* utils */
+
+// The D50 illuminant of the profile connection space.
+const D50: [f32; 3] = [0.9642, 1., 0.8249];
+
+const IDENTITY: [[f32; 3]; 3] = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+// Linear sRGB to XYZ, Bradford-adapted to D50.
+const SRGB_TO_XYZ_D50: [[f32; 3]; 3] = [
+    [0.4360747, 0.3850649, 0.1430804],
+    [0.2225045, 0.7168786, 0.0606169],
+    [0.0139322, 0.0971045, 0.7141733],
+];
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+fn read_s15f16(data: &[u8], at: usize) -> Option<f32> {
+    let n = i32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?);
+    Some(n as f32 / 65536.)
+}
+
+fn mat_mul(a: [[f32; 3]; 3], b: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let mut m = [[0.; 3]; 3];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    m
+}
+
+fn mat_inverse(m: [[f32; 3]; 3]) -> Option<[[f32; 3]; 3]> {
+    let c00 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+    let c01 = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+    let c02 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+    let det = m[0][0] * c00 + m[0][1] * c01 + m[0][2] * c02;
+    if !det.is_normal() {
+        return None;
+    }
+    let d = 1. / det;
+    Some([
+        [
+            c00 * d,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * d,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * d,
+        ],
+        [
+            c01 * d,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * d,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * d,
+        ],
+        [
+            c02 * d,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * d,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * d,
+        ],
+    ])
+}
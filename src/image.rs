@@ -0,0 +1,453 @@
+// acolor::image
+//
+//! Image buffers.
+//!
+//! [`ImageBuf`] is a shared container for the [`batch`][crate::batch],
+//! [`dither`][crate::dither] and [`quantize`][crate::quantize] functionality,
+//! and [`IndexedImage`] pairs a [`Palette`] with the indices of its colors.
+//!
+//! # Example
+//! ```
+//! # #[cfg(any(feature = "std", feature = "no_std"))] {
+//! use acolor::{image::ImageBuf, oklab::Oklab32, srgb::Srgb8};
+//!
+//! let img = ImageBuf::from_fn(4, 2, |x, y| Srgb8::new(x as u8 * 60, y as u8 * 200, 0));
+//! let lab: ImageBuf<Oklab32> = img.convert();
+//! let back: ImageBuf<Srgb8> = lab.convert();
+//! assert_eq![back, img];
+//! assert_eq![img.rows().nth(1).unwrap()[0], Srgb8::new(0, 200, 0)];
+//! # }
+//! ```
+//
+// # TOC
+//
+// - ImageBuf
+// - Palette
+// - IndexedImage
+//
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+use crate::{
+    difference::{nearest, ColorDifference},
+    dither::floyd_steinberg,
+    quantize::OctreeQuantizer,
+    Color,
+};
+use crate::{srgb::Srgb8, FromColor, LengthError};
+use alloc::{borrow::Cow, vec, vec::Vec};
+use core::slice::{ChunksExact, ChunksExactMut};
+
+/// An image made of `width` × `height` pixels of type `C`, stored row by row.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ImageBuf<C> {
+    width: usize,
+    height: usize,
+    pixels: Vec<C>,
+}
+
+/// # Constructors
+impl<C> ImageBuf<C> {
+    /// Returns a new image from its `pixels`, stored row by row.
+    ///
+    /// # Errors
+    /// Returns [`LengthError`] if the number of `pixels` is not `width` × `height`.
+    pub fn new(width: usize, height: usize, pixels: Vec<C>) -> Result<ImageBuf<C>, LengthError> {
+        let expected = width.saturating_mul(height);
+        if pixels.len() != expected {
+            return Err(LengthError {
+                expected,
+                found: pixels.len(),
+            });
+        }
+        Ok(ImageBuf {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Returns a new image with all the pixels set to `color`.
+    ///
+    /// # Panics
+    /// Panics if `width` × `height` overflows.
+    pub fn filled(width: usize, height: usize, color: C) -> ImageBuf<C>
+    where
+        C: Clone,
+    {
+        let len = width.checked_mul(height).expect("image size overflow");
+        ImageBuf {
+            width,
+            height,
+            pixels: vec![color; len],
+        }
+    }
+
+    /// Returns a new image with each pixel computed from its `(x, y)` coordinates.
+    ///
+    /// # Panics
+    /// Panics if `width` × `height` overflows.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> C) -> Self {
+        let len = width.checked_mul(height).expect("image size overflow");
+        let mut pixels = Vec::with_capacity(len);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(f(x, y));
+            }
+        }
+        ImageBuf {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// # Getters
+impl<C> ImageBuf<C> {
+    /// Returns the width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// Returns the height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Returns the pixels, stored row by row.
+    pub fn pixels(&self) -> &[C] {
+        &self.pixels
+    }
+    /// Returns the pixels mutably, stored row by row.
+    pub fn pixels_mut(&mut self) -> &mut [C] {
+        &mut self.pixels
+    }
+    /// Returns the vector of pixels, stored row by row.
+    pub fn into_pixels(self) -> Vec<C> {
+        self.pixels
+    }
+
+    /// Returns the pixel at `(x, y)`, or `None` if it's out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<&C> {
+        if x < self.width && y < self.height {
+            self.pixels.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+    /// Returns the pixel at `(x, y)` mutably, or `None` if it's out of bounds.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut C> {
+        if x < self.width && y < self.height {
+            self.pixels.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the rows.
+    pub fn rows(&self) -> ChunksExact<'_, C> {
+        self.pixels.chunks_exact(self.width.max(1))
+    }
+    /// Returns an iterator over the rows, mutably.
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, C> {
+        self.pixels.chunks_exact_mut(self.width.max(1))
+    }
+}
+
+/// # Operations
+impl<C: Copy> ImageBuf<C> {
+    /// Returns a new image with `f` applied to each pixel.
+    pub fn map<D>(&self, f: impl FnMut(C) -> D) -> ImageBuf<D> {
+        ImageBuf {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().copied().map(f).collect(),
+        }
+    }
+
+    /// Applies `f` to each pixel in place.
+    pub fn map_in_place(&mut self, mut f: impl FnMut(C) -> C) {
+        for c in self.pixels.iter_mut() {
+            *c = f(*c);
+        }
+    }
+
+    /// Returns a new image with each pixel converted into the color type `D`.
+    pub fn convert<D: FromColor<C>>(&self) -> ImageBuf<D> {
+        self.map(D::from_color)
+    }
+}
+
+/* indexed */
+
+/// The maximum number of colors of a [`Palette`].
+const MAX_PALETTE_LEN: usize = 256;
+
+/// A palette of up to 256 colors, indexable by a [`u8`].
+///
+/// The colors can be either owned or `'static`, which allows constant palettes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    colors: Cow<'static, [Srgb8]>,
+}
+
+impl Palette {
+    /// Returns a new palette from its `colors`.
+    ///
+    /// # Errors
+    /// Returns [`LengthError`] if there are more than 256 colors.
+    pub fn new(colors: Vec<Srgb8>) -> Result<Palette, LengthError> {
+        if colors.len() > MAX_PALETTE_LEN {
+            return Err(LengthError {
+                expected: MAX_PALETTE_LEN,
+                found: colors.len(),
+            });
+        }
+        Ok(Palette {
+            colors: Cow::Owned(colors),
+        })
+    }
+
+    /// Returns a new palette from `'static` colors, usable in const contexts.
+    ///
+    /// # Panics
+    /// Panics if there are more than 256 colors.
+    pub const fn from_static(colors: &'static [Srgb8]) -> Palette {
+        assert![colors.len() <= MAX_PALETTE_LEN, "more than 256 colors"];
+        Palette {
+            colors: Cow::Borrowed(colors),
+        }
+    }
+
+    /// Returns the colors.
+    pub fn colors(&self) -> &[Srgb8] {
+        &self.colors
+    }
+    /// Returns the number of colors.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+    /// Returns `true` if there are no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+    /// Returns the color at `index`, if it exists.
+    pub fn get(&self, index: u8) -> Option<Srgb8> {
+        self.colors.get(index as usize).copied()
+    }
+
+    /// Returns the index of the nearest color to `color`, according to the `metric`,
+    /// or `None` if the palette is empty.
+    ///
+    /// On ties the first color wins.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{difference::{EuclidOklab, WeightedOklch}, image::Palette, srgb::Srgb8};
+    ///
+    /// let palette = Palette::new(vec![Srgb8::new(40, 40, 40), Srgb8::new(40, 20, 160)]).unwrap();
+    /// let text = Srgb8::new(20, 10, 90);
+    /// assert_eq![palette.nearest(&EuclidOklab, text), Some(1)];
+    /// assert_eq![palette.nearest(&WeightedOklch::new(4., 1., 0.25), text), Some(0)];
+    /// ```
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    #[cfg_attr(
+        feature = "nightly",
+        doc(cfg(any(feature = "std", feature = "no_std")))
+    )]
+    pub fn nearest<M: ColorDifference, C: Color>(&self, metric: &M, color: C) -> Option<u8> {
+        // the length is at most 256
+        nearest(metric, color, &self.colors).map(|i| i as u8)
+    }
+
+    /// Returns a new palette with the colors reordered to minimize the total
+    /// difference between adjacent colors, according to the `metric`.
+    ///
+    /// The path starts from the darkest color and greedily picks the nearest one
+    /// not yet visited, then it's improved with 2-opt until no segment reversal
+    /// shortens it. The result is a good approximation, but not always the optimum.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{difference::Ciede2000, image::Palette, srgb::Srgb8};
+    ///
+    /// let gray = |v| Srgb8::new(v, v, v);
+    /// let palette = Palette::new(vec![gray(200), gray(0), gray(255), gray(100)]).unwrap();
+    /// let sorted = palette.sorted_by_difference(&Ciede2000);
+    /// assert_eq![sorted.colors(), [gray(0), gray(100), gray(200), gray(255)]];
+    /// ```
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    #[cfg_attr(
+        feature = "nightly",
+        doc(cfg(any(feature = "std", feature = "no_std")))
+    )]
+    pub fn sorted_by_difference<M: ColorDifference>(&self, metric: &M) -> Palette {
+        let n = self.colors.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let mut dist = vec![0.; n * n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let d = metric.difference(self.colors[i], self.colors[j]);
+                dist[i * n + j] = d;
+                dist[j * n + i] = d;
+            }
+        }
+        let d = |a: usize, b: usize| dist[a * n + b];
+
+        // greedy nearest neighbor, from the darkest color
+        let lightness = |i: usize| self.colors[i].to_oklab32().l;
+        let start = (0..n).fold(0, |a, b| if lightness(b) < lightness(a) { b } else { a });
+        let mut path = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        let mut current = start;
+        for _ in 0..n {
+            visited[current] = true;
+            path.push(current);
+            let next = (0..n)
+                .filter(|j| !visited[*j])
+                .min_by(|a, b| d(current, *a).total_cmp(&d(current, *b)));
+            if let Some(next) = next {
+                current = next;
+            }
+        }
+
+        // 2-opt over the open path, reversing `path[i..=j]`
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..n - 1 {
+                for j in i + 1..n {
+                    let (a, b) = (path[i], path[j]);
+                    let mut delta = 0.;
+                    if i > 0 {
+                        delta += d(path[i - 1], b) - d(path[i - 1], a);
+                    }
+                    if j < n - 1 {
+                        delta += d(a, path[j + 1]) - d(b, path[j + 1]);
+                    }
+                    if delta < -1e-6 {
+                        path[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+        Palette {
+            colors: Cow::Owned(path.into_iter().map(|i| self.colors[i]).collect()),
+        }
+    }
+}
+
+/// An image made of palette indices, as used by formats like GIF or PNG8.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexedImage {
+    width: usize,
+    height: usize,
+    palette: Palette,
+    indices: Vec<u8>,
+}
+
+/// # Constructors
+impl IndexedImage {
+    /// Returns a new indexed image from its `palette` and `indices`, stored row by row.
+    ///
+    /// # Errors
+    /// Returns [`LengthError`] if the number of `indices` is not `width` × `height`.
+    pub fn new(
+        width: usize,
+        height: usize,
+        palette: Palette,
+        indices: Vec<u8>,
+    ) -> Result<IndexedImage, LengthError> {
+        let expected = width.saturating_mul(height);
+        if indices.len() != expected {
+            return Err(LengthError {
+                expected,
+                found: indices.len(),
+            });
+        }
+        Ok(IndexedImage {
+            width,
+            height,
+            palette,
+            indices,
+        })
+    }
+
+    /// Encodes an `image` using a palette of up to `max_colors`.
+    ///
+    /// The palette is built with an [`OctreeQuantizer`], and the image is reduced
+    /// to it with [`floyd_steinberg`] dithering. A `max_colors` out of `1..=256` is clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{image::{ImageBuf, IndexedImage}, srgb::Srgb8};
+    ///
+    /// let img = ImageBuf::from_fn(8, 8, |x, y| Srgb8::new(x as u8 * 32, y as u8 * 32, 128));
+    /// let indexed = IndexedImage::encode(&img, 16);
+    /// assert![indexed.palette().len() <= 16];
+    /// assert_eq![indexed.decode().width(), 8];
+    /// ```
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    #[cfg_attr(
+        feature = "nightly",
+        doc(cfg(any(feature = "std", feature = "no_std")))
+    )]
+    pub fn encode(image: &ImageBuf<Srgb8>, max_colors: usize) -> IndexedImage {
+        let mut quantizer = OctreeQuantizer::new(max_colors.clamp(1, MAX_PALETTE_LEN));
+        quantizer.extend(image.pixels().iter().copied());
+        let palette = Palette {
+            colors: Cow::Owned(quantizer.palette()),
+        };
+        let indices = if palette.is_empty() {
+            Vec::new()
+        } else {
+            let indices = floyd_steinberg(image.pixels(), image.width(), palette.colors());
+            indices.into_iter().map(|i| i as u8).collect()
+        };
+        IndexedImage {
+            width: image.width(),
+            height: image.height(),
+            palette,
+            indices,
+        }
+    }
+}
+
+/// # Getters
+impl IndexedImage {
+    /// Returns the width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// Returns the height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Returns the palette.
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+    /// Returns the palette indices, stored row by row.
+    pub fn indices(&self) -> &[u8] {
+        &self.indices
+    }
+}
+
+/// # Operations
+impl IndexedImage {
+    /// Decodes the image, expanding each index into its palette color.
+    ///
+    /// Indices out of the palette are decoded as black.
+    pub fn decode(&self) -> ImageBuf<Srgb8> {
+        ImageBuf {
+            width: self.width,
+            height: self.height,
+            pixels: self
+                .indices
+                .iter()
+                .map(|i| self.palette.get(*i).unwrap_or_default())
+                .collect(),
+        }
+    }
+}
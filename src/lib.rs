@@ -41,15 +41,163 @@ mod external; // trait impls on external types
 #[cfg(test)]
 mod tests;
 
+pub mod any;
+pub mod batch;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod cie;
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(all(feature = "alloc", any(feature = "std", feature = "no_std"))))
+)]
+pub mod codegen;
 mod color;
+#[cfg(any(feature = "std", feature = "no_std"))]
+mod config;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod contrast;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod cvd;
+pub mod depth;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod difference;
+pub mod display;
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(all(feature = "alloc", any(feature = "std", feature = "no_std"))))
+)]
+pub mod dither;
+#[cfg(feature = "fixed")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "fixed")))]
+pub mod fixed;
 mod gamma;
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(all(feature = "alloc", any(feature = "std", feature = "no_std"))))
+)]
+pub mod gradient;
+pub mod hue;
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(all(feature = "alloc", any(feature = "std", feature = "no_std"))))
+)]
+pub mod icc;
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub mod image;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod lighting;
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub mod lut;
+mod math;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod mix;
 pub mod oklab;
+mod ops;
+pub mod parse;
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub mod quantize;
 pub mod srgb;
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub mod swatch;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod theme;
+#[cfg(feature = "themes")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "themes")))]
+pub mod themes;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod tonal;
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub mod transfer;
+pub mod yuv;
 
-pub use {color::*, gamma::*};
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub use config::*;
+pub use {
+    color::*,
+    gamma::*,
+    ops::{Channel, LengthError},
+};
 
 /// All items are reexported here.
 pub mod all {
     #[doc(inline)]
-    pub use super::{color::Color, gamma::*, oklab::*, srgb::*};
+    #[cfg(feature = "fixed")]
+    pub use super::fixed::*;
+    #[doc(inline)]
+    #[cfg(feature = "alloc")]
+    pub use super::quantize::*;
+    #[doc(inline)]
+    pub use super::{
+        any::*,
+        batch::*,
+        color::{Color, FromColor, IntoColor},
+        depth::*,
+        display::*,
+        gamma::*,
+        hue::*,
+        oklab::*,
+        ops::{Channel, LengthError},
+        parse::*,
+        srgb::*,
+        yuv::*,
+    };
+    #[doc(inline)]
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    pub use super::{
+        cie::*, config::*, contrast::*, cvd::*, difference::*, lighting::*, mix::*, theme::*,
+        tonal::*, transfer::*,
+    };
+    #[doc(inline)]
+    #[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+    pub use super::{codegen::*, dither::*, gradient::*, icc::*};
+    #[doc(inline)]
+    #[cfg(feature = "alloc")]
+    pub use super::{image::*, lut::*, swatch::*};
 }
@@ -0,0 +1,124 @@
+// acolor::lighting
+//
+//! Color temperature and scene lighting.
+//!
+//! # Example
+//! ```
+//! use acolor::{lighting::Lighting, srgb::LinearSrgb32};
+//!
+//! let tungsten = Lighting::Tungsten.white_point();
+//! assert![tungsten.r == 1. && tungsten.b < 0.5];
+//! assert_eq![tungsten, LinearSrgb32::from_kelvin(2700.)];
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/Planckian_locus#Approximation>
+//
+// # TOC
+//
+// - kelvin
+// - Lighting
+//
+
+use crate::{math::mat_mul_vec, parse::matrices::XYZ_D65_TO_SRGB, srgb::LinearSrgb32};
+
+/* kelvin */
+
+/// The minimum supported color temperature, in kelvins.
+pub const KELVIN_MIN: f32 = 1667.;
+/// The maximum supported color temperature, in kelvins.
+pub const KELVIN_MAX: f32 = 25_000.;
+
+/// Returns the CIE 1931 `xy` chromaticity of a black body at the given temperature.
+///
+/// Uses the cubic spline approximation of Kim et al.
+/// The temperature is clamped to [`KELVIN_MIN`]`..=`[`KELVIN_MAX`].
+pub fn kelvin_to_xy(kelvin: f32) -> [f32; 2] {
+    let t = kelvin.clamp(KELVIN_MIN, KELVIN_MAX);
+    let (t1, t2, t3) = (1e3 / t, 1e6 / (t * t), 1e9 / (t * t * t));
+    let x = if t <= 4000. {
+        -0.2661239 * t3 - 0.2343589 * t2 + 0.8776956 * t1 + 0.179910
+    } else {
+        -3.0258469 * t3 + 2.1070379 * t2 + 0.2226347 * t1 + 0.240390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222. {
+        -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+    } else if t <= 4000. {
+        -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+    } else {
+        3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+    };
+    [x, y]
+}
+
+/// # Color temperature
+impl LinearSrgb32 {
+    /// Returns the color of a black body at the given temperature in kelvins.
+    ///
+    /// The color is normalized so that its maximum component is `1`, and the components
+    /// out of the sRGB gamut are clamped to `0`. The temperature is clamped to
+    /// [`KELVIN_MIN`]`..=`[`KELVIN_MAX`].
+    pub fn from_kelvin(kelvin: f32) -> LinearSrgb32 {
+        let [x, y] = kelvin_to_xy(kelvin);
+        let xyz = [x / y, 1., (1. - x - y) / y];
+        let rgb = mat_mul_vec(XYZ_D65_TO_SRGB, xyz).map(|c| c.max(0.));
+        let max = rgb[0].max(rgb[1]).max(rgb[2]);
+        let [r, g, b] = rgb.map(|c| c / max);
+        LinearSrgb32::new(r, g, b)
+    }
+}
+
+/* presets */
+
+/// Common scene lighting presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lighting {
+    /// A candle flame, at 1850 K.
+    Candle,
+    /// A tungsten incandescent bulb, at 2700 K.
+    Tungsten,
+    /// A halogen lamp, at 3200 K.
+    Halogen,
+    /// Average daylight, the CIE D65 standard illuminant at 6504 K.
+    DaylightD65,
+    /// An overcast sky, at 7000 K.
+    Overcast,
+    /// Open shade under a clear sky, at 8000 K.
+    Shade,
+}
+
+impl Lighting {
+    /// All the lighting presets, from the warmest to the coolest.
+    pub const ALL: [Lighting; 6] = [
+        Lighting::Candle,
+        Lighting::Tungsten,
+        Lighting::Halogen,
+        Lighting::DaylightD65,
+        Lighting::Overcast,
+        Lighting::Shade,
+    ];
+
+    /// Returns the correlated color temperature, in kelvins.
+    pub const fn kelvin(self) -> f32 {
+        match self {
+            Lighting::Candle => 1850.,
+            Lighting::Tungsten => 2700.,
+            Lighting::Halogen => 3200.,
+            Lighting::DaylightD65 => 6504.,
+            Lighting::Overcast => 7000.,
+            Lighting::Shade => 8000.,
+        }
+    }
+
+    /// Returns the white point, normalized so that its maximum component is `1`.
+    ///
+    /// D65 is the white point of sRGB, and it's not on the black body locus,
+    /// so it returns pure white instead of [`LinearSrgb32::from_kelvin`].
+    pub fn white_point(self) -> LinearSrgb32 {
+        match self {
+            Lighting::DaylightD65 => LinearSrgb32::new(1., 1., 1.),
+            _ => LinearSrgb32::from_kelvin(self.kelvin()),
+        }
+    }
+}
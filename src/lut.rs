@@ -0,0 +1,362 @@
+// acolor::lut
+//
+//! 3D lookup tables.
+//!
+//! Supports parsing the Adobe `.cube` format and applying the table to colors
+//! using trilinear or tetrahedral interpolation.
+//!
+//! # Links
+//! - <https://web.archive.org/web/20220215173646/https://wwwimages2.adobe.com/content/dam/acom/en/products/speedgrade/cc/pdfs/cube-lut-specification-1.0.pdf>
+//
+
+use crate::srgb::Srgb32;
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// The interpolation used when applying a [`Lut3d`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LutInterpolation {
+    /// Interpolates between the 8 surrounding lattice points.
+    #[default]
+    Trilinear,
+    /// Interpolates between the 4 vertices of the enclosing tetrahedron.
+    ///
+    /// It's cheaper and preserves the neutral axis better than trilinear.
+    Tetrahedral,
+}
+
+/// An error parsing or building a [`Lut3d`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CubeError {
+    /// The `LUT_3D_SIZE` keyword is missing.
+    MissingSize,
+    /// The size is smaller than `2` or too big.
+    InvalidSize,
+    /// The file describes a 1D LUT, which is not supported.
+    Unsupported1d,
+    /// Unknown keyword or malformed line, at the given line number.
+    InvalidLine(usize),
+    /// The number of table entries doesn't match the size.
+    WrongEntryCount {
+        /// The expected number of entries.
+        expected: usize,
+        /// The found number of entries.
+        found: usize,
+    },
+}
+
+impl fmt::Display for CubeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CubeError::MissingSize => write!(f, "missing LUT_3D_SIZE"),
+            CubeError::InvalidSize => write!(f, "invalid LUT size"),
+            CubeError::Unsupported1d => write!(f, "1D LUTs are not supported"),
+            CubeError::InvalidLine(n) => write!(f, "invalid line {n}"),
+            CubeError::WrongEntryCount { expected, found } => {
+                write!(f, "expected {expected} table entries, found {found}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CubeError {}
+
+/// A 3D lookup table mapping RGB triplets to RGB triplets.
+///
+/// The table is stored with the red index changing fastest, as in `.cube` files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    title: Option<String>,
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    table: Vec<[f32; 3]>,
+    interpolation: LutInterpolation,
+}
+
+/// The maximum supported size per dimension.
+const MAX_SIZE: usize = 256;
+
+/// # Constructors
+impl Lut3d {
+    /// Returns a new LUT from a `table` of `size`³ entries, with the red index changing fastest.
+    ///
+    /// The domain is `0..=1`.
+    ///
+    /// # Errors
+    /// Returns an error if `size` is not in `2..=256`
+    /// or if the table doesn't have `size`³ entries.
+    pub fn new(size: usize, table: Vec<[f32; 3]>) -> Result<Lut3d, CubeError> {
+        if !(2..=MAX_SIZE).contains(&size) {
+            return Err(CubeError::InvalidSize);
+        }
+        let expected = size * size * size;
+        if table.len() != expected {
+            return Err(CubeError::WrongEntryCount {
+                expected,
+                found: table.len(),
+            });
+        }
+        Ok(Lut3d {
+            title: None,
+            size,
+            domain_min: [0.; 3],
+            domain_max: [1.; 3],
+            table,
+            interpolation: LutInterpolation::default(),
+        })
+    }
+
+    /// Returns a new LUT of the given `size` that leaves colors unchanged.
+    ///
+    /// # Errors
+    /// Returns an error if `size` is not in `2..=256`.
+    pub fn identity(size: usize) -> Result<Lut3d, CubeError> {
+        if !(2..=MAX_SIZE).contains(&size) {
+            return Err(CubeError::InvalidSize);
+        }
+        let max = (size - 1) as f32;
+        let mut table = Vec::with_capacity(size * size * size);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    table.push([r as f32 / max, g as f32 / max, b as f32 / max]);
+                }
+            }
+        }
+        Lut3d::new(size, table)
+    }
+
+    /// Parses a LUT in the Adobe `.cube` text format.
+    ///
+    /// Supports the `TITLE`, `LUT_3D_SIZE`, `DOMAIN_MIN`, `DOMAIN_MAX`
+    /// and `LUT_3D_INPUT_RANGE` keywords.
+    ///
+    /// # Errors
+    /// Returns an error if the text is malformed or describes a 1D LUT.
+    pub fn parse_cube(text: &str) -> Result<Lut3d, CubeError> {
+        let mut title = None;
+        let mut size = None;
+        let mut domain_min = [0.; 3];
+        let mut domain_max = [1.; 3];
+        let mut table = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let n = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let Some(first) = words.next() else { continue };
+
+            match first {
+                "TITLE" => {
+                    let rest = line["TITLE".len()..].trim();
+                    title = Some(String::from(rest.trim_matches('"')));
+                }
+                "LUT_3D_SIZE" => {
+                    let s = words.next().and_then(|w| w.parse::<usize>().ok());
+                    let s = s.ok_or(CubeError::InvalidLine(n))?;
+                    if !(2..=MAX_SIZE).contains(&s) {
+                        return Err(CubeError::InvalidSize);
+                    }
+                    size = Some(s);
+                    table.reserve(s * s * s);
+                }
+                "LUT_1D_SIZE" => return Err(CubeError::Unsupported1d),
+                "DOMAIN_MIN" => domain_min = parse_triplet(words, n)?,
+                "DOMAIN_MAX" => domain_max = parse_triplet(words, n)?,
+                "LUT_3D_INPUT_RANGE" => {
+                    let [min, max] = parse_floats::<2>(words, n)?;
+                    domain_min = [min; 3];
+                    domain_max = [max; 3];
+                }
+                _ => {
+                    let words = core::iter::once(first).chain(words);
+                    table.push(parse_triplet(words, n)?);
+                }
+            }
+        }
+
+        let size = size.ok_or(CubeError::MissingSize)?;
+        let mut lut = Lut3d::new(size, table)?;
+        lut.title = title;
+        lut.domain_min = domain_min;
+        lut.domain_max = domain_max;
+        Ok(lut)
+    }
+
+    /// Returns the same LUT using the given `interpolation`.
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: LutInterpolation) -> Lut3d {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+/// # Getters
+impl Lut3d {
+    /// Returns the title, if any.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+    /// Returns the number of lattice points per dimension.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    /// Returns the minimum and maximum input values.
+    pub fn domain(&self) -> ([f32; 3], [f32; 3]) {
+        (self.domain_min, self.domain_max)
+    }
+    /// Returns the table entries, with the red index changing fastest.
+    pub fn table(&self) -> &[[f32; 3]] {
+        &self.table
+    }
+    /// Returns the interpolation used.
+    pub fn interpolation(&self) -> LutInterpolation {
+        self.interpolation
+    }
+}
+
+/// # Operations
+impl Lut3d {
+    /// Applies the LUT to a color.
+    ///
+    /// Grading LUTs usually expect gamma encoded values.
+    #[inline]
+    pub fn apply(&self, c: Srgb32) -> Srgb32 {
+        Srgb32::from_array(self.apply_array([c.r, c.g, c.b]))
+    }
+
+    /// Applies the LUT in place to a slice of colors.
+    pub fn apply_slice(&self, colors: &mut [Srgb32]) {
+        for c in colors {
+            *c = self.apply(*c);
+        }
+    }
+
+    /// Applies the LUT to an RGB triplet.
+    ///
+    /// Inputs outside the domain are clamped to it.
+    pub fn apply_array(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max = (self.size - 1) as f32;
+
+        // lattice coordinates
+        let mut base = [0; 3];
+        let mut frac = [0.; 3];
+        for i in 0..3 {
+            let range = self.domain_max[i] - self.domain_min[i];
+            let mut x = (rgb[i] - self.domain_min[i]) / range * max;
+            if x.is_nan() || x < 0. {
+                x = 0.;
+            } else if x > max {
+                x = max;
+            }
+            let b = (x as usize).min(self.size - 2);
+            base[i] = b;
+            frac[i] = x - b as f32;
+        }
+
+        match self.interpolation {
+            LutInterpolation::Trilinear => self.trilinear(base, frac),
+            LutInterpolation::Tetrahedral => self.tetrahedral(base, frac),
+        }
+    }
+
+    /* private helpers */
+
+    // Returns the entry at the lattice point `base` offset by `(dr, dg, db)`.
+    #[inline]
+    fn at(&self, base: [usize; 3], dr: usize, dg: usize, db: usize) -> [f32; 3] {
+        let s = self.size;
+        self.table[(base[0] + dr) + (base[1] + dg) * s + (base[2] + db) * s * s]
+    }
+
+    fn trilinear(&self, base: [usize; 3], [fr, fg, fb]: [f32; 3]) -> [f32; 3] {
+        let c000 = self.at(base, 0, 0, 0);
+        let c100 = self.at(base, 1, 0, 0);
+        let c010 = self.at(base, 0, 1, 0);
+        let c110 = self.at(base, 1, 1, 0);
+        let c001 = self.at(base, 0, 0, 1);
+        let c101 = self.at(base, 1, 0, 1);
+        let c011 = self.at(base, 0, 1, 1);
+        let c111 = self.at(base, 1, 1, 1);
+
+        let mut out = [0.; 3];
+        for i in 0..3 {
+            let c00 = lerp(c000[i], c100[i], fr);
+            let c10 = lerp(c010[i], c110[i], fr);
+            let c01 = lerp(c001[i], c101[i], fr);
+            let c11 = lerp(c011[i], c111[i], fr);
+            let c0 = lerp(c00, c10, fg);
+            let c1 = lerp(c01, c11, fg);
+            out[i] = lerp(c0, c1, fb);
+        }
+        out
+    }
+
+    fn tetrahedral(&self, base: [usize; 3], [fr, fg, fb]: [f32; 3]) -> [f32; 3] {
+        let c000 = self.at(base, 0, 0, 0);
+        let c111 = self.at(base, 1, 1, 1);
+
+        // selects the tetrahedron and its 2 intermediate vertices,
+        // along with the weights of the 4 vertices.
+        let (a, b, w) = if fr > fg {
+            if fg > fb {
+                let w = [1. - fr, fr - fg, fg - fb, fb];
+                (self.at(base, 1, 0, 0), self.at(base, 1, 1, 0), w)
+            } else if fr > fb {
+                let w = [1. - fr, fr - fb, fb - fg, fg];
+                (self.at(base, 1, 0, 0), self.at(base, 1, 0, 1), w)
+            } else {
+                let w = [1. - fb, fb - fr, fr - fg, fg];
+                (self.at(base, 0, 0, 1), self.at(base, 1, 0, 1), w)
+            }
+        } else if fb > fg {
+            let w = [1. - fb, fb - fg, fg - fr, fr];
+            (self.at(base, 0, 0, 1), self.at(base, 0, 1, 1), w)
+        } else if fb > fr {
+            let w = [1. - fg, fg - fb, fb - fr, fr];
+            (self.at(base, 0, 1, 0), self.at(base, 0, 1, 1), w)
+        } else {
+            let w = [1. - fg, fg - fr, fr - fb, fb];
+            (self.at(base, 0, 1, 0), self.at(base, 1, 1, 0), w)
+        };
+
+        let mut out = [0.; 3];
+        for i in 0..3 {
+            out[i] = w[0] * c000[i] + w[1] * a[i] + w[2] * b[i] + w[3] * c111[i];
+        }
+        out
+    }
+}
+
+#[inline(always)]
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// Parses exactly `N` floats from `words`.
+fn parse_floats<'a, const N: usize>(
+    mut words: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<[f32; N], CubeError> {
+    let mut out = [0.; N];
+    for v in out.iter_mut() {
+        let w = words.next().ok_or(CubeError::InvalidLine(line))?;
+        *v = w.parse().map_err(|_| CubeError::InvalidLine(line))?;
+    }
+    if words.next().is_some() {
+        return Err(CubeError::InvalidLine(line));
+    }
+    Ok(out)
+}
+
+fn parse_triplet<'a>(
+    words: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<[f32; 3], CubeError> {
+    parse_floats::<3>(words, line)
+}
@@ -0,0 +1,363 @@
+// acolor::math
+//
+//! Float functions.
+//!
+//! This is the single place where the float backend is selected:
+//! the runtime functions use `std`, or else `micromath` if enabled, or else `libm`.
+//! The `const` ones use approximations that only need basic arithmetic.
+//!
+//! Note that micromath's `powf` is much rougher than the others: decoding sRGB
+//! can be off by up to `0.15`, and an 8-bit color can come back from linear sRGB
+//! up to 32 steps off, and from Oklab up to half the range off.
+//
+
+#[cfg(all(feature = "micromath", not(feature = "std")))]
+use micromath::F32;
+
+// The float type of the intermediate results of the conversions with matrices,
+// which is `f64` with the `precise` feature, and `f32` otherwise.
+#[cfg(feature = "precise")]
+pub(crate) type Float = f64;
+#[cfg(not(feature = "precise"))]
+pub(crate) type Float = f32;
+
+// Narrows a `Float` into an `f32`.
+#[inline]
+#[allow(clippy::unnecessary_cast, reason = "`Float` can be `f32`")]
+pub(crate) const fn float_to_f32(x: Float) -> f32 {
+    x as f32
+}
+
+// Returns the cube root of a `Float`.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn cbrt_float(x: Float) -> Float {
+    #[cfg(not(feature = "precise"))]
+    return cbrt(x);
+    #[cfg(all(feature = "precise", feature = "std"))]
+    return x.cbrt();
+    #[cfg(all(feature = "precise", not(feature = "std")))]
+    return libm::cbrt(x);
+}
+
+// Raises a `Float` to a power.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn powf_float(x: Float, e: Float) -> Float {
+    #[cfg(not(feature = "precise"))]
+    return powf(x, e);
+    #[cfg(all(feature = "precise", feature = "std"))]
+    return x.powf(e);
+    #[cfg(all(feature = "precise", not(feature = "std")))]
+    return libm::pow(x, e);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn powf(x: f32, e: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.powf(e);
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).powf(F32(e)).0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::powf(x, e);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn sqrt(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.sqrt();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).sqrt().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::sqrtf(x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn ln(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.ln();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).ln().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::logf(x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn exp(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.exp();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).exp().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::expf(x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn log10(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.log10();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).log10().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::log10f(x);
+}
+
+#[inline]
+#[cfg(all(not(feature = "precise"), any(feature = "std", feature = "no_std")))]
+pub(crate) fn cbrt(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.cbrt();
+    // micromath has no cube root, so it's derived from the power, keeping the sign
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return if x == 0. {
+        x
+    } else {
+        F32(x).abs().powf(F32(1. / 3.)).copysign(F32(x)).0
+    };
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::cbrtf(x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn hypot(x: f32, y: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.hypot(y);
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).hypot(F32(y)).0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::hypotf(x, y);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return y.atan2(x);
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(y).atan2(F32(x)).0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::atan2f(y, x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn sin(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.sin();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).sin().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::sinf(x);
+}
+
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn cos(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    return x.cos();
+    #[cfg(all(feature = "micromath", not(feature = "std")))]
+    return F32(x).cos().0;
+    #[cfg(all(not(feature = "micromath"), not(feature = "std")))]
+    return libm::cosf(x);
+}
+
+// Returns the absolute value, by clearing the sign bit, without needing a backend.
+#[inline]
+#[cfg(feature = "approx")]
+pub(crate) fn abs(x: f32) -> f32 {
+    f32::from_bits(x.to_bits() & 0x7fff_ffff)
+}
+
+// Returns `1.0` or `-1.0` with the sign of `x`, or NaN if `x` is NaN, without needing a backend.
+#[inline]
+#[cfg(feature = "approx")]
+pub(crate) fn signum(x: f32) -> f32 {
+    if x.is_nan() {
+        f32::NAN
+    } else {
+        f32::from_bits(1f32.to_bits() | (x.to_bits() & 0x8000_0000))
+    }
+}
+
+/* const */
+
+use core::f32::consts::{FRAC_PI_2, PI};
+
+// Scales a `u8` into an `f32` in `0..=1`, like `Unorm8::to_f32`.
+#[inline]
+pub(crate) const fn u8_to_f32(n: u8) -> f32 {
+    n as f32 / 255.
+}
+
+// Scales an `f32` in `0..=1` into a `u8`, like `Unorm8::from_f32`.
+#[inline]
+pub(crate) const fn f32_to_u8(f: f32) -> u8 {
+    (f * 256.) as u8
+}
+
+// Decodes a gamma-encoded sRGB channel in `0..=1`, for generating tables at compile time.
+#[cfg(any(feature = "fast_lut", feature = "fixed"))]
+pub(crate) const fn srgb_decode_const(c: f64) -> f64 {
+    if c < 0.04045 {
+        return c / 12.92;
+    }
+    // x^2.4 = x^2 * x^(2/5), with the fifth root found by Newton's method
+    let x = (c + 0.055) / 1.055;
+    let mut root = 1.;
+    let mut i = 0;
+    while i < 24 {
+        let r4 = root * root * root * root;
+        root = (4. * root + x / r4) / 5.;
+        i += 1;
+    }
+    x * x * root * root
+}
+
+// Returns the maximum, or `b` if any is NaN.
+#[inline]
+pub(crate) const fn fmax(a: f32, b: f32) -> f32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+// Clamps `v` between `min` and `max`, returning `min` if it's NaN.
+#[inline]
+pub(crate) const fn fclamp(v: f32, min: f32, max: f32) -> f32 {
+    let v = fmax(v, min);
+    if v < max {
+        v
+    } else {
+        max
+    }
+}
+
+// Multiplies a 3×3 matrix by a column vector,
+// with `Float` intermediate results.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) const fn mat_mul_vec(m: [[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    let v = [v[0] as Float, v[1] as Float, v[2] as Float];
+    let mut out = [0.; 3];
+    let mut i = 0;
+    while i < 3 {
+        let row = [m[i][0] as Float, m[i][1] as Float, m[i][2] as Float];
+        out[i] = float_to_f32(row[0] * v[0] + row[1] * v[1] + row[2] * v[2]);
+        i += 1;
+    }
+    out
+}
+
+#[inline]
+const fn fabs(v: f32) -> f32 {
+    if v < 0. {
+        -v
+    } else {
+        v
+    }
+}
+
+// Square root, by range reduction and Newton iterations.
+pub(crate) const fn sqrt_const(x: f32) -> f32 {
+    if x > 0. {
+        if x == f32::INFINITY {
+            return x;
+        }
+        // reduce into 0.25..=1, where sqrt(v) is in 0.5..=1
+        let (mut v, mut scale) = (x, 1.);
+        while v > 1. {
+            v *= 0.25;
+            scale *= 2.;
+        }
+        while v < 0.25 {
+            v *= 4.;
+            scale *= 0.5;
+        }
+        let mut y = 0.5 + 0.5 * v;
+        let mut i = 0;
+        while i < 4 {
+            y = 0.5 * (y + v / y);
+            i += 1;
+        }
+        y * scale
+    } else if x == 0. {
+        x
+    } else {
+        f32::NAN
+    }
+}
+
+// Returns the sine and cosine of an angle in degrees.
+//
+// Reduces the angle to ±45º around the nearest quarter turn
+// and evaluates the Taylor series, with an error below `1e-7`.
+pub(crate) const fn sin_cos_deg(deg: f32) -> (f32, f32) {
+    let q = deg / 90.;
+    let q = if q >= 0. {
+        (q + 0.5) as i32
+    } else {
+        (q - 0.5) as i32
+    };
+    let r = (deg - q as f32 * 90.) * (PI / 180.);
+    let r2 = r * r;
+    let s = r * (1. + r2 * (-1. / 6. + r2 * (1. / 120. + r2 * (-1. / 5040. + r2 / 362880.))));
+    let c = 1. + r2 * (-0.5 + r2 * (1. / 24. + r2 * (-1. / 720. + r2 / 40320.)));
+    match q.rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+// Arc tangent of `z` in `-1..=1`, in radians.
+//
+// Halves the angle to keep the argument below `tan(π/8)`
+// and evaluates the Taylor series, with an error below `1e-7`.
+const fn atan_unit(z: f32) -> f32 {
+    let t = z / (1. + sqrt_const(1. + z * z));
+    let t2 = t * t;
+    let mut sum = 0.;
+    let mut k = 8;
+    loop {
+        // the sign alternates with the odd denominators
+        let term = 1. / (2 * k + 1) as f32;
+        sum = if k % 2 == 0 { term } else { -term } + t2 * sum;
+        if k == 0 {
+            break;
+        }
+        k -= 1;
+    }
+    2. * t * sum
+}
+
+// Four-quadrant arc tangent of `y / x`, in radians.
+pub(crate) const fn atan2_const(y: f32, x: f32) -> f32 {
+    if x == 0. && y == 0. {
+        0.
+    } else if fabs(y) <= fabs(x) {
+        let a = atan_unit(y / x);
+        if x > 0. {
+            a
+        } else if y >= 0. {
+            a + PI
+        } else {
+            a - PI
+        }
+    } else {
+        let a = atan_unit(x / y);
+        if y > 0. {
+            FRAC_PI_2 - a
+        } else {
+            -FRAC_PI_2 - a
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// acolor::mix
+//
+//! Color interpolation in a choice of color spaces.
+//!
+//! # Example
+//! ```
+//! # #[cfg(not(feature = "micromath"))] {
+//! use acolor::{mix::{Mix, MixSpace}, srgb::Srgb8};
+//!
+//! let (red, blue) = (Srgb8::new(255, 0, 0), Srgb8::new(0, 0, 255));
+//! assert_eq![red.mix_in(MixSpace::Srgb, &blue, 0.4), Srgb8::new(153, 0, 102)];
+//! assert_eq![red.mix_in(MixSpace::LinearSrgb, &blue, 0.4), Srgb8::new(204, 0, 170)];
+//! # }
+//! ```
+//!
+//! # Links
+//! - <https://www.w3.org/TR/css-color-4/#interpolation>
+//
+// # TOC
+//
+// - HueMode
+// - MixSpace
+// - Mix
+//
+
+use crate::{
+    hue::Hue,
+    oklab::{Oklab32, Oklch32},
+    srgb::{LinearSrgba32, Srgba32},
+    Color, FromColor,
+};
+
+/// The path between two hues, when interpolating in a polar color space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HueMode {
+    /// The shorter arc, of at most 180º.
+    #[default]
+    Shorter,
+    /// The longer arc, of at least 180º.
+    Longer,
+    /// The arc that increases the angle.
+    Increasing,
+    /// The arc that decreases the angle.
+    Decreasing,
+}
+
+impl HueMode {
+    /// Returns the hue at `t` between `a` and `b` along this path, in degrees.
+    pub fn lerp(self, a: f32, b: f32, t: f32) -> f32 {
+        let a = Hue::new(a);
+        let d = a.diff(Hue::new(b));
+        let d = match self {
+            HueMode::Shorter => d,
+            HueMode::Longer if d > 0. && d < 180. => d - 360.,
+            HueMode::Longer if d > -180. && d <= 0. => d + 360.,
+            HueMode::Increasing if d < 0. => d + 360.,
+            HueMode::Decreasing if d > 0. => d - 360.,
+            _ => d,
+        };
+        (a + d * t).degrees()
+    }
+}
+
+/// The color space where two colors are interpolated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MixSpace {
+    /// Linear sRGB, which preserves the light intensity, like mixing lights.
+    LinearSrgb,
+    /// Gamma encoded sRGB, like most image editors and the CSS default.
+    Srgb,
+    /// Oklab, which gives perceptually even steps.
+    #[default]
+    Oklab,
+    /// Oklch, which keeps the chroma through the hues, following the given hue path.
+    ///
+    /// The hue of an achromatic color takes the hue of the other color.
+    Oklch(HueMode),
+}
+
+// The chroma below which a color is considered achromatic, with no hue.
+const ACHROMATIC: f32 = 1e-4;
+
+impl MixSpace {
+    /// Returns the color at `t` between `a` and `b`, interpolated in this space.
+    ///
+    /// `t` is not clamped, so values out of `0..=1` extrapolate.
+    /// The alpha is interpolated linearly, without premultiplying.
+    pub fn mix(self, a: LinearSrgba32, b: LinearSrgba32, t: f32) -> LinearSrgba32 {
+        let lerp = |x: f32, y: f32| x + (y - x) * t;
+        let alpha = lerp(a.a, b.a);
+        match self {
+            MixSpace::LinearSrgb => LinearSrgba32 {
+                r: lerp(a.r, b.r),
+                g: lerp(a.g, b.g),
+                b: lerp(a.b, b.b),
+                a: alpha,
+            },
+            MixSpace::Srgb => {
+                let (a, b) = (a.to_srgba32(), b.to_srgba32());
+                Srgba32 {
+                    r: lerp(a.r, b.r),
+                    g: lerp(a.g, b.g),
+                    b: lerp(a.b, b.b),
+                    a: alpha,
+                }
+                .to_linear_srgba32()
+            }
+            MixSpace::Oklab => {
+                let (a, b) = (a.to_oklab32(), b.to_oklab32());
+                let lab = Oklab32 {
+                    l: lerp(a.l, b.l),
+                    a: lerp(a.a, b.a),
+                    b: lerp(a.b, b.b),
+                };
+                LinearSrgba32::from_oklab32(lab, alpha)
+            }
+            MixSpace::Oklch(mode) => {
+                let (a, b) = (a.to_oklch32(), b.to_oklch32());
+                let (ha, hb) = match (a.c < ACHROMATIC, b.c < ACHROMATIC) {
+                    (true, false) => (b.h, b.h),
+                    (false, true) => (a.h, a.h),
+                    _ => (a.h, b.h),
+                };
+                let lch = Oklch32 {
+                    l: lerp(a.l, b.l),
+                    c: lerp(a.c, b.c),
+                    h: mode.lerp(ha, hb, t),
+                };
+                LinearSrgba32::from_oklab32(lch.to_oklab32(), alpha)
+            }
+        }
+    }
+}
+
+/// Interpolation between colors of the same type.
+///
+/// It's implemented for all the color types.
+pub trait Mix: Sized {
+    /// Returns the color at `t` between `self` (at `0`) and `other` (at `1`),
+    /// interpolated in the given `space`.
+    ///
+    /// See [`MixSpace::mix`].
+    fn mix_in(&self, space: MixSpace, other: &Self, t: f32) -> Self;
+}
+
+impl<C: Color + FromColor<LinearSrgba32>> Mix for C {
+    fn mix_in(&self, space: MixSpace, other: &C, t: f32) -> C {
+        let (a, b) = (
+            self.color_to_linear_srgba32(),
+            other.color_to_linear_srgba32(),
+        );
+        C::from_color(space.mix(a, b, t))
+    }
+}
@@ -13,15 +13,18 @@
 //
 
 #[cfg(any(feature = "std", feature = "no_std"))]
-use crate::srgb::{LinearSrgb32, LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8};
-use devela::cmp::{pclamp, pmax};
-
-#[cfg(all(feature = "no_std", not(feature = "std")))]
-use libm::{atan2f, cbrtf, cosf, hypotf, powf, sinf};
+use crate::math::{atan2, cbrt_float, cos, hypot, sin};
+#[cfg(any(feature = "std", feature = "no_std"))]
+use crate::srgb::{LinearSrgba32, Srgb32, Srgb8, Srgba32, Srgba8};
+use crate::{
+    hue::Hue,
+    math::{atan2_const, fclamp, float_to_f32, sin_cos_deg, sqrt_const, Float},
+    srgb::LinearSrgb32,
+};
 
 /* definitions */
 
-/// Oklab color representation using `3` × [`f32`] components.
+/// Oklab color representation using `3` × `T` components.
 ///
 /// # Fields
 /// - l: perceived luminosity
@@ -29,48 +32,77 @@ use libm::{atan2f, cbrtf, cosf, hypotf, powf, sinf};
 /// - b: blue/yellow axis
 ///
 /// Best suited for perceptual color manipulation.
+///
+/// See the [`Oklab32`] and [`Oklab64`] aliases.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Oklab32 {
-    /// Perceived lightness. A percentage between 0% and 100%.
-    pub l: f32,
+#[repr(C)]
+pub struct Oklab<T> {
+    /// Perceived lightness, between `0` (black) and `1` (white).
+    pub l: T,
     /// The distance along the `a` axis from **greenish cyan** to **purplish red**.
-    pub a: f32,
+    pub a: T,
     /// The distance along the `b` axis, from **sky blue** to **mustard yellow**.
-    pub b: f32,
+    pub b: T,
 }
 
+/// Oklab color representation using `3` × [`f32`] components.
+pub type Oklab32 = Oklab<f32>;
+
+/// Oklab color representation using `3` × [`f64`] components.
+pub type Oklab64 = Oklab<f64>;
+
 /// # Constructors
 impl Oklab32 {
-    /// New Oklab color.
+    /// New Oklab color with clamped values.
     ///
     /// # Arguments
-    /// - **lighness**, tipically between `0.` and `100.`, range: `> 0.`.
+    /// - **lightness**, range: `0.0..=1.0`.
     /// - **a**, cyan..red axis, range: `-0.5..0.5`.
     /// - **b**, blue..yellow axis, range: `-0.5..0.5`.
-    pub fn new(lightness: f32, a: f32, b: f32) -> Oklab32 {
-        let l = pmax(0.0, lightness);
-        let a = pclamp(a, -0.5, 0.5);
-        let b = pclamp(b, -0.5, 0.5);
+    pub const fn new(lightness: f32, a: f32, b: f32) -> Oklab32 {
+        let l = fclamp(lightness, 0.0, 1.0);
+        let a = fclamp(a, -0.5, 0.5);
+        let b = fclamp(b, -0.5, 0.5);
 
         Self { l, a, b }
     }
+
+    /// New Oklab color with the values as they are, without clamping.
+    pub const fn new_unchecked(lightness: f32, a: f32, b: f32) -> Oklab32 {
+        Self { l: lightness, a, b }
+    }
+
+    /// New Oklab color with clamped values, from a `lightness` percentage in `0.0..=100.0`.
+    pub const fn from_percent(lightness: f32, a: f32, b: f32) -> Oklab32 {
+        Self::new(lightness / 100., a, b)
+    }
+
+    /// New Oklab color with the values adjusted by the given `policy`.
+    ///
+    /// Since Oklab has no hue, [`ClampPolicy::WrapHue`] clamps like [`ClampPolicy::Clamp`].
+    pub const fn new_with_policy(lightness: f32, a: f32, b: f32, policy: ClampPolicy) -> Oklab32 {
+        match policy {
+            ClampPolicy::Clamp | ClampPolicy::WrapHue => Self::new(lightness, a, b),
+            ClampPolicy::Unchecked => Self::new_unchecked(lightness, a, b),
+        }
+    }
 }
 
 /// # Constants
 impl Oklab32 {
-    ///
+    /// Lightness minimum value.
     pub const L_MIN: f32 = 0.;
-    ///
-    pub const L_MAX: f32 = 100.;
+    /// Lightness maximum value.
+    pub const L_MAX: f32 = 1.;
 
-    ///
+    /// `a` axis minimum value.
     pub const A_MIN: f32 = -0.5;
-    ///
+    /// `a` axis maximum value.
     pub const A_MAX: f32 = 0.5;
 
-    ///
+    /// `b` axis minimum value.
     pub const B_MIN: f32 = -0.5;
-    ///
+    /// `b` axis maximum value.
     pub const B_MAX: f32 = 0.5;
 }
 
@@ -85,15 +117,8 @@ impl Oklab32 {
         doc(cfg(any(feature = "std", feature = "no_std")))
     )]
     pub fn squared_distance(&self, other: &Oklab32) -> f32 {
-        #[cfg(feature = "std")]
-        return (self.l - other.l).powi(2)
-            + (self.a - other.a).powi(2)
-            + (self.b - other.b).powi(2);
-
-        #[cfg(not(feature = "std"))]
-        return powf(self.l - other.l, 2.)
-            + powf(self.a - other.a, 2.)
-            + powf(self.b - other.b, 2.);
+        let (l, a, b) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        l * l + a * a + b * b
     }
 
     // ///
@@ -105,7 +130,7 @@ impl Oklab32 {
     // }
 }
 
-/// Oklch color representation using `3` × [`f32`] components.
+/// Oklch color representation using `3` × `T` components.
 ///
 /// # Fields
 /// - l: perceived luminosity
@@ -113,38 +138,125 @@ impl Oklab32 {
 /// - h: hue
 ///
 /// Best suited for perceptual color manipulation.
+///
+/// See the [`Oklch32`] and [`Oklch64`] aliases.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Oklch32 {
-    /// Perceived lightness. A percentage between 0% and 100%.
-    pub l: f32,
+#[repr(C)]
+pub struct Oklch<T> {
+    /// Perceived lightness, between `0` (black) and `1` (white).
+    pub l: T,
     /// Chromacity
-    pub c: f32,
-    /// Hue angle.
+    pub c: T,
+    /// Hue angle, in degrees.
+    ///
+    /// The conversions and operations of this crate wrap it into `0..360`
+    /// through [`Hue`], which [`hue`][Self::hue] returns.
     /// - 0º points along the positive `a` axis (purplish red).
     /// - 90º points along the positive `b` axis (mustard yellow).
     /// - 180º points along the negative `a` axis (greenish cyan).
     /// - 90º points along the negative `b` axis (sky blue).
-    pub h: f32,
+    pub h: T,
 }
 
+/// Oklch color representation using `3` × [`f32`] components.
+pub type Oklch32 = Oklch<f32>;
+
+/// Oklch color representation using `3` × [`f64`] components.
+pub type Oklch64 = Oklch<f64>;
+
 /// # Constructors
 impl Oklch32 {
-    /// New Oklch color with clamped values.
-    pub fn new(luminance: f32, chroma: f32, hue: f32) -> Oklch32 {
-        let l = pclamp(luminance, 0.0, 100.0);
-        let c = pclamp(chroma, 0.0, 0.5);
-        let h = pclamp(hue, 0.0, 360.);
+    /// New Oklch color with clamped values, and the hue wrapped around.
+    ///
+    /// # Arguments
+    /// - **luminance**, the lightness, range: `0.0..=1.0`.
+    /// - **chroma**, range: `0.0..=0.5`.
+    /// - **hue**, in degrees, wrapped into `0.0..360.0`.
+    pub const fn new(luminance: f32, chroma: f32, hue: f32) -> Oklch32 {
+        let l = fclamp(luminance, 0.0, 1.0);
+        let c = fclamp(chroma, 0.0, 0.5);
+        let h = Hue::new(hue).degrees();
 
         Self { l, c, h }
     }
+
+    /// New Oklch color with the values as they are, without clamping.
+    pub const fn new_unchecked(luminance: f32, chroma: f32, hue: f32) -> Oklch32 {
+        Self {
+            l: luminance,
+            c: chroma,
+            h: hue,
+        }
+    }
+
+    /// New Oklch color like [`new`][Self::new], from a `luminance` percentage in `0.0..=100.0`.
+    pub const fn from_percent(luminance: f32, chroma: f32, hue: f32) -> Oklch32 {
+        Self::new(luminance / 100., chroma, hue)
+    }
+
+    /// New Oklch color with the values adjusted by the given `policy`.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::oklab::{ClampPolicy, Oklch32};
+    ///
+    /// assert_eq![Oklch32::new_with_policy(0.5, 0.1, 480., ClampPolicy::Clamp).h, 360.];
+    /// assert_eq![Oklch32::new_with_policy(0.5, 0.1, 480., ClampPolicy::WrapHue).h, 120.];
+    /// assert_eq![Oklch32::new_with_policy(0.5, 0.1, -90., ClampPolicy::WrapHue).h, 270.];
+    /// ```
+    pub const fn new_with_policy(
+        luminance: f32,
+        chroma: f32,
+        hue: f32,
+        policy: ClampPolicy,
+    ) -> Oklch32 {
+        match policy {
+            ClampPolicy::Clamp => {
+                let c = Self::new(luminance, chroma, 0.);
+                Self {
+                    h: fclamp(hue, 0.0, 360.),
+                    ..c
+                }
+            }
+            ClampPolicy::WrapHue => Self::new(luminance, chroma, hue),
+            ClampPolicy::Unchecked => Self::new_unchecked(luminance, chroma, hue),
+        }
+    }
+}
+
+/// # Hue
+impl Oklch32 {
+    /// Returns the hue as a [`Hue`], wrapped into `0..360` degrees.
+    pub const fn hue(&self) -> Hue {
+        Hue::new(self.h)
+    }
+
+    /// Returns the color with its hue rotated by `degrees`, wrapping around.
+    pub const fn rotate_hue(mut self, degrees: f32) -> Oklch32 {
+        self.h = Hue::new(self.h + degrees).degrees();
+        self
+    }
+}
+
+/// How the constructors of [`Oklab32`] and [`Oklch32`] treat out of range values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ClampPolicy {
+    /// Clamps every value into its range, including the hue into `0..=360`.
+    #[default]
+    Clamp,
+    /// Wraps the hue around into `0..360`, and clamps the rest of values,
+    /// like the `new` constructors.
+    WrapHue,
+    /// Keeps the values as they are, like the `new_unchecked` constructors.
+    Unchecked,
 }
 
 /// # Constants
 impl Oklch32 {
-    /// Luminance minimum value.
+    /// Lightness minimum value.
     pub const L_MIN: f32 = 0.;
-    /// Luminance maximum value.
-    pub const L_MAX: f32 = 100.;
+    /// Lightness maximum value.
+    pub const L_MAX: f32 = 1.;
 
     /// Chroma minimum value.
     pub const C_MIN: f32 = 0.;
@@ -157,43 +269,369 @@ impl Oklch32 {
     pub const H_MAX: f32 = 360.;
 }
 
-// // TODO
-// /// # Operations
-// impl Oklch32 {
-// }
+/// # Operations
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Oklch32 {
+    /// Returns the color mapped into the sRGB gamut, by reducing its chroma
+    /// while keeping its lightness and hue.
+    ///
+    /// The lightness is clamped to `0..=1`.
+    pub fn gamut_map_srgb(&self) -> Oklch32 {
+        let (l, h) = (self.l.clamp(0., 1.), self.h);
+        let in_gamut = |c: f32| {
+            let rgb = Oklch32 { l, c, h }.to_linear_srgb32();
+            [rgb.r, rgb.g, rgb.b]
+                .iter()
+                .all(|v| (-GAMUT_EPSILON..=1. + GAMUT_EPSILON).contains(v))
+        };
+        if in_gamut(self.c) {
+            return Oklch32 { l, c: self.c, h };
+        }
+        let (mut lo, mut hi) = (0., self.c);
+        for _ in 0..24 {
+            let mid = (lo + hi) / 2.;
+            if in_gamut(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Oklch32 { l, c: lo, h }
+    }
 
-/* conversions */
+    /// Returns the color with its hue rotated by `degrees`, mapped into the sRGB gamut
+    /// while keeping its lightness.
+    ///
+    /// Rotating the hue alone can leave the color out of the gamut, and clamping
+    /// its sRGB components afterwards changes the lightness, visibly darkening some hues.
+    /// This reduces the chroma instead, like [`gamut_map_srgb`][Self::gamut_map_srgb].
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(not(feature = "micromath"))] {
+    /// use acolor::{oklab::Oklch32, srgb::Srgb8};
+    ///
+    /// let green = Srgb8::new(0, 255, 0).to_oklch32();
+    /// let shifted = green.shift_hue_preserving_lightness(240.);
+    /// assert![(shifted.to_srgb8().to_oklch32().l - green.l).abs() < 0.01];
+    ///
+    /// let clamped = green.rotate_hue(240.).to_srgb8().to_oklch32();
+    /// assert![green.l - clamped.l > 0.1];
+    /// # }
+    /// ```
+    pub fn shift_hue_preserving_lightness(&self, degrees: f32) -> Oklch32 {
+        self.rotate_hue(degrees).gamut_map_srgb()
+    }
 
-// Converts from [`Oklab32`] to [`Oklch32`] color spaces.
-#[inline]
+    /// Returns the cusp of the sRGB gamut for the given `hue`,
+    /// the color with the maximum chroma for that hue.
+    ///
+    /// Uses the approximation by Björn Ottosson.
+    ///
+    /// # Links
+    /// - <https://bottosson.github.io/posts/gamutclipping/>
+    pub fn srgb_cusp(hue: f32) -> Oklch32 {
+        let (b, a) = sin_cos_deg(hue);
+        let s = max_saturation(a, b);
+        let rgb = Oklab32 {
+            l: 1.,
+            a: s * a,
+            b: s * b,
+        }
+        .to_linear_srgb32();
+        let l = crate::math::powf(1. / rgb.r.max(rgb.g).max(rgb.b), 1. / 3.);
+        Oklch32 {
+            l,
+            c: l * s,
+            h: hue,
+        }
+    }
+
+    /// Returns the maximum chroma that keeps a color with the given `lightness`
+    /// and `hue` inside the sRGB gamut.
+    ///
+    /// Returns `0` for a `lightness` out of `0..1`.
+    /// Uses the approximation by Björn Ottosson, which puts the color
+    /// within `1e-3` of the gamut boundary.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::oklab::Oklch32;
+    ///
+    /// let c = Oklch32::max_srgb_chroma(0.7, 150.);
+    /// let rgb = Oklch32 { l: 0.7, c, h: 150. }.to_linear_srgb32();
+    /// assert![rgb.r.abs() < 1e-3 && rgb.g <= 1. && rgb.b > 0.];
+    /// ```
+    ///
+    /// # Links
+    /// - <https://bottosson.github.io/posts/gamutclipping/>
+    pub fn max_srgb_chroma(lightness: f32, hue: f32) -> f32 {
+        if !(lightness > 0. && lightness < 1.) {
+            return 0.;
+        }
+        let cusp = Oklch32::srgb_cusp(hue);
+        if lightness <= cusp.l {
+            return cusp.c * lightness / cusp.l;
+        }
+        // above the cusp, refine the triangle estimate with a step of Halley's method
+        let t = cusp.c * (lightness - 1.) / (cusp.l - 1.);
+        let (b, a) = sin_cos_deg(hue);
+        let (k_l, k_m, k_s) = lms_direction(a, b);
+        let (l_, m_, s_) = (
+            lightness + t * k_l,
+            lightness + t * k_m,
+            lightness + t * k_s,
+        );
+        let lms = [l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_];
+        let lms_dt = [3. * k_l * l_ * l_, 3. * k_m * m_ * m_, 3. * k_s * s_ * s_];
+        let lms_dt2 = [
+            6. * k_l * k_l * l_,
+            6. * k_m * k_m * m_,
+            6. * k_s * k_s * s_,
+        ];
+
+        let mut step = f32::MAX;
+        for w in LMS_TO_LINEAR_SRGB {
+            let dot = |v: [f32; 3]| w[0] * v[0] + w[1] * v[1] + w[2] * v[2];
+            let (f, f1, f2) = (dot(lms) - 1., dot(lms_dt), dot(lms_dt2));
+            let u = f1 / (f1 * f1 - 0.5 * f * f2);
+            if u >= 0. {
+                step = step.min(-f * u);
+            }
+        }
+        t + step
+    }
+}
+
+// The tolerance of the sRGB gamut checks.
 #[cfg(any(feature = "std", feature = "no_std"))]
-fn oklab32_to_oklch32(c: Oklab32) -> Oklch32 {
-    #[cfg(feature = "std")]
-    {
-        use core::f32::consts::PI as PI_32;
-        let hue = c.b.atan2(c.a) * 180. / PI_32;
-        #[rustfmt::skip]
-        let h = if hue >= 0. { hue } else { hue + 360. };
+const GAMUT_EPSILON: f32 = 1e-5;
 
-        Oklch32 {
-            l: c.l,
-            c: c.a.hypot(c.b),
-            h,
+// The rows of the matrix from cubed LMS into linear sRGB.
+#[cfg(any(feature = "std", feature = "no_std"))]
+const LMS_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+// Returns how the non-linear LMS components change along the Oklab direction `(a, b)`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+const fn lms_direction(a: f32, b: f32) -> (f32, f32, f32) {
+    (
+        0.3963377774 * a + 0.2158037573 * b,
+        -0.1055613458 * a - 0.0638541728 * b,
+        -0.0894841775 * a - 1.2914855480 * b,
+    )
+}
+
+// Returns the maximum saturation `S = C / L` in sRGB for the normalized
+// Oklab direction `(a, b)`, by fitting a polynomial for the component that
+// clips first, and refining it with a step of Halley's method.
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn max_saturation(a: f32, b: f32) -> f32 {
+    let (k, w) = if -1.88170328 * a - 0.80936493 * b > 1. {
+        // red clips first
+        (
+            [1.19086277, 1.76576728, 0.59662641, 0.75515197, 0.56771245],
+            LMS_TO_LINEAR_SRGB[0],
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1. {
+        // green clips first
+        (
+            [0.73956515, -0.45954404, 0.08285427, 0.12541070, 0.14503204],
+            LMS_TO_LINEAR_SRGB[1],
+        )
+    } else {
+        // blue clips first
+        (
+            [
+                1.35733652,
+                -0.00915799,
+                -1.15130210,
+                -0.50559606,
+                0.00692167,
+            ],
+            LMS_TO_LINEAR_SRGB[2],
+        )
+    };
+    let s = k[0] + k[1] * a + k[2] * b + k[3] * a * a + k[4] * a * b;
+
+    let (k_l, k_m, k_s) = lms_direction(a, b);
+    let (l_, m_, s_) = (1. + s * k_l, 1. + s * k_m, 1. + s * k_s);
+    let dot = |v: [f32; 3]| w[0] * v[0] + w[1] * v[1] + w[2] * v[2];
+    let f = dot([l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_]);
+    let f1 = dot([3. * k_l * l_ * l_, 3. * k_m * m_ * m_, 3. * k_s * s_ * s_]);
+    let f2 = dot([
+        6. * k_l * k_l * l_,
+        6. * k_m * k_m * m_,
+        6. * k_s * k_s * s_,
+    ]);
+    s - f * f1 / (f1 * f1 - 0.5 * f * f2)
+}
+
+/// Oklab color representation using `3` × [`u8`] quantized components.
+///
+/// Best suited for memory efficient palettes and nearest color searches.
+///
+/// # Quantization
+/// - `l = l8 / 255`, covering `0.0..=1.0`.
+/// - `a = (a8 - 128) / 255`, covering `-0.502..=0.498`, with `128` being `0.0`.
+/// - `b = (b8 - 128) / 255`, likewise.
+///
+/// Values outside those ranges are clamped, and the rest rounded to the nearest step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Oklab8 {
+    /// Quantized perceived lightness.
+    pub l: u8,
+    /// Quantized `a` axis, offset by `128`.
+    pub a: u8,
+    /// Quantized `b` axis, offset by `128`.
+    pub b: u8,
+}
+
+/// # Constructors
+impl Oklab8 {
+    /// New quantized Oklab color, from already quantized components.
+    pub const fn new(l: u8, a: u8, b: u8) -> Oklab8 {
+        Self { l, a, b }
+    }
+
+    /// Returns the components packed into the lower 24 bits, as `0x00LLAABB`.
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        (self.l as u32) << 16 | (self.a as u32) << 8 | self.b as u32
+    }
+    /// Returns a new color from the lower 24 bits, as `0x00LLAABB`.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Oklab8 {
+        Self::new((bits >> 16) as u8, (bits >> 8) as u8, bits as u8)
+    }
+}
+
+/// # Operations
+impl Oklab8 {
+    /// Measures the squared distance to another color, in quantized steps.
+    ///
+    /// Uses only integer arithmetic.
+    #[inline]
+    pub const fn squared_distance(&self, other: &Oklab8) -> u32 {
+        let dl = self.l.abs_diff(other.l) as u32;
+        let da = self.a.abs_diff(other.a) as u32;
+        let db = self.b.abs_diff(other.b) as u32;
+        dl * dl + da * da + db * db
+    }
+}
+
+/// # Direct conversions
+impl Oklab8 {
+    /// Direct conversion from [`Oklab32`], quantizing.
+    #[inline]
+    pub const fn from_oklab32(c: Oklab32) -> Oklab8 {
+        Oklab8 {
+            l: quantize(c.l),
+            a: quantize(c.a + 128. / 255.),
+            b: quantize(c.b + 128. / 255.),
         }
     }
+    /// Direct conversion to [`Oklab32`].
+    #[inline]
+    pub const fn to_oklab32(&self) -> Oklab32 {
+        Oklab32 {
+            l: self.l as f32 / 255.,
+            a: (self.a as f32 - 128.) / 255.,
+            b: (self.b as f32 - 128.) / 255.,
+        }
+    }
+}
+
+/// # Indirect conversions
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Oklab8 {
+    /// Indirect conversion from [`Srgb8`].
+    #[inline]
+    pub fn from_srgb8(c: Srgb8) -> Oklab8 {
+        Oklab8::from_oklab32(c.to_oklab32())
+    }
+    /// Indirect conversion to [`Srgb8`].
+    #[inline]
+    pub fn to_srgb8(&self) -> Srgb8 {
+        self.to_oklab32().to_srgb8()
+    }
+}
 
-    #[cfg(not(feature = "std"))]
-    {
-        use core::f32::consts::PI as PI_32;
-        let hue = atan2f(c.b, c.a) * 180. / PI_32;
-        #[rustfmt::skip]
-        let h = if hue >= 0. { hue } else { hue + 360. };
+// Quantizes a value in `0..=1` into a `u8`, clamping and rounding to the nearest.
+#[inline]
+const fn quantize(x: f32) -> u8 {
+    (fclamp(x, 0., 1.) * 255. + 0.5) as u8
+}
 
-        Oklch32 {
-            l: c.l,
-            c: hypotf(c.a, c.b),
-            h,
+impl From<Oklab32> for Oklab8 {
+    #[inline]
+    fn from(c: Oklab32) -> Oklab8 {
+        Oklab8::from_oklab32(c)
+    }
+}
+impl From<Oklab8> for Oklab32 {
+    #[inline]
+    fn from(c: Oklab8) -> Oklab32 {
+        c.to_oklab32()
+    }
+}
+
+// Implements the array and tuple conversions.
+macro_rules! impl_array_tuple {
+    ($($type:ident: $c0:ident, $c1:ident, $c2:ident);+) => { $(
+        /// # Array and tuple conversions
+        impl<T: Copy> $type<T> {
+            /// Direct conversion from an array.
+            #[inline]
+            pub const fn from_array(c: [T; 3]) -> $type<T> {
+                $type { $c0: c[0], $c1: c[1], $c2: c[2] }
+            }
+            /// Direct conversion to an array.
+            #[inline]
+            pub const fn to_array(c: $type<T>) -> [T; 3] {
+                [c.$c0, c.$c1, c.$c2]
+            }
+            /// Direct conversion from a tuple.
+            #[inline]
+            pub const fn from_tuple(c: (T, T, T)) -> $type<T> {
+                $type { $c0: c.0, $c1: c.1, $c2: c.2 }
+            }
+            /// Direct conversion to a tuple.
+            #[inline]
+            pub const fn to_tuple(c: $type<T>) -> (T, T, T) {
+                (c.$c0, c.$c1, c.$c2)
+            }
         }
+    )+ };
+}
+impl_array_tuple![Oklab: l, a, b; Oklch: l, c, h];
+crate::srgb::impl_from_f32_to_f64![Oklab: l, a, b; Oklch: l, c, h];
+
+/* conversions */
+
+// Converts from [`Oklab32`] to [`Oklch32`] color spaces.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn oklab32_to_oklch32(c: Oklab32) -> Oklch32 {
+    use core::f32::consts::PI as PI_32;
+    let hue = atan2(c.b, c.a) * 180. / PI_32;
+    let h = Hue::new(hue).degrees();
+
+    Oklch32 {
+        l: c.l,
+        c: hypot(c.a, c.b),
+        h,
     }
 }
 
@@ -201,100 +639,95 @@ fn oklab32_to_oklch32(c: Oklab32) -> Oklch32 {
 #[inline]
 #[cfg(any(feature = "std", feature = "no_std"))]
 fn oklch32_to_oklab32(c: Oklch32) -> Oklab32 {
-    #[cfg(feature = "std")]
-    {
-        use core::f32::consts::PI as PI_32;
-        Oklab32 {
-            l: c.l,
-            a: c.c * (c.h * PI_32 / 180.).cos(),
-            b: c.c * (c.h * PI_32 / 180.).sin(),
-        }
+    use core::f32::consts::PI as PI_32;
+    Oklab32 {
+        l: c.l,
+        a: c.c * cos(c.h * PI_32 / 180.),
+        b: c.c * sin(c.h * PI_32 / 180.),
     }
+}
 
-    #[cfg(not(feature = "std"))]
-    {
-        use core::f32::consts::PI as PI_32;
-        Oklab32 {
-            l: c.l,
-            a: c.c * cosf(c.h * PI_32 / 180.),
-            b: c.c * sinf(c.h * PI_32 / 180.),
-        }
+// Converts from [`Oklab32`] to [`Oklch32`] color spaces, using const approximations.
+const fn oklab32_to_oklch32_const(c: Oklab32) -> Oklch32 {
+    let hue = atan2_const(c.b, c.a) * 180. / core::f32::consts::PI;
+    let h = Hue::new(hue).degrees();
+
+    Oklch32 {
+        l: c.l,
+        c: sqrt_const(c.a * c.a + c.b * c.b),
+        h,
+    }
+}
+
+// Converts from [`Oklch32`] to [`Oklab32`] color spaces, using const approximations.
+const fn oklch32_to_oklab32_const(c: Oklch32) -> Oklab32 {
+    let (sin, cos) = sin_cos_deg(c.h);
+    Oklab32 {
+        l: c.l,
+        a: c.c * cos,
+        b: c.c * sin,
     }
 }
 
 /// Converts from [`LinearSrgb32`] to [`Oklab32`] color spaces.
 #[cfg(any(feature = "std", feature = "no_std"))]
 fn linear_srgb32_to_oklab32(c: LinearSrgb32) -> Oklab32 {
-    #[cfg(feature = "std")]
-    let l = (0.4122214708 * c.r + 0.5363325363 * c.g + 0.0514459929 * c.b).cbrt();
-    #[cfg(not(feature = "std"))]
-    let l = cbrtf(0.4122214708 * c.r + 0.5363325363 * c.g + 0.0514459929 * c.b);
-    #[cfg(feature = "std")]
-    let m = (0.2119034982 * c.r + 0.6806995451 * c.g + 0.1073969566 * c.b).cbrt();
-    #[cfg(not(feature = "std"))]
-    let m = cbrtf(0.2119034982 * c.r + 0.6806995451 * c.g + 0.1073969566 * c.b);
-    #[cfg(feature = "std")]
-    let s = (0.0883024619 * c.r + 0.2817188376 * c.g + 0.6299787005 * c.b).cbrt();
-    #[cfg(not(feature = "std"))]
-    let s = cbrtf(0.0883024619 * c.r + 0.2817188376 * c.g + 0.6299787005 * c.b);
+    let [r, g, b] = [c.r as Float, c.g as Float, c.b as Float];
+    let l = cbrt_float(0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b);
+    let m = cbrt_float(0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b);
+    let s = cbrt_float(0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b);
 
     Oklab32 {
-        l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
-        a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
-        b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        l: float_to_f32(0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s),
+        a: float_to_f32(1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s),
+        b: float_to_f32(0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s),
     }
 }
 
 /// Converts from [`Oklab32`] to [`LinearSrgb32`] color spaces.
-#[cfg(any(feature = "std", feature = "no_std"))]
-fn oklab32_to_linear_srgb32(c: Oklab32) -> LinearSrgb32 {
-    let _l = c.l + 0.3963377774 * c.a + 0.2158037573 * c.b;
-    let _m = c.l - 0.1055613458 * c.a - 0.0638541728 * c.b;
-    let _s = c.l - 0.0894841775 * c.a - 1.2914855480 * c.b;
+const fn oklab32_to_linear_srgb32(c: Oklab32) -> LinearSrgb32 {
+    let [cl, ca, cb] = [c.l as Float, c.a as Float, c.b as Float];
+    let _l = cl + 0.3963377774 * ca + 0.2158037573 * cb;
+    let _m = cl - 0.1055613458 * ca - 0.0638541728 * cb;
+    let _s = cl - 0.0894841775 * ca - 1.2914855480 * cb;
 
     let l = _l * _l * _l;
     let m = _m * _m * _m;
     let s = _s * _s * _s;
 
     LinearSrgb32 {
-        r: 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
-        g: -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
-        b: -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        r: float_to_f32(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+        g: float_to_f32(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+        b: float_to_f32(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
     }
 }
 
 /// # Direct conversions
 impl Oklab32 {
-    // [] ()
+    // LinearSrgb32
 
-    /// Direct conversion from an array.
-    #[inline]
-    pub fn from_array(c: [f32; 3]) -> Oklab32 {
-        Oklab32 {
-            l: c[0],
-            a: c[1],
-            b: c[2],
-        }
-    }
-    /// Direct conversion to an array.
+    /// Direct conversion to [`LinearSrgb32`].
     #[inline]
-    pub fn to_array(c: Oklab32) -> [f32; 3] {
-        [c.l, c.a, c.b]
+    pub const fn to_linear_srgb32(&self) -> LinearSrgb32 {
+        oklab32_to_linear_srgb32(*self)
     }
 
-    /// Direct conversion from a tuple.
+    // Oklch32
+
+    /// Direct conversion from [`Oklch32`], usable in const contexts.
+    ///
+    /// Uses a sine and cosine approximation, with an error below `1e-6`.
     #[inline]
-    pub fn from_tuple(c: (f32, f32, f32)) -> Oklab32 {
-        Oklab32 {
-            l: c.0,
-            a: c.1,
-            b: c.2,
-        }
+    pub const fn from_oklch32_const(c: Oklch32) -> Oklab32 {
+        oklch32_to_oklab32_const(c)
     }
-    /// Direct conversion to a tuple.
+
+    /// Direct conversion to [`Oklch32`], usable in const contexts.
+    ///
+    /// Uses a square root and arc tangent approximation, with an error below `1e-6`.
     #[inline]
-    pub fn to_tuple(c: Oklab32) -> (f32, f32, f32) {
-        (c.l, c.a, c.b)
+    pub const fn to_oklch32_const(&self) -> Oklch32 {
+        oklab32_to_oklch32_const(*self)
     }
 }
 
@@ -312,12 +745,6 @@ impl Oklab32 {
         linear_srgb32_to_oklab32(c)
     }
 
-    /// Direct conversion to [`LinearSrgb32`].
-    #[inline]
-    pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
-        oklab32_to_linear_srgb32(*self)
-    }
-
     // LinearSrgba32
 
     /// Direct conversion from [`LinearSrgba32`].
@@ -425,36 +852,32 @@ impl Oklab32 {
 
 /// # Direct conversions
 impl Oklch32 {
-    // [] ()
+    // Oklab32
 
-    /// Direct conversion from an array.
-    #[inline]
-    pub fn from_array(c: [f32; 3]) -> Oklch32 {
-        Oklch32 {
-            l: c[0],
-            c: c[1],
-            h: c[2],
-        }
-    }
-    /// Direct conversion to an array.
+    /// Direct conversion from [`Oklab32`], usable in const contexts.
+    ///
+    /// Uses a square root and arc tangent approximation, with an error below `1e-6`.
     #[inline]
-    pub fn to_array(c: Oklch32) -> [f32; 3] {
-        [c.l, c.c, c.h]
+    pub const fn from_oklab32_const(c: Oklab32) -> Oklch32 {
+        oklab32_to_oklch32_const(c)
     }
 
-    /// Direct conversion from a tuple.
+    /// Direct conversion to [`Oklab32`], usable in const contexts.
+    ///
+    /// Uses a sine and cosine approximation, with an error below `1e-6`.
     #[inline]
-    pub fn from_tuple(c: (f32, f32, f32)) -> Oklch32 {
-        Oklch32 {
-            l: c.0,
-            c: c.1,
-            h: c.2,
-        }
+    pub const fn to_oklab32_const(&self) -> Oklab32 {
+        oklch32_to_oklab32_const(*self)
     }
-    /// Direct conversion to a tuple.
+
+    // LinearSrgb32
+
+    /// Indirect conversion to [`LinearSrgb32`], usable in const contexts.
+    ///
+    /// Uses [`to_oklab32_const`][Self::to_oklab32_const].
     #[inline]
-    pub fn to_tuple(c: Oklch32) -> (f32, f32, f32) {
-        (c.l, c.c, c.h)
+    pub const fn to_linear_srgb32_const(&self) -> LinearSrgb32 {
+        oklab32_to_linear_srgb32(oklch32_to_oklab32_const(*self))
     }
 }
 
@@ -577,56 +1000,6 @@ impl Oklch32 {
     }
 }
 
-mod impl_from {
-    use super::*;
-
-    /* From/Into arrays and tuples */
-
-    impl From<[f32; 3]> for Oklab32 {
-        #[inline]
-        fn from(c: [f32; 3]) -> Oklab32 {
-            Oklab32::new(c[0], c[1], c[2])
-        }
-    }
-
-    impl From<(f32, f32, f32)> for Oklab32 {
-        #[inline]
-        fn from(c: (f32, f32, f32)) -> Oklab32 {
-            Oklab32::new(c.0, c.1, c.2)
-        }
-    }
-
-    impl From<[f32; 3]> for Oklch32 {
-        #[inline]
-        fn from(c: [f32; 3]) -> Oklch32 {
-            Oklch32::new(c[0], c[1], c[2])
-        }
-    }
-
-    impl From<(f32, f32, f32)> for Oklch32 {
-        #[inline]
-        fn from(c: (f32, f32, f32)) -> Oklch32 {
-            Oklch32::new(c.0, c.1, c.2)
-        }
-    }
-
-    /* From Oklab32 */
-
-    impl From<Oklab32> for [f32; 3] {
-        #[inline]
-        fn from(c: Oklab32) -> [f32; 3] {
-            [c.l, c.a, c.b]
-        }
-    }
-
-    impl From<Oklab32> for (f32, f32, f32) {
-        #[inline]
-        fn from(c: Oklab32) -> (f32, f32, f32) {
-            (c.l, c.a, c.b)
-        }
-    }
-}
-
 #[cfg(any(feature = "std", feature = "no_std"))]
 #[cfg_attr(
     feature = "nightly",
@@ -689,20 +1062,6 @@ mod impl_from_std {
 
     /* From Oklch32 */
 
-    impl From<Oklch32> for [f32; 3] {
-        #[inline]
-        fn from(c: Oklch32) -> [f32; 3] {
-            [c.l, c.c, c.h]
-        }
-    }
-
-    impl From<Oklch32> for (f32, f32, f32) {
-        #[inline]
-        fn from(c: Oklch32) -> (f32, f32, f32) {
-            (c.l, c.c, c.h)
-        }
-    }
-
     impl From<Oklch32> for Oklab32 {
         #[inline]
         fn from(c: Oklch32) -> Oklab32 {
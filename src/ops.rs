@@ -0,0 +1,721 @@
+// acolor::ops
+//
+//! Operations over the color components.
+//
+// # TOC
+//
+// - setters
+// - iteration
+// - mapping
+// - indexing
+// - arithmetic
+// - comparison
+// - bytes
+// - arrays and slices
+//
+
+use crate::{
+    hue::{diff_degrees_f64, wrap_degrees_f64, Hue},
+    oklab::{Oklab, Oklch},
+    srgb::{LinearSrgb, LinearSrgba, Srgb, Srgba},
+};
+use core::{
+    array, fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign},
+};
+
+/* setters */
+
+// Implements builder-style setters that return the color with a component replaced.
+macro_rules! impl_with {
+    ($($type:ident: $($c:ident: $with:ident = $name:literal),+);+ $(;)?) => { $(
+        /// # Setters
+        impl<T: Copy> $type<T> {
+            $(
+                #[doc = concat!("Returns the same color with the given ", $name, ".")]
+                #[inline]
+                #[must_use]
+                pub const fn $with(mut self, $c: T) -> $type<T> {
+                    self.$c = $c;
+                    self
+                }
+            )+
+        }
+    )+ };
+}
+impl_with![
+    Srgb: r: with_red = "red", g: with_green = "green", b: with_blue = "blue";
+    Srgba: r: with_red = "red", g: with_green = "green", b: with_blue = "blue",
+        a: with_alpha = "alpha";
+    LinearSrgb: r: with_red = "red", g: with_green = "green", b: with_blue = "blue";
+    LinearSrgba: r: with_red = "red", g: with_green = "green", b: with_blue = "blue",
+        a: with_alpha = "alpha";
+    Oklab: l: with_lightness = "lightness";
+    Oklch: l: with_lightness = "lightness", c: with_chroma = "chroma", h: with_hue = "hue";
+];
+
+/* iteration */
+
+// Implements iteration over the components of a color type, in field order.
+macro_rules! impl_iter {
+    ($($type:ident: $n:literal = $($c:ident),+);+ $(;)?) => { $(
+        /// # Components iteration
+        impl<T: Copy> $type<T> {
+            /// Returns an iterator over the components, in field order.
+            #[inline]
+            pub fn iter(&self) -> array::IntoIter<T, $n> {
+                [$( self.$c ),+].into_iter()
+            }
+        }
+
+        impl<T> IntoIterator for $type<T> {
+            type Item = T;
+            type IntoIter = array::IntoIter<T, $n>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                [$( self.$c ),+].into_iter()
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a $type<T> {
+            type Item = &'a T;
+            type IntoIter = array::IntoIter<&'a T, $n>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                [$( &self.$c ),+].into_iter()
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a mut $type<T> {
+            type Item = &'a mut T;
+            type IntoIter = array::IntoIter<&'a mut T, $n>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                [$( &mut self.$c ),+].into_iter()
+            }
+        }
+    )+ };
+}
+impl_iter![
+    Srgb: 3 = r, g, b;
+    Srgba: 4 = r, g, b, a;
+    LinearSrgb: 3 = r, g, b;
+    LinearSrgba: 4 = r, g, b, a;
+    Oklab: 3 = l, a, b;
+    Oklch: 3 = l, c, h;
+];
+
+/* mapping */
+
+// Implements mapping a closure over the components of a color type.
+macro_rules! impl_map {
+    // implements the mapping methods that leave the alpha unchanged.
+    (alpha: $($type:ident),+) => { $(
+        /// # Components mapping
+        impl<T> $type<T> {
+            /// Returns a new color by applying `f` to the red, green and blue
+            /// components, leaving the alpha unchanged.
+            #[inline]
+            pub fn map_rgb(self, mut f: impl FnMut(T) -> T) -> $type<T> {
+                $type { r: f(self.r), g: f(self.g), b: f(self.b), a: self.a }
+            }
+            /// Returns a new color by applying `f` to each pair of red, green and blue
+            /// components of `self` and `other`, keeping the alpha of `self`.
+            #[inline]
+            pub fn zip_with_rgb(self, other: $type<T>, mut f: impl FnMut(T, T) -> T) -> $type<T> {
+                $type {
+                    r: f(self.r, other.r),
+                    g: f(self.g, other.g),
+                    b: f(self.b, other.b),
+                    a: self.a,
+                }
+            }
+        }
+    )+ };
+    ($($type:ident: $($c:ident),+);+ $(;)?) => { $(
+        /// # Components mapping
+        impl<T> $type<T> {
+            /// Returns a new color by applying `f` to each component, in field order.
+            #[inline]
+            pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> $type<U> {
+                $type { $( $c: f(self.$c) ),+ }
+            }
+            /// Returns a new color by applying `f` to each pair of components
+            /// of `self` and `other`, in field order.
+            #[inline]
+            pub fn zip_with<U, V>(self, other: $type<U>, mut f: impl FnMut(T, U) -> V) -> $type<V> {
+                $type { $( $c: f(self.$c, other.$c) ),+ }
+            }
+        }
+    )+ };
+}
+impl_map![
+    Srgb: r, g, b;
+    Srgba: r, g, b, a;
+    LinearSrgb: r, g, b;
+    LinearSrgba: r, g, b, a;
+    Oklab: l, a, b;
+    Oklch: l, c, h;
+];
+impl_map![alpha: Srgba, LinearSrgba];
+
+/* indexing */
+
+/// A color channel of the sRGB based types, usable as an index.
+///
+/// # Example
+/// ```
+/// use acolor::{srgb::Srgba8, Channel};
+///
+/// let mut c = Srgba8::new(10, 20, 30, 255);
+/// c[Channel::Green] = 50;
+/// assert_eq![c[1], 50];
+/// assert_eq![c[Channel::Alpha], 255];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Channel {
+    /// The red channel, at index `0`.
+    Red,
+    /// The green channel, at index `1`.
+    Green,
+    /// The blue channel, at index `2`.
+    Blue,
+    /// The alpha channel, at index `3`.
+    Alpha,
+}
+
+impl Channel {
+    /// The 3 color channels.
+    pub const RGB: [Channel; 3] = [Channel::Red, Channel::Green, Channel::Blue];
+    /// The 4 channels, including alpha.
+    pub const RGBA: [Channel; 4] = [Channel::Red, Channel::Green, Channel::Blue, Channel::Alpha];
+
+    /// Returns the index of the channel.
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// Implements `Index` and `IndexMut` by `usize`, in field order.
+macro_rules! impl_index {
+    ($($type:ident: $($i:literal = $c:ident),+);+ $(;)?) => { $(
+        /// # Panics
+        /// Panics if the index is out of bounds.
+        impl<T> Index<usize> for $type<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, index: usize) -> &T {
+                match index {
+                    $( $i => &self.$c, )+
+                    _ => index_out_of_bounds(index, stringify!($type)),
+                }
+            }
+        }
+        impl<T> IndexMut<usize> for $type<T> {
+            #[inline]
+            fn index_mut(&mut self, index: usize) -> &mut T {
+                match index {
+                    $( $i => &mut self.$c, )+
+                    _ => index_out_of_bounds(index, stringify!($type)),
+                }
+            }
+        }
+    )+ };
+    // also implements `Index` and `IndexMut` by `Channel`.
+    (channel: $($type:ident),+) => { $(
+        /// # Panics
+        /// Panics if the channel is [`Channel::Alpha`] and the type has no alpha.
+        impl<T> Index<Channel> for $type<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, channel: Channel) -> &T {
+                &self[channel.index()]
+            }
+        }
+        impl<T> IndexMut<Channel> for $type<T> {
+            #[inline]
+            fn index_mut(&mut self, channel: Channel) -> &mut T {
+                &mut self[channel.index()]
+            }
+        }
+    )+ };
+}
+impl_index![
+    Srgb: 0 = r, 1 = g, 2 = b;
+    Srgba: 0 = r, 1 = g, 2 = b, 3 = a;
+    LinearSrgb: 0 = r, 1 = g, 2 = b;
+    LinearSrgba: 0 = r, 1 = g, 2 = b, 3 = a;
+    Oklab: 0 = l, 1 = a, 2 = b;
+    Oklch: 0 = l, 1 = c, 2 = h;
+];
+impl_index![channel: Srgb, Srgba, LinearSrgb, LinearSrgba];
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn index_out_of_bounds(index: usize, name: &str) -> ! {
+    panic!("index {index} is out of bounds for {name}")
+}
+
+/* arithmetic */
+
+// Implements `Add` and `Sub` between colors of the same type, and `Mul` and `Div`
+// by a scalar, along with their assigning versions, operating on every component.
+//
+// The `u8` components use saturating arithmetic, and a zero divisor saturates at `255`.
+macro_rules! impl_arith {
+    ($($type:ident: $($c:ident),+);+ $(;)?
+     => $t:ty, $add:expr, $sub:expr, $mul:expr, $div:expr) => { $(
+        impl Add for $type<$t> {
+            type Output = $type<$t>;
+            #[inline]
+            fn add(self, rhs: $type<$t>) -> $type<$t> {
+                $type { $( $c: $add(self.$c, rhs.$c) ),+ }
+            }
+        }
+        impl Sub for $type<$t> {
+            type Output = $type<$t>;
+            #[inline]
+            fn sub(self, rhs: $type<$t>) -> $type<$t> {
+                $type { $( $c: $sub(self.$c, rhs.$c) ),+ }
+            }
+        }
+        impl Mul<$t> for $type<$t> {
+            type Output = $type<$t>;
+            #[inline]
+            fn mul(self, rhs: $t) -> $type<$t> {
+                $type { $( $c: $mul(self.$c, rhs) ),+ }
+            }
+        }
+        impl Div<$t> for $type<$t> {
+            type Output = $type<$t>;
+            #[inline]
+            fn div(self, rhs: $t) -> $type<$t> {
+                $type { $( $c: $div(self.$c, rhs) ),+ }
+            }
+        }
+        impl AddAssign for $type<$t> {
+            #[inline]
+            fn add_assign(&mut self, rhs: $type<$t>) {
+                *self = *self + rhs;
+            }
+        }
+        impl SubAssign for $type<$t> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $type<$t>) {
+                *self = *self - rhs;
+            }
+        }
+        impl MulAssign<$t> for $type<$t> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+        impl DivAssign<$t> for $type<$t> {
+            #[inline]
+            fn div_assign(&mut self, rhs: $t) {
+                *self = *self / rhs;
+            }
+        }
+    )+ };
+}
+impl_arith![Srgb: r, g, b; Srgba: r, g, b, a
+    => u8, u8::saturating_add, u8::saturating_sub, u8::saturating_mul, div_u8];
+
+// Divides a `u8`, saturating at `255` for a zero `divisor`.
+const fn div_u8(c: u8, divisor: u8) -> u8 {
+    match c.checked_div(divisor) {
+        Some(v) => v,
+        None => u8::MAX,
+    }
+}
+
+// Implements the named saturating operations for the `u8` types.
+macro_rules! impl_arith_u8 {
+    ($($type:ident: $($c:ident),+);+ $(;)?) => { $(
+        /// # Arithmetic
+        impl $type<u8> {
+            /// Adds each component of `other`, saturating at `255`.
+            ///
+            /// The same as the `+` operator, usable in const contexts.
+            #[inline]
+            pub const fn saturating_add(self, other: $type<u8>) -> $type<u8> {
+                $type { $( $c: self.$c.saturating_add(other.$c) ),+ }
+            }
+            /// Subtracts each component of `other`, saturating at `0`.
+            ///
+            /// The same as the `-` operator, usable in const contexts.
+            #[inline]
+            pub const fn saturating_sub(self, other: $type<u8>) -> $type<u8> {
+                $type { $( $c: self.$c.saturating_sub(other.$c) ),+ }
+            }
+            /// Multiplies each component by a `factor`, rounding to the nearest
+            /// integer and saturating at `0` and `255`.
+            ///
+            /// Scales every component, including alpha, if any.
+            #[inline]
+            pub const fn scale(self, factor: f32) -> $type<u8> {
+                $type { $( $c: scale_u8(self.$c, factor) ),+ }
+            }
+        }
+    )+ };
+}
+impl_arith_u8![Srgb: r, g, b; Srgba: r, g, b, a];
+
+// Multiplies a `u8` by a `factor`, rounding and saturating the result.
+const fn scale_u8(c: u8, factor: f32) -> u8 {
+    let v = c as f32 * factor + 0.5;
+    // the cast saturates, and maps `NaN` to `0`
+    v as u8
+}
+impl_arith![Srgb: r, g, b; Srgba: r, g, b, a; LinearSrgb: r, g, b; LinearSrgba: r, g, b, a;
+    Oklab: l, a, b
+    => f32, <f32 as Add>::add, <f32 as Sub>::sub, <f32 as Mul>::mul, <f32 as Div>::div];
+impl_arith![Srgb: r, g, b; Srgba: r, g, b, a; LinearSrgb: r, g, b; LinearSrgba: r, g, b, a;
+    Oklab: l, a, b
+    => f64, <f64 as Add>::add, <f64 as Sub>::sub, <f64 as Mul>::mul, <f64 as Div>::div];
+
+// Implements the arithmetic for `Oklch`, like `impl_arith`, but wrapping the hue
+// around with `$wrap`, and scaling only the lightness and chroma by a scalar.
+macro_rules! impl_arith_oklch {
+    ($($t:ty: $wrap:expr),+ $(,)?) => { $(
+        /// Adds the lightness and chroma, and the hue wrapping it around into `0..360`.
+        impl Add for Oklch<$t> {
+            type Output = Oklch<$t>;
+            #[inline]
+            fn add(self, rhs: Oklch<$t>) -> Oklch<$t> {
+                Oklch { l: self.l + rhs.l, c: self.c + rhs.c, h: $wrap(self.h + rhs.h) }
+            }
+        }
+        /// Subtracts the lightness and chroma, and the hue wrapping it around into `0..360`.
+        impl Sub for Oklch<$t> {
+            type Output = Oklch<$t>;
+            #[inline]
+            fn sub(self, rhs: Oklch<$t>) -> Oklch<$t> {
+                Oklch { l: self.l - rhs.l, c: self.c - rhs.c, h: $wrap(self.h - rhs.h) }
+            }
+        }
+        /// Scales only the lightness and chroma, keeping the hue.
+        ///
+        /// For averaging hues use [`Hue::lerp`][crate::hue::Hue::lerp],
+        /// or average in [`Oklab`] instead.
+        impl Mul<$t> for Oklch<$t> {
+            type Output = Oklch<$t>;
+            #[inline]
+            fn mul(self, rhs: $t) -> Oklch<$t> {
+                Oklch { l: self.l * rhs, c: self.c * rhs, h: self.h }
+            }
+        }
+        /// Scales only the lightness and chroma, keeping the hue.
+        ///
+        /// For averaging hues use [`Hue::lerp`][crate::hue::Hue::lerp],
+        /// or average in [`Oklab`] instead.
+        impl Div<$t> for Oklch<$t> {
+            type Output = Oklch<$t>;
+            #[inline]
+            fn div(self, rhs: $t) -> Oklch<$t> {
+                Oklch { l: self.l / rhs, c: self.c / rhs, h: self.h }
+            }
+        }
+        impl AddAssign for Oklch<$t> {
+            #[inline]
+            fn add_assign(&mut self, rhs: Oklch<$t>) {
+                *self = *self + rhs;
+            }
+        }
+        impl SubAssign for Oklch<$t> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Oklch<$t>) {
+                *self = *self - rhs;
+            }
+        }
+        impl MulAssign<$t> for Oklch<$t> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+        impl DivAssign<$t> for Oklch<$t> {
+            #[inline]
+            fn div_assign(&mut self, rhs: $t) {
+                *self = *self / rhs;
+            }
+        }
+    )+ };
+}
+impl_arith_oklch![f32: wrap_degrees_f32, f64: wrap_degrees_f64];
+
+// Wraps an `f32` angle in degrees into `0..360`, through `Hue`.
+const fn wrap_degrees_f32(degrees: f32) -> f32 {
+    Hue::new(degrees).degrees()
+}
+
+/* comparison */
+
+// Implements the approximate equality for the float types.
+macro_rules! impl_approx_eq {
+    ($t:ty: $($type:ident: $($c:ident),+);+ $(;)?) => { $(
+        /// # Comparison
+        impl $type<$t> {
+            /// Returns `true` if each component differs from `other`'s by at most `epsilon`.
+            ///
+            /// The components are compared as they are, and `NaN` components are never equal.
+            #[inline]
+            pub fn approx_eq(&self, other: &Self, epsilon: $t) -> bool {
+                let close = |d: $t| d <= epsilon && -d <= epsilon;
+                $( close(self.$c - other.$c) )&&+
+            }
+        }
+    )+ };
+}
+impl_approx_eq![f32: Srgb: r, g, b; Srgba: r, g, b, a; LinearSrgb: r, g, b;
+    LinearSrgba: r, g, b, a; Oklab: l, a, b];
+impl_approx_eq![f64: Srgb: r, g, b; Srgba: r, g, b, a; LinearSrgb: r, g, b;
+    LinearSrgba: r, g, b, a; Oklab: l, a, b];
+
+// Implements the approximate equality for `Oklch`, comparing the hues with `$diff`.
+macro_rules! impl_approx_eq_oklch {
+    ($($t:ty: $diff:expr),+ $(,)?) => { $(
+        /// # Comparison
+        impl Oklch<$t> {
+            /// Returns `true` if each component differs from `other`'s by at most `epsilon`.
+            ///
+            /// The hues are compared by their shortest distance around the circle,
+            /// so `359.9` and `0.1` are `0.2` degrees apart. `NaN` components are never equal.
+            #[inline]
+            pub fn approx_eq(&self, other: &Self, epsilon: $t) -> bool {
+                let close = |d: $t| d <= epsilon && -d <= epsilon;
+                close(self.l - other.l) && close(self.c - other.c) && close($diff(self.h, other.h))
+            }
+        }
+    )+ };
+}
+impl_approx_eq_oklch![f32: diff_degrees_f32, f64: diff_degrees_f64];
+
+// Returns the shortest signed distance in degrees between two `f32` angles, through `Hue`.
+const fn diff_degrees_f32(from: f32, to: f32) -> f32 {
+    Hue::new(from).diff(Hue::new(to))
+}
+
+// Implements the tolerance comparisons for the `u8` types.
+macro_rules! impl_approx_eq_u8 {
+    ($($type:ident: $($c:ident),+);+ $(;)?) => { $(
+        /// # Comparison
+        impl $type<u8> {
+            /// Returns the maximum difference between a component and `other`'s.
+            #[inline]
+            pub const fn max_channel_diff(&self, other: &Self) -> u8 {
+                let mut max = 0;
+                $(
+                    let d = self.$c.abs_diff(other.$c);
+                    if d > max {
+                        max = d;
+                    }
+                )+
+                max
+            }
+
+            /// Returns `true` if each component differs from `other`'s by at most `tolerance`.
+            ///
+            /// Useful for comparing colors after a round-trip through a quantized format.
+            #[inline]
+            pub const fn approx_eq(&self, other: &Self, tolerance: u8) -> bool {
+                self.max_channel_diff(other) <= tolerance
+            }
+        }
+    )+ };
+}
+impl_approx_eq_u8![Srgb: r, g, b; Srgba: r, g, b, a];
+
+/* bytes */
+
+// Implements the byte conversions for the `u8` types.
+macro_rules! impl_bytes_u8 {
+    ($($type:ident: $n:literal = $($c:ident),+);+ $(;)?) => { $(
+        /// # Bytes conversions
+        impl $type<u8> {
+            /// Returns the components as bytes, in field order.
+            #[inline]
+            pub const fn to_bytes(self) -> [u8; $n] {
+                [$( self.$c ),+]
+            }
+            /// Returns a new color from bytes, in field order.
+            #[inline]
+            pub const fn from_bytes(bytes: [u8; $n]) -> $type<u8> {
+                let [$( $c ),+] = bytes;
+                $type { $( $c ),+ }
+            }
+        }
+
+        /// # Bytes views
+        #[cfg(all(feature = "unsafe", not(feature = "safe")))]
+        #[cfg_attr(feature = "nightly", doc(cfg(feature = "unsafe")))]
+        impl $type<u8> {
+            /// Returns a view of the components as bytes, in field order.
+            #[inline]
+            pub fn as_bytes(&self) -> &[u8; $n] {
+                // SAFETY: the type is `repr(C)` with only `u8` fields, no padding.
+                unsafe { &*(self as *const Self as *const [u8; $n]) }
+            }
+            /// Returns a mutable view of the components as bytes, in field order.
+            #[inline]
+            pub fn as_bytes_mut(&mut self) -> &mut [u8; $n] {
+                // SAFETY: the type is `repr(C)` with only `u8` fields, no padding.
+                unsafe { &mut *(self as *mut Self as *mut [u8; $n]) }
+            }
+            /// Returns a view of a slice of colors as interleaved bytes.
+            #[inline]
+            pub fn slice_as_bytes(colors: &[$type<u8>]) -> &[u8] {
+                // SAFETY: the type is `repr(C)` with only `u8` fields, no padding, align 1.
+                unsafe { core::slice::from_raw_parts(colors.as_ptr().cast(), colors.len() * $n) }
+            }
+            /// Returns a view of interleaved bytes as a slice of colors.
+            ///
+            /// Returns `None` if the length of `bytes` is not a multiple of the components.
+            #[inline]
+            pub fn slice_from_bytes(bytes: &[u8]) -> Option<&[$type<u8>]> {
+                if bytes.len() % $n != 0 {
+                    return None;
+                }
+                // SAFETY: the type is `repr(C)` with only `u8` fields, no padding, align 1.
+                Some(unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / $n) })
+            }
+            /// Returns a mutable view of interleaved bytes as a slice of colors.
+            ///
+            /// Returns `None` if the length of `bytes` is not a multiple of the components.
+            #[inline]
+            pub fn slice_from_bytes_mut(bytes: &mut [u8]) -> Option<&mut [$type<u8>]> {
+                if bytes.len() % $n != 0 {
+                    return None;
+                }
+                // SAFETY: the type is `repr(C)` with only `u8` fields, no padding, align 1.
+                Some(unsafe {
+                    core::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), bytes.len() / $n)
+                })
+            }
+        }
+    )+ };
+}
+impl_bytes_u8![Srgb: 3 = r, g, b; Srgba: 4 = r, g, b, a];
+
+// Implements the little endian byte conversions for the float types.
+macro_rules! impl_bytes_float {
+    ($t:ty, $size:literal: $($type:ident: $n:literal = $($c:ident),+);+ $(;)?) => { $(
+        /// # Bytes conversions
+        impl $type<$t> {
+            /// Returns the components as little endian bytes, in field order.
+            #[inline]
+            pub fn to_le_bytes(self) -> [u8; $n * $size] {
+                let mut bytes = [0; $n * $size];
+                for (chunk, c) in bytes.chunks_exact_mut($size).zip(self) {
+                    chunk.copy_from_slice(&c.to_le_bytes());
+                }
+                bytes
+            }
+            /// Returns a new color from little endian bytes, in field order.
+            #[inline]
+            pub fn from_le_bytes(bytes: [u8; $n * $size]) -> $type<$t> {
+                let mut c = [0.; $n];
+                for (c, chunk) in c.iter_mut().zip(bytes.chunks_exact($size)) {
+                    let mut b = [0; $size];
+                    b.copy_from_slice(chunk);
+                    *c = <$t>::from_le_bytes(b);
+                }
+                let [$( $c ),+] = c;
+                $type { $( $c ),+ }
+            }
+        }
+    )+ };
+}
+impl_bytes_float![f32, 4:
+    Srgb: 3 = r, g, b; Srgba: 4 = r, g, b, a; LinearSrgb: 3 = r, g, b; LinearSrgba: 4 = r, g, b, a;
+    Oklab: 3 = l, a, b; Oklch: 3 = l, c, h];
+impl_bytes_float![f64, 8:
+    Srgb: 3 = r, g, b; Srgba: 4 = r, g, b, a; LinearSrgb: 3 = r, g, b; LinearSrgba: 4 = r, g, b, a;
+    Oklab: 3 = l, a, b; Oklch: 3 = l, c, h];
+
+/* arrays and slices */
+
+/// An error converting a slice of the wrong length into a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthError {
+    /// The expected number of components.
+    pub expected: usize,
+    /// The found number of components.
+    pub found: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} components, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+// Implements the conversions from and into arrays and tuples, and from slices.
+macro_rules! impl_from_array {
+    ($($type:ident: $n:literal, $tuple:ty = $($c:ident),+);+ $(;)?) => { $(
+        impl<T> From<[T; $n]> for $type<T> {
+            #[inline]
+            fn from(c: [T; $n]) -> $type<T> {
+                let [$( $c ),+] = c;
+                $type { $( $c ),+ }
+            }
+        }
+        impl<T> From<$type<T>> for [T; $n] {
+            #[inline]
+            fn from(c: $type<T>) -> [T; $n] {
+                [$( c.$c ),+]
+            }
+        }
+        impl<T> From<$tuple> for $type<T> {
+            #[inline]
+            fn from(c: $tuple) -> $type<T> {
+                let ($( $c ),+) = c;
+                $type { $( $c ),+ }
+            }
+        }
+        impl<T> From<$type<T>> for $tuple {
+            #[inline]
+            fn from(c: $type<T>) -> $tuple {
+                ($( c.$c ),+)
+            }
+        }
+        /// # Errors
+        /// Returns [`LengthError`] if the length of the slice
+        /// doesn't match the number of components.
+        impl<T: Copy> TryFrom<&[T]> for $type<T> {
+            type Error = LengthError;
+
+            #[inline]
+            fn try_from(c: &[T]) -> Result<$type<T>, LengthError> {
+                let c: [T; $n] = c.try_into().map_err(|_| LengthError {
+                    expected: $n,
+                    found: c.len(),
+                })?;
+                Ok(c.into())
+            }
+        }
+    )+ };
+}
+impl_from_array![
+    Srgb: 3, (T, T, T) = r, g, b;
+    Srgba: 4, (T, T, T, T) = r, g, b, a;
+    LinearSrgb: 3, (T, T, T) = r, g, b;
+    LinearSrgba: 4, (T, T, T, T) = r, g, b, a;
+    Oklab: 3, (T, T, T) = l, a, b;
+    Oklch: 3, (T, T, T) = l, c, h;
+];
@@ -0,0 +1,577 @@
+// acolor::parse
+//
+//! Parsing colors from strings.
+//!
+//! Supports the CSS functional syntax of [`Oklab32`] and [`Oklch32`],
+//! including percentages, `none` components and hue angle units.
+//!
+//! Also supports the `color()` function with all its predefined color spaces,
+//! through [`PredefinedColor`], which can be converted into the sRGB types.
+//!
+//! # Example
+//! ```
+//! use acolor::oklab::{Oklab32, Oklch32};
+//!
+//! let lab: Oklab32 = "oklab(40% 0.1 -25%)".parse().unwrap();
+//! assert_eq![Oklab32::to_array(lab), [0.4, 0.1, -0.1]];
+//!
+//! let lch: Oklch32 = "oklch(0.7 none 0.5turn / 50%)".parse().unwrap();
+//! assert_eq![Oklch32::to_array(lch), [0.7, 0., 180.]];
+//! ```
+//!
+//! # Links
+//! - <https://www.w3.org/TR/css-color-4/#specifying-oklab-oklch>
+//! - <https://www.w3.org/TR/css-color-4/#predefined>
+//
+// # TOC
+//
+// - ParseColorError
+// - oklab
+// - color()
+//   - PredefinedSpace
+//   - PredefinedColor
+// - helpers
+//
+
+use crate::{
+    hue::Hue,
+    oklab::{Oklab32, Oklch32},
+};
+#[cfg(any(feature = "std", feature = "no_std"))]
+use crate::{
+    math::{mat_mul_vec, powf},
+    srgb::{encode32, linearize32, LinearSrgb32, LinearSrgba32, Srgb32, Srgba32},
+    GAMMA_32,
+};
+use core::{
+    f32::consts::PI,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// An error parsing a color from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseColorError {
+    /// The color function name or the parentheses are missing or wrong.
+    InvalidSyntax,
+    /// The number of components is wrong.
+    WrongComponentCount,
+    /// A component is not a valid number, percentage, angle or `none`.
+    InvalidComponent,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidSyntax => write!(f, "invalid color syntax"),
+            ParseColorError::WrongComponentCount => write!(f, "wrong number of components"),
+            ParseColorError::InvalidComponent => write!(f, "invalid color component"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError {}
+
+/* oklab */
+
+// The value of `100%` for the `a`, `b` and chroma components.
+const AB_PERCENT: f32 = 0.4;
+
+/// Parses the CSS `oklab()` syntax, e.g. `oklab(59% 0.1 -0.1)`.
+///
+/// The lightness percentage maps `100%` to `1.0`, and the `a` and `b` ones to `0.4`.
+/// An alpha component is validated and discarded.
+impl FromStr for Oklab32 {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Oklab32, ParseColorError> {
+        let ([l, a, b], alpha) = parse_function(s, "oklab")?;
+        parse_alpha(alpha)?;
+        Ok(Oklab32 {
+            l: parse_number(l, 1.)?,
+            a: parse_number(a, AB_PERCENT)?,
+            b: parse_number(b, AB_PERCENT)?,
+        })
+    }
+}
+
+/// Parses the CSS `oklch()` syntax, e.g. `oklch(59% 0.15 45deg)`.
+///
+/// The lightness percentage maps `100%` to `1.0`, and the chroma one to `0.4`.
+/// The hue accepts the `deg`, `grad`, `rad` and `turn` units, and is wrapped to `0..360`.
+/// An alpha component is validated and discarded.
+impl FromStr for Oklch32 {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Oklch32, ParseColorError> {
+        let ([l, c, h], alpha) = parse_function(s, "oklch")?;
+        parse_alpha(alpha)?;
+        Ok(Oklch32 {
+            l: parse_number(l, 1.)?,
+            c: parse_number(c, AB_PERCENT)?,
+            h: parse_hue(h)?,
+        })
+    }
+}
+
+/* color() */
+
+/// A predefined color space of the CSS `color()` function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PredefinedSpace {
+    /// `srgb`, the non-linear sRGB space.
+    Srgb,
+    /// `srgb-linear`, the linear sRGB space.
+    SrgbLinear,
+    /// `display-p3`, with the P3 primaries, the D65 white point and the sRGB curve.
+    DisplayP3,
+    /// `a98-rgb`, the Adobe RGB (1998) space.
+    A98Rgb,
+    /// `prophoto-rgb`, the ProPhoto RGB space, with the D50 white point.
+    ProphotoRgb,
+    /// `rec2020`, the ITU-R BT.2020 space.
+    Rec2020,
+    /// `xyz-d50`, the CIE XYZ space with the D50 white point.
+    XyzD50,
+    /// `xyz-d65`, or `xyz`, the CIE XYZ space with the D65 white point.
+    XyzD65,
+}
+
+impl PredefinedSpace {
+    /// All the predefined color spaces.
+    pub const ALL: [PredefinedSpace; 8] = [
+        PredefinedSpace::Srgb,
+        PredefinedSpace::SrgbLinear,
+        PredefinedSpace::DisplayP3,
+        PredefinedSpace::A98Rgb,
+        PredefinedSpace::ProphotoRgb,
+        PredefinedSpace::Rec2020,
+        PredefinedSpace::XyzD50,
+        PredefinedSpace::XyzD65,
+    ];
+
+    /// Returns the CSS name of the color space.
+    pub const fn name(self) -> &'static str {
+        match self {
+            PredefinedSpace::Srgb => "srgb",
+            PredefinedSpace::SrgbLinear => "srgb-linear",
+            PredefinedSpace::DisplayP3 => "display-p3",
+            PredefinedSpace::A98Rgb => "a98-rgb",
+            PredefinedSpace::ProphotoRgb => "prophoto-rgb",
+            PredefinedSpace::Rec2020 => "rec2020",
+            PredefinedSpace::XyzD50 => "xyz-d50",
+            PredefinedSpace::XyzD65 => "xyz-d65",
+        }
+    }
+
+    /// Returns the color space with the given CSS name, ignoring case.
+    pub fn from_name(name: &str) -> Option<PredefinedSpace> {
+        if name.eq_ignore_ascii_case("xyz") {
+            return Some(PredefinedSpace::XyzD65);
+        }
+        PredefinedSpace::ALL
+            .into_iter()
+            .find(|space| space.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A color of the CSS `color()` function, e.g. `color(display-p3 1 0.5 0 / 50%)`.
+///
+/// The components are stored as given, in the color `space`.
+///
+/// # Example
+/// ```
+/// # #[cfg(any(feature = "std", feature = "no_std"))] {
+/// use acolor::{parse::{PredefinedColor, PredefinedSpace}, srgb::{Srgb32, Srgb8}};
+///
+/// let c: PredefinedColor = "color(display-p3 1 0 0 / 100%)".parse().unwrap();
+/// assert_eq![c.space, PredefinedSpace::DisplayP3];
+/// assert_eq![c.to_string(), "color(display-p3 1 0 0)"];
+///
+/// let srgb = c.to_linear_srgba32().to_srgba32();
+/// assert![srgb.r > 1.]; // out of the sRGB gamut
+///
+/// let p3 = PredefinedColor::from_linear_srgba32(PredefinedSpace::DisplayP3, srgb.to_linear_srgba32());
+/// assert![(p3.components[0] - 1.).abs() < 1e-3];
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredefinedColor {
+    /// The color space.
+    pub space: PredefinedSpace,
+    /// The components, in the color space.
+    pub components: [f32; 3],
+    /// The alpha, in `0..=1`.
+    pub alpha: f32,
+}
+
+/// Parses the CSS `color()` syntax with any of the predefined color spaces.
+///
+/// Percentages map `100%` to `1.0`.
+impl FromStr for PredefinedColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<PredefinedColor, ParseColorError> {
+        let ([space, c0, c1, c2], alpha) = parse_function(s, "color")?;
+        Ok(PredefinedColor {
+            space: PredefinedSpace::from_name(space).ok_or(ParseColorError::InvalidSyntax)?,
+            components: [
+                parse_number(c0, 1.)?,
+                parse_number(c1, 1.)?,
+                parse_number(c2, 1.)?,
+            ],
+            alpha: parse_alpha(alpha)?,
+        })
+    }
+}
+
+/// Serializes using the CSS `color()` syntax, omitting an opaque alpha.
+///
+/// The components honor the formatting precision.
+impl Display for PredefinedColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "color({}", self.space.name())?;
+        for c in self.components {
+            f.write_str(" ")?;
+            Display::fmt(&c, f)?;
+        }
+        if self.alpha != 1. {
+            f.write_str(" / ")?;
+            Display::fmt(&self.alpha, f)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// # Conversions
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl PredefinedColor {
+    /// Converts the color into linear sRGB, without gamut mapping.
+    pub fn to_linear_srgba32(&self) -> LinearSrgba32 {
+        let linear = self.components.map(|c| self.space.decode(c));
+        let [r, g, b] = match self.space {
+            PredefinedSpace::Srgb | PredefinedSpace::SrgbLinear => linear,
+            space => mat_mul_vec(XYZ_D65_TO_SRGB, space.linear_to_xyz_d65(linear)),
+        };
+        LinearSrgba32::new(r, g, b, self.alpha)
+    }
+
+    /// Converts a linear sRGB color into the given `space`, without gamut mapping.
+    pub fn from_linear_srgba32(space: PredefinedSpace, c: LinearSrgba32) -> PredefinedColor {
+        let linear = [c.r, c.g, c.b];
+        let linear = match space {
+            PredefinedSpace::Srgb | PredefinedSpace::SrgbLinear => linear,
+            space => space.xyz_d65_to_linear(mat_mul_vec(SRGB_TO_XYZ_D65, linear)),
+        };
+        PredefinedColor {
+            space,
+            components: linear.map(|c| space.encode(c)),
+            alpha: c.a,
+        }
+    }
+}
+
+// Implements `FromStr` for the sRGB float types, from any predefined color space.
+macro_rules! impl_from_str_srgb {
+    ($($type:ty: $c:ident => $convert:expr),+ $(,)?) => { $(
+        /// Parses the CSS `color()` syntax with any of the predefined color spaces,
+        /// converting from them without gamut mapping.
+        #[cfg(any(feature = "std", feature = "no_std"))]
+        #[cfg_attr(
+            feature = "nightly",
+            doc(cfg(any(feature = "std", feature = "no_std")))
+        )]
+        impl FromStr for $type {
+            type Err = ParseColorError;
+
+            fn from_str(s: &str) -> Result<$type, ParseColorError> {
+                let $c: PredefinedColor = s.parse()?;
+                Ok($convert)
+            }
+        }
+    )+ };
+}
+impl_from_str_srgb![
+    Srgb32: c => match c.space {
+        PredefinedSpace::Srgb => Srgb32::from_array(c.components),
+        _ => c.to_linear_srgba32().to_linear_srgb32().to_srgb32(),
+    },
+    Srgba32: c => match c.space {
+        PredefinedSpace::Srgb => {
+            let [r, g, b] = c.components;
+            Srgba32::new(r, g, b, c.alpha)
+        }
+        _ => c.to_linear_srgba32().to_srgba32(),
+    },
+    LinearSrgb32: c => c.to_linear_srgba32().to_linear_srgb32(),
+    LinearSrgba32: c => c.to_linear_srgba32(),
+];
+
+// The matrices from linear RGB into XYZ, and back, from the CSS Color 4 specification.
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) mod matrices {
+    pub(crate) const SRGB_TO_XYZ_D65: [[f32; 3]; 3] = [
+        [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+        [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+        [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+    ];
+    pub(crate) const XYZ_D65_TO_SRGB: [[f32; 3]; 3] = [
+        [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+        [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+        [
+            0.05563007969699366,
+            -0.20397695888897652,
+            1.0569715142428786,
+        ],
+    ];
+    pub(super) const P3_TO_XYZ_D65: [[f32; 3]; 3] = [
+        [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+        [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+        [0.0, 0.04511338185890264, 1.043944368900976],
+    ];
+    pub(super) const XYZ_D65_TO_P3: [[f32; 3]; 3] = [
+        [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+        [
+            -0.8294889695615747,
+            1.7626640603183463,
+            0.023624685841943577,
+        ],
+        [
+            0.03584583024378447,
+            -0.07617238926804182,
+            0.9568845240076872,
+        ],
+    ];
+    pub(super) const A98_TO_XYZ_D65: [[f32; 3]; 3] = [
+        [0.5766690429101305, 0.1855582379065463, 0.1882286462349947],
+        [0.29734497525053605, 0.6273635662554661, 0.07529145849399788],
+        [0.02703136138641234, 0.07068885253582723, 0.9913375368376388],
+    ];
+    pub(super) const XYZ_D65_TO_A98: [[f32; 3]; 3] = [
+        [
+            2.0415879038107465,
+            -0.5650069742788596,
+            -0.34473135077832956,
+        ],
+        [-0.9692436362808795, 1.8759675015077202, 0.04155505740717557],
+        [
+            0.013444280632031142,
+            -0.11836239223101838,
+            1.0151749943912054,
+        ],
+    ];
+    pub(super) const REC2020_TO_XYZ_D65: [[f32; 3]; 3] = [
+        [0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+        [0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+        [0.0, 0.028072693049087428, 1.060985057710791],
+    ];
+    pub(super) const XYZ_D65_TO_REC2020: [[f32; 3]; 3] = [
+        [1.716651187971268, -0.355670783776392, -0.253366281373660],
+        [-0.666684351832489, 1.616481236634939, 0.0157685458139111],
+        [0.017639857445311, -0.042770613257809, 0.942103121235474],
+    ];
+    pub(super) const PROPHOTO_TO_XYZ_D50: [[f32; 3]; 3] = [
+        [0.7977604896723027, 0.13518583717574031, 0.0313493495815248],
+        [
+            0.2880711282292934,
+            0.7118432178101014,
+            0.00008565396060525902,
+        ],
+        [0.0, 0.0, 0.8251046025104601],
+    ];
+    pub(super) const XYZ_D50_TO_PROPHOTO: [[f32; 3]; 3] = [
+        [
+            1.3457989731028281,
+            -0.25558010007997534,
+            -0.05110628506753401,
+        ],
+        [-0.5446224939028347, 1.5082327413132781, 0.02053603239147973],
+        [0.0, 0.0, 1.2119675456389454],
+    ];
+    // Bradford chromatic adaptation.
+    pub(super) const D50_TO_D65: [[f32; 3]; 3] = [
+        [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+        [
+            -0.0283697093338637,
+            1.0099953980813041,
+            0.021041441191917323,
+        ],
+        [
+            0.012314014864481998,
+            -0.020507649298898964,
+            1.330365926242124,
+        ],
+    ];
+    pub(super) const D65_TO_D50: [[f32; 3]; 3] = [
+        [
+            1.0479297925449969,
+            0.022946870601609652,
+            -0.05019226628920524,
+        ],
+        [
+            0.02962780877005599,
+            0.9904344267538799,
+            -0.017073799063418826,
+        ],
+        [
+            -0.009243040646204504,
+            0.015055191490298152,
+            0.7518742814281371,
+        ],
+    ];
+}
+#[cfg(any(feature = "std", feature = "no_std"))]
+use matrices::*;
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+impl PredefinedSpace {
+    // Decodes a component into linear light, extending the curve to negative values.
+    fn decode(self, c: f32) -> f32 {
+        let f = |c: f32| match self {
+            PredefinedSpace::Srgb | PredefinedSpace::DisplayP3 => linearize32(c, GAMMA_32),
+            PredefinedSpace::A98Rgb => powf(c, 563. / 256.),
+            PredefinedSpace::ProphotoRgb if c < 16. / 512. => c / 16.,
+            PredefinedSpace::ProphotoRgb => powf(c, 1.8),
+            PredefinedSpace::Rec2020 if c < REC2020_BETA * 4.5 => c / 4.5,
+            PredefinedSpace::Rec2020 => powf((c + REC2020_ALPHA - 1.) / REC2020_ALPHA, 1. / 0.45),
+            _ => c,
+        };
+        if c < 0. {
+            -f(-c)
+        } else {
+            f(c)
+        }
+    }
+
+    // Encodes a linear component, extending the curve to negative values.
+    fn encode(self, c: f32) -> f32 {
+        let f = |c: f32| match self {
+            PredefinedSpace::Srgb | PredefinedSpace::DisplayP3 => encode32(c),
+            PredefinedSpace::A98Rgb => powf(c, 256. / 563.),
+            PredefinedSpace::ProphotoRgb if c < 1. / 512. => c * 16.,
+            PredefinedSpace::ProphotoRgb => powf(c, 1. / 1.8),
+            PredefinedSpace::Rec2020 if c < REC2020_BETA => c * 4.5,
+            PredefinedSpace::Rec2020 => REC2020_ALPHA * powf(c, 0.45) - (REC2020_ALPHA - 1.),
+            _ => c,
+        };
+        if c < 0. {
+            -f(-c)
+        } else {
+            f(c)
+        }
+    }
+
+    // Converts linear components into XYZ relative to D65.
+    fn linear_to_xyz_d65(self, c: [f32; 3]) -> [f32; 3] {
+        match self {
+            PredefinedSpace::Srgb | PredefinedSpace::SrgbLinear => mat_mul_vec(SRGB_TO_XYZ_D65, c),
+            PredefinedSpace::DisplayP3 => mat_mul_vec(P3_TO_XYZ_D65, c),
+            PredefinedSpace::A98Rgb => mat_mul_vec(A98_TO_XYZ_D65, c),
+            PredefinedSpace::ProphotoRgb => {
+                mat_mul_vec(D50_TO_D65, mat_mul_vec(PROPHOTO_TO_XYZ_D50, c))
+            }
+            PredefinedSpace::Rec2020 => mat_mul_vec(REC2020_TO_XYZ_D65, c),
+            PredefinedSpace::XyzD50 => mat_mul_vec(D50_TO_D65, c),
+            PredefinedSpace::XyzD65 => c,
+        }
+    }
+
+    // Converts XYZ relative to D65 into linear components.
+    fn xyz_d65_to_linear(self, c: [f32; 3]) -> [f32; 3] {
+        match self {
+            PredefinedSpace::Srgb | PredefinedSpace::SrgbLinear => mat_mul_vec(XYZ_D65_TO_SRGB, c),
+            PredefinedSpace::DisplayP3 => mat_mul_vec(XYZ_D65_TO_P3, c),
+            PredefinedSpace::A98Rgb => mat_mul_vec(XYZ_D65_TO_A98, c),
+            PredefinedSpace::ProphotoRgb => {
+                mat_mul_vec(XYZ_D50_TO_PROPHOTO, mat_mul_vec(D65_TO_D50, c))
+            }
+            PredefinedSpace::Rec2020 => mat_mul_vec(XYZ_D65_TO_REC2020, c),
+            PredefinedSpace::XyzD50 => mat_mul_vec(D65_TO_D50, c),
+            PredefinedSpace::XyzD65 => c,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+const REC2020_ALPHA: f32 = 1.09929682680944;
+#[cfg(any(feature = "std", feature = "no_std"))]
+const REC2020_BETA: f32 = 0.018053968510807;
+
+/* helpers */
+
+// Returns the `N` components of the function `name`, and the alpha if present.
+fn parse_function<'a, const N: usize>(
+    s: &'a str,
+    name: &str,
+) -> Result<([&'a str; N], Option<&'a str>), ParseColorError> {
+    let s = s.trim();
+    let inner = s
+        .get(..name.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(name))
+        .and_then(|_| s[name.len()..].trim_start().strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or(ParseColorError::InvalidSyntax)?;
+
+    let (components, alpha) = match inner.split_once('/') {
+        Some((components, alpha)) => (components, Some(alpha.trim())),
+        None => (inner, None),
+    };
+
+    let mut words = components.split_whitespace();
+    let mut out = [""; N];
+    for c in out.iter_mut() {
+        *c = words.next().ok_or(ParseColorError::WrongComponentCount)?;
+    }
+    if words.next().is_some() {
+        return Err(ParseColorError::WrongComponentCount);
+    }
+    Ok((out, alpha))
+}
+
+// Parses an optional alpha, which defaults to `1`.
+fn parse_alpha(alpha: Option<&str>) -> Result<f32, ParseColorError> {
+    alpha.map_or(Ok(1.), |a| parse_number(a, 1.))
+}
+
+// Parses a number, a percentage where `100%` equals `percent`, or `none` as zero.
+fn parse_number(s: &str, percent: f32) -> Result<f32, ParseColorError> {
+    if s.eq_ignore_ascii_case("none") {
+        return Ok(0.);
+    }
+    let (s, is_percent) = match s.strip_suffix('%') {
+        Some(s) => (s, true),
+        None => (s, false),
+    };
+    let n: f32 = s.parse().map_err(|_| ParseColorError::InvalidComponent)?;
+    if !n.is_finite() {
+        return Err(ParseColorError::InvalidComponent);
+    }
+    Ok(if is_percent { n * percent / 100. } else { n })
+}
+
+// Parses a hue in degrees, with an optional angle unit, or `none` as zero.
+fn parse_hue(s: &str) -> Result<f32, ParseColorError> {
+    if s.eq_ignore_ascii_case("none") {
+        return Ok(0.);
+    }
+    let units: [(&str, f32); 4] = [
+        ("deg", 1.),
+        ("grad", 0.9),
+        ("rad", 180. / PI),
+        ("turn", 360.),
+    ];
+    let (s, scale) = units
+        .iter()
+        .find_map(|(unit, scale)| s.strip_suffix(unit).map(|s| (s, *scale)))
+        .unwrap_or((s, 1.));
+    let n: f32 = s.parse().map_err(|_| ParseColorError::InvalidComponent)?;
+    if !n.is_finite() {
+        return Err(ParseColorError::InvalidComponent);
+    }
+    Ok(Hue::new(n * scale).degrees())
+}
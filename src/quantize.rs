@@ -0,0 +1,414 @@
+// acolor::quantize
+//
+//! Color quantization, reducing many colors into a small palette.
+//!
+//! - [`OctreeQuantizer`] builds a palette incrementally, in sRGB space.
+//! - [`kmeans_oklab`] extracts the dominant colors of an image, in Oklab space.
+//!
+//! # Example
+//! ```
+//! use acolor::{quantize::OctreeQuantizer, srgb::Srgb8};
+//!
+//! let mut q = OctreeQuantizer::new(2);
+//! q.extend([Srgb8::new(250, 0, 0), Srgb8::new(252, 0, 0), Srgb8::new(0, 0, 200)]);
+//! assert_eq![q.palette(), [Srgb8::new(251, 0, 0), Srgb8::new(0, 0, 200)]];
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/Octree#Color_quantization>
+//! - <https://en.wikipedia.org/wiki/K-means%2B%2B>
+//
+// # TOC
+//
+// - OctreeQuantizer
+// - kmeans_oklab
+//
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+use crate::oklab::Oklab32;
+use crate::srgb::Srgb8;
+use alloc::vec::Vec;
+use core::{cmp::Reverse, mem};
+
+/// The depth of the leaves, one level per bit of each component.
+const MAX_DEPTH: usize = 8;
+
+/// The minimum number of leaves kept in the tree, so that the root is never merged
+/// while it has room for a new branch.
+const MIN_LEAVES: usize = 8;
+
+/// An octree color quantizer.
+///
+/// Colors are inserted one at a time, so streaming pixel sources can be
+/// quantized without buffering the whole image. Whenever the number of leaves
+/// exceeds the maximum number of colors, the least populated node of the deepest
+/// level is merged, which keeps the memory used bounded by the palette size.
+///
+/// Fewer than 8 maximum colors are reached by merging the nearest leaves
+/// when building the palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OctreeQuantizer {
+    max_colors: usize,
+    nodes: Vec<Node>,
+    // The indices of the nodes that are not leaves, by depth.
+    levels: [Vec<u32>; MAX_DEPTH],
+    // The indices of the nodes available for reuse.
+    free: Vec<u32>,
+    leaves: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    // The index of each child, where `0` (the root) means there's none.
+    children: [u32; 8],
+    leaf: bool,
+    count: u64,
+    sum: [u64; 3],
+}
+
+/// # Constructors
+impl OctreeQuantizer {
+    /// Returns a new empty quantizer, producing at most `max_colors` colors.
+    ///
+    /// A `max_colors` of `0` is treated as `1`.
+    pub fn new(max_colors: usize) -> OctreeQuantizer {
+        let mut levels: [Vec<u32>; MAX_DEPTH] = Default::default();
+        levels[0].push(0);
+        OctreeQuantizer {
+            max_colors: max_colors.max(1),
+            nodes: Vec::from([Node::default()]),
+            levels,
+            free: Vec::new(),
+            leaves: 0,
+        }
+    }
+}
+
+/// # Getters
+impl OctreeQuantizer {
+    /// Returns the maximum number of colors.
+    pub fn max_colors(&self) -> usize {
+        self.max_colors
+    }
+    /// Returns the current number of colors of the palette.
+    pub fn len(&self) -> usize {
+        self.leaves.min(self.max_colors)
+    }
+    /// Returns `true` if no colors have been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.leaves == 0
+    }
+}
+
+/// # Operations
+impl OctreeQuantizer {
+    /// Inserts a color.
+    pub fn insert(&mut self, c: Srgb8) {
+        let rgb = [c.r, c.g, c.b];
+        let (mut idx, mut depth) = (0, 0);
+        loop {
+            let node = &mut self.nodes[idx];
+            node.count += 1;
+            for (sum, c) in node.sum.iter_mut().zip(rgb) {
+                *sum += c as u64;
+            }
+            if node.leaf {
+                break;
+            }
+            let branch = branch(rgb, depth);
+            depth += 1;
+            idx = match node.children[branch] {
+                0 => {
+                    let child = self.new_node(depth);
+                    self.nodes[idx].children[branch] = child as u32;
+                    child
+                }
+                child => child as usize,
+            };
+        }
+        while self.leaves > self.max_colors.max(MIN_LEAVES) {
+            self.reduce();
+        }
+    }
+
+    /// Returns the palette, ordered from the most to the least frequent color.
+    pub fn palette(&self) -> Vec<Srgb8> {
+        let mut leaves = Vec::with_capacity(self.leaves);
+        let mut stack = Vec::from([0]);
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            if node.leaf {
+                leaves.push((node.count, node.sum));
+            } else {
+                let children = node.children.iter().rev().filter(|c| **c != 0);
+                stack.extend(children.map(|c| *c as usize));
+            }
+        }
+        leaves.sort_by_key(|(count, _)| Reverse(*count));
+
+        // merges the least frequent leaves into their nearest remaining ones
+        while leaves.len() > self.max_colors {
+            let (count, sum) = leaves.pop().expect("more than 1 leaf");
+            let c = average(count, sum);
+            let nearest = leaves
+                .iter_mut()
+                .min_by_key(|(n, s)| squared_distance(average(*n, *s), c))
+                .expect("at least 1 leaf");
+            nearest.0 += count;
+            for (a, b) in nearest.1.iter_mut().zip(sum) {
+                *a += b;
+            }
+        }
+        leaves.sort_by_key(|(count, _)| Reverse(*count));
+        leaves
+            .iter()
+            .map(|(count, sum)| average(*count, *sum))
+            .collect()
+    }
+
+    /* private helpers */
+
+    // Returns the index of a new node at `depth`, reusing a freed one if possible.
+    fn new_node(&mut self, depth: usize) -> usize {
+        let node = Node {
+            leaf: depth == MAX_DEPTH,
+            ..Node::default()
+        };
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx as usize] = node;
+                idx as usize
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        if depth == MAX_DEPTH {
+            self.leaves += 1;
+        } else {
+            self.levels[depth].push(idx as u32);
+        }
+        idx
+    }
+
+    // Merges the children of the least populated node of the deepest level,
+    // which are all leaves, into it.
+    fn reduce(&mut self) {
+        let Self {
+            nodes,
+            levels,
+            free,
+            leaves,
+            ..
+        } = self;
+        let Some(level) = levels.iter_mut().rev().find(|l| !l.is_empty()) else {
+            return;
+        };
+        let (pos, _) = level
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, idx)| nodes[**idx as usize].count)
+            .expect("non-empty level");
+        let node = &mut nodes[level.swap_remove(pos) as usize];
+        node.leaf = true;
+        for child in mem::take(&mut node.children)
+            .into_iter()
+            .filter(|c| *c != 0)
+        {
+            free.push(child);
+            *leaves -= 1;
+        }
+        *leaves += 1;
+    }
+}
+
+impl Extend<Srgb8> for OctreeQuantizer {
+    fn extend<I: IntoIterator<Item = Srgb8>>(&mut self, iter: I) {
+        for c in iter {
+            self.insert(c);
+        }
+    }
+}
+
+// Returns the average color of `count` colors adding up to `sum`, rounded.
+#[inline]
+fn average(count: u64, sum: [u64; 3]) -> Srgb8 {
+    let [r, g, b] = sum.map(|sum| ((sum + count / 2) / count) as u8);
+    Srgb8::new(r, g, b)
+}
+
+// Returns the squared euclidean distance between two colors.
+#[inline]
+fn squared_distance(a: Srgb8, b: Srgb8) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(a.r, b.r) + d(a.g, b.g) + d(a.b, b.b)
+}
+
+// Returns the child branch of the components at `depth`.
+#[inline]
+fn branch(rgb: [u8; 3], depth: usize) -> usize {
+    let shift = MAX_DEPTH - 1 - depth;
+    let bit = |c: u8| ((c >> shift) & 1) as usize;
+    bit(rgb[0]) << 2 | bit(rgb[1]) << 1 | bit(rgb[2])
+}
+
+/* k-means */
+
+/// A dominant color of an image, returned by [`kmeans_oklab`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub struct DominantColor {
+    /// The average color of the cluster.
+    pub color: Srgb8,
+    /// The fraction of the pixels in the cluster, in `0..=1`.
+    pub weight: f32,
+}
+
+/// Returns up to `k` dominant colors of the `pixels`, ordered by decreasing weight.
+///
+/// Clusters the pixels by their Oklab positions using k-means, with the initial
+/// centers chosen by k-means++, until the assignments converge or after
+/// `max_iterations`. The seeding is deterministic, so the same input gives the same output.
+///
+/// Fewer colors are returned if there are fewer than `k` distinct pixels.
+///
+/// # Example
+/// ```
+/// use acolor::{quantize::kmeans_oklab, srgb::Srgb8};
+///
+/// let mut pixels = [Srgb8::new(200, 30, 30); 30];
+/// pixels[..10].fill(Srgb8::new(20, 20, 220));
+/// let colors = kmeans_oklab(&pixels, 2, 16);
+/// assert_eq![colors[0].color, Srgb8::new(200, 30, 30)];
+/// assert_eq![colors[1].weight, 1. / 3.];
+/// ```
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn kmeans_oklab(pixels: &[Srgb8], k: usize, max_iterations: usize) -> Vec<DominantColor> {
+    if pixels.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    let points: Vec<[f32; 3]> = pixels
+        .iter()
+        .map(|c| Oklab32::to_array(c.to_oklab32()))
+        .collect();
+
+    // k-means++ seeding
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let mut centers = Vec::from([points[rng.next_below(points.len())]]);
+    let mut dist: Vec<f32> = points.iter().map(|p| distance2(*p, centers[0])).collect();
+    while centers.len() < k {
+        let total: f32 = dist.iter().sum();
+        if total <= 0. {
+            break;
+        }
+        let mut target = rng.next_f32() * total;
+        let mut chosen = points.len() - 1;
+        for (i, d) in dist.iter().enumerate() {
+            if target < *d {
+                chosen = i;
+                break;
+            }
+            target -= d;
+        }
+        let center = points[chosen];
+        for (d, p) in dist.iter_mut().zip(&points) {
+            *d = d.min(distance2(*p, center));
+        }
+        centers.push(center);
+    }
+
+    // Lloyd iterations
+    let mut assignments = Vec::from_iter(points.iter().map(|p| nearest(&centers, *p)));
+    for _ in 0..max_iterations {
+        let mut sums = Vec::from_iter(centers.iter().map(|_| ([0.; 3], 0_usize)));
+        for (p, a) in points.iter().zip(&assignments) {
+            let (sum, count) = &mut sums[*a];
+            for (s, c) in sum.iter_mut().zip(p) {
+                *s += c;
+            }
+            *count += 1;
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(&sums) {
+            if *count > 0 {
+                *center = sum.map(|s: f32| s / *count as f32);
+            }
+        }
+        let mut changed = false;
+        for (p, a) in points.iter().zip(assignments.iter_mut()) {
+            let n = nearest(&centers, *p);
+            changed |= n != *a;
+            *a = n;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut counts = Vec::from_iter(centers.iter().map(|_| 0_usize));
+    for a in &assignments {
+        counts[*a] += 1;
+    }
+    let mut colors: Vec<DominantColor> = centers
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(center, count)| DominantColor {
+            color: Oklab32::from_array(*center).to_srgb8(),
+            weight: count as f32 / points.len() as f32,
+        })
+        .collect();
+    colors.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    colors
+}
+
+// Returns the squared euclidean distance between two points.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn distance2(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    d[0] * d[0] + d[1] * d[1] + d[2] * d[2]
+}
+
+// Returns the index of the nearest center to the point `p`.
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn nearest(centers: &[[f32; 3]], p: [f32; 3]) -> usize {
+    let mut best = (0, f32::INFINITY);
+    for (i, c) in centers.iter().enumerate() {
+        let d = distance2(*c, p);
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best.0
+}
+
+// A small deterministic pseudo-random number generator.
+#[cfg(any(feature = "std", feature = "no_std"))]
+struct XorShift(u64);
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+    // Returns a number in `0..1`.
+    fn next_f32(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1_u64 << 24) as f32
+    }
+    // Returns a number in `0..n`.
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
@@ -17,6 +17,7 @@
 //   - Srgba32
 //   - LinearSrgb32
 //   - LinearSrgba32
+//   - AlphaPolicy
 // - conversions:
 //   - Srgb8
 //   - Srgba8
@@ -24,190 +25,325 @@
 //   - Srgba32
 //   - LinearSrgb32
 //   - LinearSrgba32
+// - conversions with a transfer function
+// - channel order
 // - utils
 //   - linearize32
+//   - linearize8
 //   - nonlinearize32
+//   - nonlinearize32_fast
+//   - [non]linearize[8]_slice
 //
 
+use crate::math::{f32_to_u8, u8_to_f32};
 #[cfg(any(feature = "std", feature = "no_std"))]
 use crate::{
+    math::{float_to_f32, powf_float, sqrt, Float},
     oklab::{Oklab32, Oklch32},
+    transfer::TransferFunction,
     GAMMA_32,
 };
-use iunorm::Unorm8;
 
 // DEFINITIONS
 // -----------------------------------------------------------------------------
 
-/// Non-linear sRGB color representation using `3` × [`u8`] components.
+/// Non-linear sRGB color representation using `3` × `T` components.
 ///
-/// Better suited for saving to the final graphics buffer.
+/// See the [`Srgb8`], [`Srgb32`] and [`Srgb64`] aliases.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Srgb8 {
+#[repr(C)]
+pub struct Srgb<T> {
     /// Gamma encoded red luminosity.
-    pub r: u8,
+    pub r: T,
     /// Gamma encoded green luminosity.
-    pub g: u8,
+    pub g: T,
     /// Gamma encoded blue luminosity.
-    pub b: u8,
-}
-/// # Constructors
-impl Srgb8 {
-    /// New Srgb8.
-    pub const fn new(r: u8, g: u8, b: u8) -> Srgb8 {
-        Self { r, g, b }
-    }
+    pub b: T,
 }
 
-/// Non-linear sRGB+A color representation using `4` × [`u8`] components.
+/// Non-linear sRGB+A color representation using `4` × `T` components.
 ///
-/// Better suited for saving to the final graphics buffer.
+/// See the [`Srgba8`], [`Srgba32`] and [`Srgba64`] aliases.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Srgba8 {
+#[repr(C)]
+pub struct Srgba<T> {
     /// Gamma encoded red luminosity.
-    pub r: u8,
+    pub r: T,
     /// Gamma encoded green luminosity.
-    pub g: u8,
+    pub g: T,
     /// Gamma encoded blue luminosity.
-    pub b: u8,
+    pub b: T,
     /// Linear alpha channel.
-    pub a: u8,
+    pub a: T,
 }
-/// # Constructors
-impl Srgba8 {
-    /// New Srgba8.
-    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Srgba8 {
-        Self { r, g, b, a }
-    }
+
+/// Linear sRGB color representation using `3` × `T` components.
+///
+/// See the [`LinearSrgb32`] and [`LinearSrgb64`] aliases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct LinearSrgb<T> {
+    /// Linear red luminosity.
+    pub r: T,
+    /// Linear green luminosity.
+    pub g: T,
+    /// Linear blue luminosity.
+    pub b: T,
+}
+
+/// Linear sRGB+A color representation using `4` × `T` components.
+///
+/// See the [`LinearSrgba32`] and [`LinearSrgba64`] aliases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct LinearSrgba<T> {
+    /// Linear red luminosity.
+    pub r: T,
+    /// Linear green luminosity.
+    pub g: T,
+    /// Linear blue luminosity.
+    pub b: T,
+    /// Linear alpha channel.
+    pub a: T,
 }
 
+/// Non-linear sRGB color representation using `3` × [`u8`] components.
+///
+/// Better suited for saving to the final graphics buffer.
+pub type Srgb8 = Srgb<u8>;
+
+/// Non-linear sRGB+A color representation using `4` × [`u8`] components.
+///
+/// Better suited for saving to the final graphics buffer.
+pub type Srgba8 = Srgba<u8>;
+
 /// Non-linear sRGB color representation using `3` × [`f32`] components.
 ///
 /// Values are normalized between `[0.0 .. 1.0]`
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Srgb32 {
-    /// Gamma encoded red luminosity.
-    pub r: f32,
-    /// Gamma encoded green luminosity.
-    pub g: f32,
-    /// Gamma encoded blue luminosity.
-    pub b: f32,
-}
-/// # Constructors
-impl Srgb32 {
-    /// New Srgb32.
-    pub const fn new(r: f32, g: f32, b: f32) -> Srgb32 {
-        Self { r, g, b }
-    }
-}
+pub type Srgb32 = Srgb<f32>;
 
 /// Non-linear sRGB+A color representation using `4` × [`f32`] components.
 ///
 /// Values are normalized between `[0.0 .. 1.0]`
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct Srgba32 {
-    /// Gamma encoded red luminosity.
-    pub r: f32,
-    /// Gamma encoded green luminosity.
-    pub g: f32,
-    /// Gamma encoded blue luminosity.
-    pub b: f32,
-    /// Linear alpha channel.
-    pub a: f32,
-}
-/// # Constructors
-impl Srgba32 {
-    /// New Srgba32.
-    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Srgba32 {
-        Self { r, g, b, a }
-    }
-}
+pub type Srgba32 = Srgba<f32>;
+
+/// Non-linear sRGB color representation using `3` × [`f64`] components.
+///
+/// Values are normalized between `[0.0 .. 1.0]`
+pub type Srgb64 = Srgb<f64>;
+
+/// Non-linear sRGB+A color representation using `4` × [`f64`] components.
+///
+/// Values are normalized between `[0.0 .. 1.0]`
+pub type Srgba64 = Srgba<f64>;
 
 /// Linear sRGB color representation using `3` × [`f32`] components.
 ///
 /// Values are normalized between `[0.0 .. 1.0]`
 ///
 /// Better suited for physical calculations.
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct LinearSrgb32 {
-    /// Linear red luminosity.
-    pub r: f32,
-    /// Linear green luminosity.
-    pub g: f32,
-    /// Linear blue luminosity.
-    pub b: f32,
-}
-/// # Constructors
-impl LinearSrgb32 {
-    /// New LinearSrgb32.
-    pub const fn new(r: f32, g: f32, b: f32) -> LinearSrgb32 {
-        Self { r, g, b }
-    }
-}
+pub type LinearSrgb32 = LinearSrgb<f32>;
 
 /// Linear sRGB+A color representation using `4` × [`f32`] components.
 ///
 /// Values are normalized between `[0.0 .. 1.0]`
 ///
 /// Better suited for physical calculations.
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
-pub struct LinearSrgba32 {
-    /// Linear red luminosity.
-    pub r: f32,
-    /// Linear green luminosity.
-    pub g: f32,
-    /// Linear blue luminosity.
-    pub b: f32,
-    /// Linear alpha channel.
-    pub a: f32,
+pub type LinearSrgba32 = LinearSrgba<f32>;
+
+/// Linear sRGB color representation using `3` × [`f64`] components.
+///
+/// Values are normalized between `[0.0 .. 1.0]`
+///
+/// Better suited for physical calculations.
+pub type LinearSrgb64 = LinearSrgb<f64>;
+
+/// Linear sRGB+A color representation using `4` × [`f64`] components.
+///
+/// Values are normalized between `[0.0 .. 1.0]`
+///
+/// Better suited for physical calculations.
+pub type LinearSrgba64 = LinearSrgba<f64>;
+
+/// # Constructors
+impl<T> Srgb<T> {
+    /// New sRGB color.
+    pub const fn new(r: T, g: T, b: T) -> Srgb<T> {
+        Self { r, g, b }
+    }
 }
 /// # Constructors
-impl LinearSrgba32 {
-    /// New LinearSrgba32.
-    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> LinearSrgba32 {
+impl<T> Srgba<T> {
+    /// New sRGB+A color.
+    pub const fn new(r: T, g: T, b: T, a: T) -> Srgba<T> {
+        Self { r, g, b, a }
+    }
+}
+/// # Constructors
+impl<T> LinearSrgb<T> {
+    /// New linear sRGB color.
+    pub const fn new(r: T, g: T, b: T) -> LinearSrgb<T> {
+        Self { r, g, b }
+    }
+}
+/// # Constructors
+impl<T> LinearSrgba<T> {
+    /// New linear sRGB+A color.
+    pub const fn new(r: T, g: T, b: T, a: T) -> LinearSrgba<T> {
         Self { r, g, b, a }
     }
 }
 
-// CONVERSIONS
-// -----------------------------------------------------------------------------
-
-/* conversions: Srgb8 */
+// Implements the array and tuple conversions for 3 and 4 component types.
+macro_rules! impl_array_tuple {
+    (3: $($type:ident),+) => { $(
+        /// # Array and tuple conversions
+        impl<T: Copy> $type<T> {
+            /// Direct conversion from an array.
+            #[inline]
+            pub const fn from_array(c: [T; 3]) -> $type<T> {
+                $type { r: c[0], g: c[1], b: c[2] }
+            }
+            /// Direct conversion to an array.
+            #[inline]
+            pub const fn to_array(c: $type<T>) -> [T; 3] {
+                [c.r, c.g, c.b]
+            }
+            /// Direct conversion from a tuple.
+            #[inline]
+            pub const fn from_tuple(c: (T, T, T)) -> $type<T> {
+                $type { r: c.0, g: c.1, b: c.2 }
+            }
+            /// Direct conversion to a tuple.
+            #[inline]
+            pub const fn to_tuple(c: $type<T>) -> (T, T, T) {
+                (c.r, c.g, c.b)
+            }
+        }
+    )+ };
+    (4: $($type:ident),+) => { $(
+        /// # Array and tuple conversions
+        impl<T: Copy> $type<T> {
+            /// Direct conversion from an array.
+            #[inline]
+            pub const fn from_array(c: [T; 4]) -> $type<T> {
+                $type { r: c[0], g: c[1], b: c[2], a: c[3] }
+            }
+            /// Direct conversion to an array.
+            #[inline]
+            pub const fn to_array(c: $type<T>) -> [T; 4] {
+                [c.r, c.g, c.b, c.a]
+            }
+            /// Direct conversion from a tuple.
+            #[inline]
+            pub const fn from_tuple(c: (T, T, T, T)) -> $type<T> {
+                $type { r: c.0, g: c.1, b: c.2, a: c.3 }
+            }
+            /// Direct conversion to a tuple.
+            #[inline]
+            pub const fn to_tuple(c: $type<T>) -> (T, T, T, T) {
+                (c.r, c.g, c.b, c.a)
+            }
+        }
+    )+ };
+}
+impl_array_tuple![3: Srgb, LinearSrgb];
+impl_array_tuple![4: Srgba, LinearSrgba];
+
+// Implements lossless `From` conversions from `f32` into `f64` components.
+macro_rules! impl_from_f32_to_f64 {
+    ($($type:ident: $($c:ident),+);+ $(;)?) => { $(
+        impl From<$type<f32>> for $type<f64> {
+            #[inline]
+            fn from(c: $type<f32>) -> $type<f64> {
+                $type { $( $c: c.$c as f64 ),+ }
+            }
+        }
+    )+ };
+}
+impl_from_f32_to_f64![Srgb: r, g, b; Srgba: r, g, b, a; LinearSrgb: r, g, b; LinearSrgba: r, g, b, a];
+pub(crate) use impl_from_f32_to_f64;
 
-/// # Direct conversions
-impl Srgb8 {
-    // [] ()
+/// How to fill in the alpha channel when converting from a color without one.
+///
+/// The `From` impls that add alpha always follow [`AlphaPolicy::Opaque`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AlphaPolicy {
+    /// Adds alpha at max opacity.
+    #[default]
+    Opaque,
+    /// Adds alpha at zero opacity.
+    Transparent,
+}
 
-    /// Direct conversion from an array.
+impl AlphaPolicy {
+    /// Returns the alpha value as a [`u8`].
     #[inline]
-    pub const fn from_array(c: [u8; 3]) -> Srgb8 {
-        Srgb8 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
+    pub const fn alpha8(self) -> u8 {
+        match self {
+            AlphaPolicy::Opaque => u8::MAX,
+            AlphaPolicy::Transparent => 0,
         }
     }
-    /// Direct conversion to an array.
-    #[inline]
-    pub const fn to_array(c: Srgb8) -> [u8; 3] {
-        [c.r, c.g, c.b]
-    }
-
-    /// Direct conversion from a tuple.
+    /// Returns the alpha value as an [`f32`].
     #[inline]
-    pub const fn from_tuple(c: (u8, u8, u8)) -> Srgb8 {
-        Srgb8 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
+    pub const fn alpha32(self) -> f32 {
+        match self {
+            AlphaPolicy::Opaque => 1.,
+            AlphaPolicy::Transparent => 0.,
         }
     }
-    /// Direct conversion to a tuple.
+    /// Returns the alpha value as an [`f64`].
     #[inline]
-    pub const fn to_tuple(c: Srgb8) -> (u8, u8, u8) {
-        (c.r, c.g, c.b)
+    pub const fn alpha64(self) -> f64 {
+        match self {
+            AlphaPolicy::Opaque => 1.,
+            AlphaPolicy::Transparent => 0.,
+        }
     }
+}
+
+// Implements the constructors and conversions that add alpha following a policy.
+macro_rules! impl_alpha_policy {
+    ($($from:ident => $type:ident, $t:ty, $alpha:ident);+ $(;)?) => { $(
+        /// # Alpha policy
+        impl $type {
+            /// New color at max opacity.
+            #[inline]
+            pub const fn opaque(r: $t, g: $t, b: $t) -> $type {
+                $type::new(r, g, b, AlphaPolicy::Opaque.$alpha())
+            }
+            /// New color at zero opacity.
+            #[inline]
+            pub const fn transparent(r: $t, g: $t, b: $t) -> $type {
+                $type::new(r, g, b, AlphaPolicy::Transparent.$alpha())
+            }
+        }
+        /// # Alpha policy
+        impl $from {
+            #[doc = concat!("Converts to [`", stringify!($type), "`], adding alpha following the `policy`.")]
+            #[inline]
+            pub const fn add_alpha(self, policy: AlphaPolicy) -> $type {
+                $type::new(self.r, self.g, self.b, policy.$alpha())
+            }
+        }
+    )+ };
+}
+impl_alpha_policy![
+    Srgb8 => Srgba8, u8, alpha8;
+    Srgb32 => Srgba32, f32, alpha32;
+    Srgb64 => Srgba64, f64, alpha64;
+    LinearSrgb32 => LinearSrgba32, f32, alpha32;
+    LinearSrgb64 => LinearSrgba64, f64, alpha64;
+];
+
+// CONVERSIONS
+// -----------------------------------------------------------------------------
+
+/* conversions: Srgb8 */
 
+/// # Direct conversions
+impl Srgb8 {
     // Srgba8
 
     /// Direct conversion from [`Srgba8`].
@@ -238,21 +374,21 @@ impl Srgb8 {
 
     /// Direct conversion from [`Srgb32`].
     #[inline]
-    pub fn from_srgb32(c: Srgb32) -> Srgb8 {
+    pub const fn from_srgb32(c: Srgb32) -> Srgb8 {
         Srgb8 {
-            r: Unorm8::from_f32(c.r).0,
-            g: Unorm8::from_f32(c.g).0,
-            b: Unorm8::from_f32(c.b).0,
+            r: f32_to_u8(c.r),
+            g: f32_to_u8(c.g),
+            b: f32_to_u8(c.b),
         }
     }
 
     /// Direct conversion to [`Srgb32`].
     #[inline]
-    pub fn to_srgb32(&self) -> Srgb32 {
+    pub const fn to_srgb32(&self) -> Srgb32 {
         Srgb32 {
-            r: Unorm8(self.r).to_f32(),
-            g: Unorm8(self.g).to_f32(),
-            b: Unorm8(self.b).to_f32(),
+            r: u8_to_f32(self.r),
+            g: u8_to_f32(self.g),
+            b: u8_to_f32(self.b),
         }
     }
 
@@ -260,11 +396,11 @@ impl Srgb8 {
 
     /// Direct conversion from [`Srgba32`].
     #[inline]
-    pub fn from_srgba32(c: Srgba32) -> Srgb8 {
+    pub const fn from_srgba32(c: Srgba32) -> Srgb8 {
         Srgb8 {
-            r: Unorm8::from_f32(c.r).0,
-            g: Unorm8::from_f32(c.g).0,
-            b: Unorm8::from_f32(c.b).0,
+            r: f32_to_u8(c.r),
+            g: f32_to_u8(c.g),
+            b: f32_to_u8(c.b),
         }
     }
 
@@ -272,11 +408,11 @@ impl Srgb8 {
     ///
     /// Adds the `alpha` channel.
     #[inline]
-    pub fn to_srgba32(&self, alpha: f32) -> Srgba32 {
+    pub const fn to_srgba32(&self, alpha: f32) -> Srgba32 {
         Srgba32 {
-            r: Unorm8(self.r).to_f32(),
-            g: Unorm8(self.g).to_f32(),
-            b: Unorm8(self.b).to_f32(),
+            r: u8_to_f32(self.r),
+            g: u8_to_f32(self.g),
+            b: u8_to_f32(self.b),
             a: alpha,
         }
     }
@@ -294,13 +430,15 @@ impl Srgb8 {
     /// Indirect conversion from [`LinearSrgb32`].
     #[inline]
     pub fn from_linear_srgb32(c: LinearSrgb32) -> Srgb8 {
-        c.to_srgb32().to_srgb8()
+        Srgb8::from_linear_srgb32_with(c, TransferFunction::SrgbPiecewise)
     }
 
     /// Indirect conversion to [`LinearSrgb32`].
+    ///
+    /// Uses a lookup table with the `fast_lut` feature.
     #[inline]
     pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
-        self.to_srgb32().to_linear_srgb32()
+        self.to_linear_srgb32_with(TransferFunction::SrgbPiecewise)
     }
 
     // LinearSrgba32
@@ -318,7 +456,7 @@ impl Srgb8 {
     /// Adds the `alpha` channel.
     #[inline]
     pub fn to_linear_srgba32(&self, alpha: f32) -> LinearSrgba32 {
-        self.to_srgb32().to_linear_srgba32(alpha)
+        self.to_linear_srgb32().to_linear_srgba32(alpha)
     }
 
     // Oklab32
@@ -332,7 +470,7 @@ impl Srgb8 {
     /// Indirect conversion to [`Oklab32`].
     #[inline]
     pub fn to_oklab32(&self) -> Oklab32 {
-        self.to_srgb32().to_linear_srgb32().to_oklab32()
+        self.to_linear_srgb32().to_oklab32()
     }
 
     // Oklch32
@@ -354,47 +492,13 @@ impl Srgb8 {
 
 /// # Direct conversions
 impl Srgba8 {
-    // [] ()
-
-    /// Direct conversion from an array.
-    #[inline]
-    pub fn from_array(c: [u8; 4]) -> Srgba8 {
-        Srgba8 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
-            a: c[3],
-        }
-    }
-    /// Direct conversion to an array.
-    #[inline]
-    pub fn to_array(c: Srgba8) -> [u8; 4] {
-        [c.r, c.g, c.b, c.a]
-    }
-
-    /// Direct conversion from a tuple.
-    #[inline]
-    pub fn from_tuple(c: (u8, u8, u8, u8)) -> Srgba8 {
-        Srgba8 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
-            a: c.3,
-        }
-    }
-    /// Direct conversion to a tuple.
-    #[inline]
-    pub fn to_tuple(c: Srgba8) -> (u8, u8, u8, u8) {
-        (c.r, c.g, c.b, c.a)
-    }
-
     // Srgb8
 
     /// Direct conversion from [`Srgb8`].
     ///
     /// Adds the `alpha` channel.
     #[inline]
-    pub fn from_srgb8(c: Srgb8, alpha: u8) -> Srgba8 {
+    pub const fn from_srgb8(c: Srgb8, alpha: u8) -> Srgba8 {
         Srgba8 {
             r: c.r,
             g: c.g,
@@ -406,7 +510,7 @@ impl Srgba8 {
     ///
     /// Loses the alpha channel.
     #[inline]
-    pub fn to_srgb8(&self) -> Srgb8 {
+    pub const fn to_srgb8(&self) -> Srgb8 {
         Srgb8 {
             r: self.r,
             g: self.g,
@@ -420,11 +524,11 @@ impl Srgba8 {
     ///
     /// Adds the `alpha` channel.
     #[inline]
-    pub fn from_srgb32(c: Srgb32, alpha: u8) -> Srgba8 {
+    pub const fn from_srgb32(c: Srgb32, alpha: u8) -> Srgba8 {
         Srgba8 {
-            r: Unorm8::from_f32(c.r).0,
-            g: Unorm8::from_f32(c.g).0,
-            b: Unorm8::from_f32(c.b).0,
+            r: f32_to_u8(c.r),
+            g: f32_to_u8(c.g),
+            b: f32_to_u8(c.b),
             a: alpha,
         }
     }
@@ -433,11 +537,11 @@ impl Srgba8 {
     ///
     /// Loses the alpha channel.
     #[inline]
-    pub fn to_srgb32(&self) -> Srgb32 {
+    pub const fn to_srgb32(&self) -> Srgb32 {
         Srgb32 {
-            r: Unorm8(self.r).to_f32(),
-            g: Unorm8(self.g).to_f32(),
-            b: Unorm8(self.b).to_f32(),
+            r: u8_to_f32(self.r),
+            g: u8_to_f32(self.g),
+            b: u8_to_f32(self.b),
         }
     }
 
@@ -445,23 +549,23 @@ impl Srgba8 {
 
     /// Direct conversion from [`Srgba32`].
     #[inline]
-    pub fn from_srgba32(c: Srgba32) -> Srgba8 {
+    pub const fn from_srgba32(c: Srgba32) -> Srgba8 {
         Srgba8 {
-            r: Unorm8::from_f32(c.r).0,
-            g: Unorm8::from_f32(c.g).0,
-            b: Unorm8::from_f32(c.b).0,
-            a: Unorm8::from_f32(c.a).0,
+            r: f32_to_u8(c.r),
+            g: f32_to_u8(c.g),
+            b: f32_to_u8(c.b),
+            a: f32_to_u8(c.a),
         }
     }
 
     /// Direct conversion to [`Srgba32`].
     #[inline]
-    pub fn to_srgba32(&self) -> Srgba32 {
+    pub const fn to_srgba32(&self) -> Srgba32 {
         Srgba32 {
-            r: Unorm8(self.r).to_f32(),
-            g: Unorm8(self.g).to_f32(),
-            b: Unorm8(self.b).to_f32(),
-            a: Unorm8(self.a).to_f32(),
+            r: u8_to_f32(self.r),
+            g: u8_to_f32(self.g),
+            b: u8_to_f32(self.b),
+            a: u8_to_f32(self.a),
         }
     }
 }
@@ -488,7 +592,7 @@ impl Srgba8 {
     /// Loses the alpha channel.
     #[inline]
     pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
-        self.to_srgb32().to_linear_srgb32()
+        self.to_srgb8().to_linear_srgb32()
     }
 
     // LinearSrgba32
@@ -496,13 +600,13 @@ impl Srgba8 {
     /// Indirect conversion from [`LinearSrgba32`].
     #[inline]
     pub fn from_linear_srgba32(c: LinearSrgba32) -> Srgba8 {
-        c.to_srgba32().to_srgba8()
+        Srgba8::from_linear_srgba32_with(c, TransferFunction::SrgbPiecewise)
     }
 
     /// Indirect conversion to [`LinearSrgba32`].
     #[inline]
     pub fn to_linear_srgba32(&self) -> LinearSrgba32 {
-        self.to_srgba32().to_linear_srgba32()
+        self.to_linear_srgba32_with(TransferFunction::SrgbPiecewise)
     }
 
     // Oklab
@@ -520,7 +624,7 @@ impl Srgba8 {
     /// Loses the alpha channel.
     #[inline]
     pub fn to_oklab32(&self) -> Oklab32 {
-        self.to_srgb32().to_linear_srgb32().to_oklab32()
+        self.to_linear_srgb32().to_oklab32()
     }
 
     // Oklch32
@@ -549,48 +653,16 @@ impl Srgba8 {
 
 /// # Direct conversions
 impl Srgb32 {
-    // [] ()
-
-    /// Direct conversion from an array.
-    #[inline]
-    pub fn from_array(c: [f32; 3]) -> Srgb32 {
-        Srgb32 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
-        }
-    }
-    /// Direct conversion to an array.
-    #[inline]
-    pub fn to_array(c: Srgb32) -> [f32; 3] {
-        [c.r, c.g, c.b]
-    }
-
-    /// Direct conversion from a tuple.
-    #[inline]
-    pub fn from_tuple(c: (f32, f32, f32)) -> Srgb32 {
-        Srgb32 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
-        }
-    }
-    /// Direct conversion to a tuple.
-    #[inline]
-    pub fn to_tuple(c: Srgb32) -> (f32, f32, f32) {
-        (c.r, c.g, c.b)
-    }
-
     // Srgb8
 
     /// Direct conversion from [`Srgb8`].
     #[inline]
-    pub fn from_srgb8(c: Srgb8) -> Srgb32 {
+    pub const fn from_srgb8(c: Srgb8) -> Srgb32 {
         c.to_srgb32()
     }
     /// Direct conversion to [`Srgb8`].
     #[inline]
-    pub fn to_srgb8(&self) -> Srgb8 {
+    pub const fn to_srgb8(&self) -> Srgb8 {
         Srgb8::from_srgb32(*self)
     }
 
@@ -600,14 +672,14 @@ impl Srgb32 {
     ///
     /// Loses the alpha channel.
     #[inline]
-    pub fn from_srgba8(c: Srgba8) -> Srgb32 {
+    pub const fn from_srgba8(c: Srgba8) -> Srgb32 {
         c.to_srgb32()
     }
     /// Direct conversion to [`Srgba8`].
     ///
     /// Adds the `alpha` channel.
     #[inline]
-    pub fn to_srgba8(&self, alpha: u8) -> Srgba8 {
+    pub const fn to_srgba8(&self, alpha: u8) -> Srgba8 {
         Srgba8::from_srgb32(*self, alpha)
     }
 
@@ -640,20 +712,12 @@ impl Srgb32 {
     /// Direct conversion from [`LinearSrgb32`].
     #[inline]
     pub fn from_linear_srgb32(c: LinearSrgb32) -> Srgb32 {
-        Srgb32 {
-            r: nonlinearize32(c.r, GAMMA_32),
-            g: nonlinearize32(c.g, GAMMA_32),
-            b: nonlinearize32(c.b, GAMMA_32),
-        }
+        Srgb32::from_linear_srgb32_with(c, TransferFunction::SrgbPiecewise)
     }
     /// Direct conversion to [`LinearSrgb32`].
     #[inline]
     pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
-        LinearSrgb32 {
-            r: linearize32(self.r, GAMMA_32),
-            g: linearize32(self.g, GAMMA_32),
-            b: linearize32(self.b, GAMMA_32),
-        }
+        self.to_linear_srgb32_with(TransferFunction::SrgbPiecewise)
     }
 
     // LinearSrgba32
@@ -664,9 +728,9 @@ impl Srgb32 {
     #[inline]
     pub fn from_linear_srgba32(c: LinearSrgba32) -> Srgb32 {
         Srgb32 {
-            r: nonlinearize32(c.r, GAMMA_32),
-            g: nonlinearize32(c.g, GAMMA_32),
-            b: nonlinearize32(c.b, GAMMA_32),
+            r: encode32(c.r),
+            g: encode32(c.g),
+            b: encode32(c.b),
         }
     }
     /// Direct conversion to [`LinearSrgb32`].
@@ -725,40 +789,6 @@ impl Srgb32 {
 ///
 /// - From/Into [`Srgb8`], [`Srgba8`], [`Srgb32`], [`LinearSrgb32`], [`LinearSrgba32`].
 impl Srgba32 {
-    // [] ()
-
-    /// Direct conversion from an array.
-    #[inline]
-    pub const fn from_array(c: [f32; 4]) -> Srgba32 {
-        Srgba32 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
-            a: c[3],
-        }
-    }
-    /// Direct conversion to an array.
-    #[inline]
-    pub const fn to_array(c: Srgba32) -> [f32; 4] {
-        [c.r, c.g, c.b, c.a]
-    }
-
-    /// Direct conversion from a tuple.
-    #[inline]
-    pub const fn from_tuple(c: (f32, f32, f32, f32)) -> Srgba32 {
-        Srgba32 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
-            a: c.3,
-        }
-    }
-    /// Direct conversion to a tuple.
-    #[inline]
-    pub const fn to_tuple(c: Srgba32) -> (f32, f32, f32, f32) {
-        (c.r, c.g, c.b, c.a)
-    }
-
     // Srgb8
 
     /// Direct conversion from [`Srgb8`].
@@ -821,9 +851,9 @@ impl Srgba32 {
     #[inline]
     pub fn from_linear_srgb32(c: LinearSrgb32, alpha: f32) -> Srgba32 {
         Srgba32 {
-            r: nonlinearize32(c.r, GAMMA_32),
-            g: nonlinearize32(c.g, GAMMA_32),
-            b: nonlinearize32(c.b, GAMMA_32),
+            r: encode32(c.r),
+            g: encode32(c.g),
+            b: encode32(c.b),
             a: alpha,
         }
     }
@@ -832,11 +862,7 @@ impl Srgba32 {
     /// Loses the alpha channel.
     #[inline]
     pub fn to_linear_srgb32(&self) -> LinearSrgb32 {
-        LinearSrgb32 {
-            r: linearize32(self.r, GAMMA_32),
-            g: linearize32(self.g, GAMMA_32),
-            b: linearize32(self.b, GAMMA_32),
-        }
+        self.to_srgb32().to_linear_srgb32()
     }
 
     // LinearSrgba32
@@ -844,22 +870,12 @@ impl Srgba32 {
     /// Direct conversion from [`LinearSrgba32`].
     #[inline]
     pub fn from_linear_srgba32(c: LinearSrgba32) -> Srgba32 {
-        Srgba32 {
-            r: nonlinearize32(c.r, GAMMA_32),
-            g: nonlinearize32(c.g, GAMMA_32),
-            b: nonlinearize32(c.b, GAMMA_32),
-            a: c.a,
-        }
+        Srgba32::from_linear_srgba32_with(c, TransferFunction::SrgbPiecewise)
     }
     /// Direct conversion to [`LinearSrgb32`].
     #[inline]
     pub fn to_linear_srgba32(&self) -> LinearSrgba32 {
-        LinearSrgba32 {
-            r: linearize32(self.r, GAMMA_32),
-            g: linearize32(self.g, GAMMA_32),
-            b: linearize32(self.b, GAMMA_32),
-            a: self.a,
-        }
+        self.to_linear_srgba32_with(TransferFunction::SrgbPiecewise)
     }
 }
 
@@ -910,39 +926,7 @@ impl Srgba32 {
 /* conversions: LinearSrgb32 */
 
 /// # Direct conversions
-impl LinearSrgb32 {
-    // [] ()
-
-    /// Direct conversion from an array.
-    #[inline]
-    pub const fn from_array(c: [f32; 3]) -> LinearSrgb32 {
-        LinearSrgb32 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
-        }
-    }
-    /// Direct conversion to an array.
-    #[inline]
-    pub const fn to_array(c: LinearSrgb32) -> [f32; 3] {
-        [c.r, c.g, c.b]
-    }
-
-    /// Direct conversion from a tuple.
-    #[inline]
-    pub const fn from_tuple(c: (f32, f32, f32)) -> LinearSrgb32 {
-        LinearSrgb32 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
-        }
-    }
-    /// Direct conversion to a tuple.
-    #[inline]
-    pub const fn to_tuple(c: LinearSrgb32) -> (f32, f32, f32) {
-        (c.r, c.g, c.b)
-    }
-}
+impl LinearSrgb32 {}
 
 #[cfg(any(feature = "std", feature = "no_std"))]
 #[cfg_attr(
@@ -955,21 +939,13 @@ impl LinearSrgb32 {
     /// Direct conversion from [`Srgb32`].
     #[inline]
     pub fn from_srgb32(c: Srgb32) -> LinearSrgb32 {
-        LinearSrgb32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-        }
+        c.to_linear_srgb32()
     }
 
     /// Direct conversion to [`Srgb32`].
     #[inline]
     pub fn to_srgb32(&self) -> Srgb32 {
-        Srgb32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
-        }
+        Srgb32::from_linear_srgb32(*self)
     }
 
     // Srgba32
@@ -992,9 +968,9 @@ impl LinearSrgb32 {
     #[inline]
     pub fn to_srgba32(&self, alpha: f32) -> Srgba32 {
         Srgba32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
+            r: encode32(self.r),
+            g: encode32(self.g),
+            b: encode32(self.b),
             a: alpha,
         }
     }
@@ -1053,12 +1029,7 @@ impl LinearSrgb32 {
     /// Indirect conversion from [`Srgb8`].
     #[inline]
     pub fn from_srgb8(c: Srgb8) -> LinearSrgb32 {
-        let c = c.to_srgb32();
-        LinearSrgb32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-        }
+        c.to_linear_srgb32()
     }
 
     /// Indirect conversion to [`Srgb32`].
@@ -1074,12 +1045,7 @@ impl LinearSrgb32 {
     /// Loses the alpha channel
     #[inline]
     pub fn from_srgba8(c: Srgba8) -> LinearSrgb32 {
-        let c = c.to_srgba32();
-        LinearSrgb32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-        }
+        c.to_linear_srgb32()
     }
 
     /// Indirect conversion to [`Srgba8`].
@@ -1108,55 +1074,21 @@ impl LinearSrgb32 {
 /* conversions: LinearSrgba32 */
 
 /// # Direct conversions
+impl LinearSrgba32 {}
+
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
 impl LinearSrgba32 {
-    // [] ()
+    // Srgb32
 
-    /// Direct conversion from an array.
+    /// Direct conversion from [`Srgb32`].
+    ///
+    /// Adds the `alpha` channel.
     #[inline]
-    pub fn from_array(c: [f32; 4]) -> LinearSrgba32 {
-        LinearSrgba32 {
-            r: c[0],
-            g: c[1],
-            b: c[2],
-            a: c[3],
-        }
-    }
-    /// Direct conversion to an array.
-    #[inline]
-    pub fn to_array(c: LinearSrgba32) -> [f32; 4] {
-        [c.r, c.g, c.b, c.a]
-    }
-
-    /// Direct conversion from a tuple.
-    #[inline]
-    pub fn from_tuple(c: (f32, f32, f32, f32)) -> LinearSrgba32 {
-        LinearSrgba32 {
-            r: c.0,
-            g: c.1,
-            b: c.2,
-            a: c.3,
-        }
-    }
-    /// Direct conversion to a tuple.
-    #[inline]
-    pub fn to_tuple(c: LinearSrgba32) -> (f32, f32, f32, f32) {
-        (c.r, c.g, c.b, c.a)
-    }
-}
-
-#[cfg(any(feature = "std", feature = "no_std"))]
-#[cfg_attr(
-    feature = "nightly",
-    doc(cfg(any(feature = "std", feature = "no_std")))
-)]
-impl LinearSrgba32 {
-    // Srgb32
-
-    /// Direct conversion from [`Srgb32`].
-    ///
-    /// Adds the `alpha` channel.
-    #[inline]
-    pub fn from_srgb32(c: Srgb32, alpha: f32) -> LinearSrgba32 {
+    pub fn from_srgb32(c: Srgb32, alpha: f32) -> LinearSrgba32 {
         LinearSrgba32 {
             r: linearize32(c.r, GAMMA_32),
             g: linearize32(c.g, GAMMA_32),
@@ -1170,11 +1102,7 @@ impl LinearSrgba32 {
     /// Loses the alpha channel.
     #[inline]
     pub fn to_srgb32(&self) -> Srgb32 {
-        Srgb32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
-        }
+        Srgb32::from_linear_srgba32(*self)
     }
 
     // Srgba32
@@ -1182,23 +1110,13 @@ impl LinearSrgba32 {
     /// Direct conversion from [`Srgba32`].
     #[inline]
     pub fn from_srgba32(c: Srgba32) -> LinearSrgba32 {
-        LinearSrgba32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-            a: c.a,
-        }
+        c.to_linear_srgba32()
     }
 
     /// Direct conversion to [`Srgba32`].
     #[inline]
     pub fn to_srgba32(&self) -> Srgba32 {
-        Srgba32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
-            a: self.a,
-        }
+        Srgba32::from_linear_srgba32(*self)
     }
 
     // LinearSrgb32
@@ -1261,13 +1179,7 @@ impl LinearSrgba32 {
     /// Adds the `alpha` channel.
     #[inline]
     pub fn from_srgb8(c: Srgb8, alpha: f32) -> LinearSrgba32 {
-        let c = c.to_srgb32();
-        LinearSrgba32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-            a: alpha,
-        }
+        c.to_linear_srgba32(alpha)
     }
 
     /// Indirect conversion to [`Srgb32`].
@@ -1276,9 +1188,9 @@ impl LinearSrgba32 {
     #[inline]
     pub fn to_srgb8(&self) -> Srgb8 {
         Srgb32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
+            r: encode32(self.r),
+            g: encode32(self.g),
+            b: encode32(self.b),
         }
         .to_srgb8()
     }
@@ -1288,22 +1200,16 @@ impl LinearSrgba32 {
     /// Indirect conversion from [`Srgba8`].
     #[inline]
     pub fn from_srgba8(c: Srgba8) -> LinearSrgba32 {
-        let c = c.to_srgba32();
-        LinearSrgba32 {
-            r: linearize32(c.r, GAMMA_32),
-            g: linearize32(c.g, GAMMA_32),
-            b: linearize32(c.b, GAMMA_32),
-            a: c.a,
-        }
+        c.to_linear_srgba32()
     }
 
     /// Indirect conversion to [`Srgba8`].
     #[inline]
     pub fn to_srgba8(&self) -> Srgba8 {
         Srgba32 {
-            r: nonlinearize32(self.r, GAMMA_32),
-            g: nonlinearize32(self.g, GAMMA_32),
-            b: nonlinearize32(self.b, GAMMA_32),
+            r: encode32(self.r),
+            g: encode32(self.g),
+            b: encode32(self.b),
             a: self.a,
         }
         .to_srgba8()
@@ -1328,6 +1234,177 @@ impl LinearSrgba32 {
     }
 }
 
+// CHANNEL ORDER
+// -----------------------------------------------------------------------------
+
+/// # Channel order
+impl Srgb8 {
+    /// Returns a new color from its `0xRRGGBB` hexadecimal representation.
+    ///
+    /// The highest byte is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::srgb::Srgb8;
+    ///
+    /// const ORANGE: Srgb8 = Srgb8::from_hex(0xff8000);
+    /// assert_eq![ORANGE, Srgb8::new(255, 128, 0)];
+    /// assert_eq![ORANGE.to_hex(), 0xff8000];
+    /// ```
+    #[inline]
+    pub const fn from_hex(c: u32) -> Srgb8 {
+        let [_, r, g, b] = c.to_be_bytes();
+        Srgb8::new(r, g, b)
+    }
+    /// Returns the color as its `0xRRGGBB` hexadecimal representation.
+    #[inline]
+    pub const fn to_hex(&self) -> u32 {
+        u32::from_be_bytes([0, self.r, self.g, self.b])
+    }
+
+    /// Returns a new color from its components in blue, green, red order.
+    #[inline]
+    pub const fn from_bgr(c: [u8; 3]) -> Srgb8 {
+        Srgb8::new(c[2], c[1], c[0])
+    }
+    /// Returns the components in blue, green, red order.
+    #[inline]
+    pub const fn to_bgr(&self) -> [u8; 3] {
+        [self.b, self.g, self.r]
+    }
+    /// Returns the components in blue, green, red, alpha order, with max opacity.
+    #[inline]
+    pub const fn to_bgra_array(&self) -> [u8; 4] {
+        [self.b, self.g, self.r, u8::MAX]
+    }
+    /// Returns the color packed as `0xAARRGGBB`, with max opacity.
+    #[inline]
+    pub const fn to_argb_u32(&self) -> u32 {
+        u32::from_be_bytes([u8::MAX, self.r, self.g, self.b])
+    }
+
+    /// Returns the components in the `order` given by a pattern like `"bgra"`.
+    ///
+    /// See [`Srgba8::swizzle`]. The alpha is max opacity.
+    ///
+    /// # Panics
+    /// Panics if the length of `order` is not `N`, or if it has invalid characters.
+    #[inline]
+    pub const fn swizzle<const N: usize>(&self, order: &str) -> [u8; N] {
+        self.to_srgba8(u8::MAX).swizzle(order)
+    }
+}
+
+/// # Channel order
+impl Srgba8 {
+    /// Returns a new color from its components in blue, green, red, alpha order.
+    #[inline]
+    pub const fn from_bgra_array(c: [u8; 4]) -> Srgba8 {
+        Srgba8::new(c[2], c[1], c[0], c[3])
+    }
+    /// Returns the components in blue, green, red, alpha order.
+    #[inline]
+    pub const fn to_bgra_array(&self) -> [u8; 4] {
+        [self.b, self.g, self.r, self.a]
+    }
+    /// Returns the red, green and blue components in blue, green, red order.
+    ///
+    /// Loses the alpha channel.
+    #[inline]
+    pub const fn to_bgr(&self) -> [u8; 3] {
+        [self.b, self.g, self.r]
+    }
+
+    /// Returns a new color packed as `0xAARRGGBB`.
+    #[inline]
+    pub const fn from_argb_u32(c: u32) -> Srgba8 {
+        let [a, r, g, b] = c.to_be_bytes();
+        Srgba8::new(r, g, b, a)
+    }
+    /// Returns the color packed as `0xAARRGGBB`.
+    #[inline]
+    pub const fn to_argb_u32(&self) -> u32 {
+        u32::from_be_bytes([self.a, self.r, self.g, self.b])
+    }
+    /// Returns a new color from its `0xRRGGBBAA` hexadecimal representation.
+    ///
+    /// The same as [`from_rgba_u32`][Self::from_rgba_u32].
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::srgb::Srgba8;
+    ///
+    /// const GLASS: Srgba8 = Srgba8::from_hex(0x80c0ff40);
+    /// assert_eq![GLASS, Srgba8::new(128, 192, 255, 64)];
+    /// assert_eq![GLASS.to_hex(), 0x80c0ff40];
+    /// ```
+    #[inline]
+    pub const fn from_hex(c: u32) -> Srgba8 {
+        Srgba8::from_rgba_u32(c)
+    }
+    /// Returns the color as its `0xRRGGBBAA` hexadecimal representation.
+    ///
+    /// The same as [`to_rgba_u32`][Self::to_rgba_u32].
+    #[inline]
+    pub const fn to_hex(&self) -> u32 {
+        self.to_rgba_u32()
+    }
+    /// Returns a new color packed as `0xRRGGBBAA`.
+    #[inline]
+    pub const fn from_rgba_u32(c: u32) -> Srgba8 {
+        let [r, g, b, a] = c.to_be_bytes();
+        Srgba8::new(r, g, b, a)
+    }
+    /// Returns the color packed as `0xRRGGBBAA`.
+    #[inline]
+    pub const fn to_rgba_u32(&self) -> u32 {
+        u32::from_be_bytes([self.r, self.g, self.b, self.a])
+    }
+
+    /// Returns the components in the `order` given by a pattern like `"bgra"`.
+    ///
+    /// Each character of the pattern selects a channel, either `r`, `g`, `b` and `a`,
+    /// or a constant: `0` for the minimum, and `1` for the maximum value.
+    /// The pattern is case insensitive.
+    ///
+    /// # Panics
+    /// Panics if the length of `order` is not `N`, or if it has invalid characters.
+    /// In a const context this is a compile time error.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::srgb::Srgba8;
+    ///
+    /// let c = Srgba8::new(1, 2, 3, 4);
+    /// assert_eq![c.swizzle("bgra"), [3, 2, 1, 4]];
+    /// assert_eq![c.swizzle("ARGB"), [4, 1, 2, 3]];
+    /// assert_eq![c.swizzle("rgb1"), [1, 2, 3, 255]];
+    /// assert_eq![c.swizzle("gg"), [2, 2]];
+    /// ```
+    pub const fn swizzle<const N: usize>(&self, order: &str) -> [u8; N] {
+        let order = order.as_bytes();
+        assert![
+            order.len() == N,
+            "the length of the order must match the output"
+        ];
+        let mut out = [0; N];
+        let mut i = 0;
+        while i < N {
+            out[i] = match order[i] {
+                b'r' | b'R' => self.r,
+                b'g' | b'G' => self.g,
+                b'b' | b'B' => self.b,
+                b'a' | b'A' => self.a,
+                b'0' => 0,
+                b'1' => u8::MAX,
+                _ => panic!("invalid channel in the order"),
+            };
+            i += 1;
+        }
+        out
+    }
+}
+
 // From/Into impls
 // -----------------------------------------------------------------------------
 
@@ -1373,7 +1450,19 @@ mod impl_from {
             c.to_oklab32().to_oklch32()
         }
     }
-    // … LinearSrgb32, LinearSrgba32
+    impl From<Srgb8> for LinearSrgb32 {
+        #[inline]
+        fn from(c: Srgb8) -> LinearSrgb32 {
+            c.to_linear_srgb32()
+        }
+    }
+    impl From<Srgb8> for LinearSrgba32 {
+        /// Automatically adds alpha at max opacity.
+        #[inline]
+        fn from(c: Srgb8) -> LinearSrgba32 {
+            c.to_linear_srgba32(1.)
+        }
+    }
 
     /* From Srgba8 */
 
@@ -1407,7 +1496,18 @@ mod impl_from {
             c.to_oklab32().to_oklch32()
         }
     }
-    // … LinearSrgb32, LinearSrgba32
+    impl From<Srgba8> for LinearSrgb32 {
+        #[inline]
+        fn from(c: Srgba8) -> LinearSrgb32 {
+            c.to_linear_srgb32()
+        }
+    }
+    impl From<Srgba8> for LinearSrgba32 {
+        #[inline]
+        fn from(c: Srgba8) -> LinearSrgba32 {
+            c.to_linear_srgba32()
+        }
+    }
 
     /* From Srgb32 */
 
@@ -1551,9 +1651,322 @@ mod impl_from {
     }
 }
 
+/// # Conversions with a transfer function
+///
+/// These use the given [`TransferFunction`] instead of the default sRGB curve.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgb8 {
+    /// Conversion from [`LinearSrgb32`], encoding with the transfer function `tf`.
+    #[inline]
+    pub fn from_linear_srgb32_with(c: LinearSrgb32, tf: TransferFunction) -> Srgb8 {
+        Srgb8 {
+            r: tf.encode8(c.r),
+            g: tf.encode8(c.g),
+            b: tf.encode8(c.b),
+        }
+    }
+
+    /// Conversion to [`LinearSrgb32`], decoding with the transfer function `tf`.
+    #[inline]
+    pub fn to_linear_srgb32_with(&self, tf: TransferFunction) -> LinearSrgb32 {
+        LinearSrgb32 {
+            r: tf.decode8(self.r),
+            g: tf.decode8(self.g),
+            b: tf.decode8(self.b),
+        }
+    }
+}
+
+/// # Conversions with a transfer function
+///
+/// These use the given [`TransferFunction`] instead of the default sRGB curve.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgba8 {
+    /// Conversion from [`LinearSrgba32`], encoding with the transfer function `tf`.
+    ///
+    /// The alpha channel is not affected.
+    #[inline]
+    pub fn from_linear_srgba32_with(c: LinearSrgba32, tf: TransferFunction) -> Srgba8 {
+        Srgba8 {
+            r: tf.encode8(c.r),
+            g: tf.encode8(c.g),
+            b: tf.encode8(c.b),
+            a: f32_to_u8(c.a),
+        }
+    }
+
+    /// Conversion to [`LinearSrgba32`], decoding with the transfer function `tf`.
+    ///
+    /// The alpha channel is not affected.
+    #[inline]
+    pub fn to_linear_srgba32_with(&self, tf: TransferFunction) -> LinearSrgba32 {
+        LinearSrgba32 {
+            r: tf.decode8(self.r),
+            g: tf.decode8(self.g),
+            b: tf.decode8(self.b),
+            a: u8_to_f32(self.a),
+        }
+    }
+}
+
+/// # Conversions with a transfer function
+///
+/// These use the given [`TransferFunction`] instead of the default sRGB curve.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgb32 {
+    /// Conversion from [`LinearSrgb32`], encoding with the transfer function `tf`.
+    #[inline]
+    pub fn from_linear_srgb32_with(c: LinearSrgb32, tf: TransferFunction) -> Srgb32 {
+        Srgb32 {
+            r: tf.encode(c.r),
+            g: tf.encode(c.g),
+            b: tf.encode(c.b),
+        }
+    }
+
+    /// Conversion to [`LinearSrgb32`], decoding with the transfer function `tf`.
+    #[inline]
+    pub fn to_linear_srgb32_with(&self, tf: TransferFunction) -> LinearSrgb32 {
+        LinearSrgb32 {
+            r: tf.decode(self.r),
+            g: tf.decode(self.g),
+            b: tf.decode(self.b),
+        }
+    }
+}
+
+/// # Conversions with a transfer function
+///
+/// These use the given [`TransferFunction`] instead of the default sRGB curve.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgba32 {
+    /// Conversion from [`LinearSrgba32`], encoding with the transfer function `tf`.
+    ///
+    /// The alpha channel is not affected.
+    #[inline]
+    pub fn from_linear_srgba32_with(c: LinearSrgba32, tf: TransferFunction) -> Srgba32 {
+        Srgba32 {
+            r: tf.encode(c.r),
+            g: tf.encode(c.g),
+            b: tf.encode(c.b),
+            a: c.a,
+        }
+    }
+
+    /// Conversion to [`LinearSrgba32`], decoding with the transfer function `tf`.
+    ///
+    /// The alpha channel is not affected.
+    #[inline]
+    pub fn to_linear_srgba32_with(&self, tf: TransferFunction) -> LinearSrgba32 {
+        LinearSrgba32 {
+            r: tf.decode(self.r),
+            g: tf.decode(self.g),
+            b: tf.decode(self.b),
+            a: self.a,
+        }
+    }
+}
+
+/// # Exact conversions
+///
+/// These encode each channel into the [`u8`] whose decoded value is the nearest,
+/// guaranteeing that converting any `Srgb8` into linear and back returns the same color,
+/// at the cost of speed. See [`TransferFunction::encode8_exact`].
+///
+/// The conversions between `Srgb8` and `Srgb32` are already exact.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgb8 {
+    /// Exact conversion from [`LinearSrgb32`].
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::srgb::Srgb8;
+    ///
+    /// let c = Srgb8::new(1, 128, 254);
+    /// assert_eq![Srgb8::from_linear_srgb32_exact(c.to_linear_srgb32()), c];
+    /// ```
+    pub fn from_linear_srgb32_exact(c: LinearSrgb32) -> Srgb8 {
+        let tf = TransferFunction::SrgbPiecewise;
+        Srgb8 {
+            r: tf.encode8_exact(c.r),
+            g: tf.encode8_exact(c.g),
+            b: tf.encode8_exact(c.b),
+        }
+    }
+}
+
+/// # Exact conversions
+///
+/// These encode each channel into the [`u8`] whose decoded value is the nearest,
+/// guaranteeing that converting any `Srgba8` into linear and back returns the same color,
+/// at the cost of speed. See [`TransferFunction::encode8_exact`].
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl Srgba8 {
+    /// Exact conversion from [`LinearSrgba32`].
+    pub fn from_linear_srgba32_exact(c: LinearSrgba32) -> Srgba8 {
+        let tf = TransferFunction::SrgbPiecewise;
+        Srgba8 {
+            r: tf.encode8_exact(c.r),
+            g: tf.encode8_exact(c.g),
+            b: tf.encode8_exact(c.b),
+            a: f32_to_u8(c.a),
+        }
+    }
+}
+
+/// # Exact conversions
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl LinearSrgb32 {
+    /// Exact conversion to [`Srgb8`], see [`Srgb8::from_linear_srgb32_exact`].
+    #[inline]
+    pub fn to_srgb8_exact(&self) -> Srgb8 {
+        Srgb8::from_linear_srgb32_exact(*self)
+    }
+}
+
+/// # Exact conversions
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl LinearSrgba32 {
+    /// Exact conversion to [`Srgba8`], see [`Srgba8::from_linear_srgba32_exact`].
+    #[inline]
+    pub fn to_srgba8_exact(&self) -> Srgba8 {
+        Srgba8::from_linear_srgba32_exact(*self)
+    }
+}
+
+/// # Dithered conversions
+///
+/// These add triangular noise before quantizing to 8 bits, which trades the banding
+/// of smooth gradients for fine grain.
+///
+/// The `noise` closure must return uniformly distributed values in `0..1`,
+/// from a random number generator or a noise function. It's called twice per channel.
+impl Srgb32 {
+    /// Conversion to [`Srgb8`], with triangular dithering.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::srgb::Srgb32;
+    ///
+    /// let c = Srgb32::new(0.5, 0.5, 0.5);
+    /// assert_eq![c.to_srgb8_dithered(|| 0.5), c.to_srgb8()];
+    /// ```
+    pub fn to_srgb8_dithered(&self, mut noise: impl FnMut() -> f32) -> Srgb8 {
+        Srgb8 {
+            r: dither_u8(self.r, &mut noise),
+            g: dither_u8(self.g, &mut noise),
+            b: dither_u8(self.b, &mut noise),
+        }
+    }
+}
+
+/// # Dithered conversions
+///
+/// These add triangular noise before quantizing to 8 bits, which trades the banding
+/// of smooth gradients for fine grain.
+///
+/// The `noise` closure must return uniformly distributed values in `0..1`,
+/// from a random number generator or a noise function. It's called twice per channel.
+impl Srgba32 {
+    /// Conversion to [`Srgba8`], with triangular dithering of every channel.
+    pub fn to_srgba8_dithered(&self, mut noise: impl FnMut() -> f32) -> Srgba8 {
+        Srgba8 {
+            r: dither_u8(self.r, &mut noise),
+            g: dither_u8(self.g, &mut noise),
+            b: dither_u8(self.b, &mut noise),
+            a: dither_u8(self.a, &mut noise),
+        }
+    }
+}
+
+/// # Dithered conversions
+///
+/// These encode the color with the sRGB curve, and then add triangular noise before
+/// quantizing to 8 bits, which trades the banding of smooth gradients for fine grain.
+///
+/// The `noise` closure must return uniformly distributed values in `0..1`,
+/// from a random number generator or a noise function. It's called twice per channel.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl LinearSrgb32 {
+    /// Conversion to [`Srgb8`], with triangular dithering.
+    pub fn to_srgb8_dithered(&self, noise: impl FnMut() -> f32) -> Srgb8 {
+        self.to_srgb32().to_srgb8_dithered(noise)
+    }
+}
+
+/// # Dithered conversions
+///
+/// These encode the color with the sRGB curve, and then add triangular noise before
+/// quantizing to 8 bits, which trades the banding of smooth gradients for fine grain.
+///
+/// The `noise` closure must return uniformly distributed values in `0..1`,
+/// from a random number generator or a noise function. It's called twice per channel.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+impl LinearSrgba32 {
+    /// Conversion to [`Srgba8`], with triangular dithering of every channel.
+    pub fn to_srgba8_dithered(&self, noise: impl FnMut() -> f32) -> Srgba8 {
+        self.to_srgba32().to_srgba8_dithered(noise)
+    }
+}
+
 /* utils */
 
-/// Applies the `gamma` to an `f32` channel.
+// Quantizes an `f32` channel to the nearest of 255 steps, after adding triangular
+// noise of up to one step, made from two uniform `noise` samples.
+//
+// The noise is centered, so the expected result is `v * 255`, unbiased,
+// except where it saturates at `0` and `255`.
+#[inline]
+fn dither_u8(v: f32, noise: &mut impl FnMut() -> f32) -> u8 {
+    let n = noise() + noise() - 1.;
+    // the cast saturates, and maps `NaN` to `0`
+    (v * 255. + 0.5 + n) as u8
+}
+
+/// Decodes an `f32` channel using the sRGB piecewise curve with the given `gamma` exponent.
+///
+/// The standard exponent is [`GAMMA_32`]. For a pure power curve
+/// use [`TransferFunction::PureGamma`].
 #[inline]
 #[cfg(any(feature = "std", feature = "no_std"))]
 #[cfg_attr(
@@ -1561,34 +1974,191 @@ mod impl_from {
     doc(cfg(any(feature = "std", feature = "no_std")))
 )]
 pub fn linearize32(nonlinear: f32, gamma: f32) -> f32 {
-    if nonlinear >= 0.04045 {
-        #[cfg(feature = "std")]
-        return ((nonlinear + 0.055) / (1. + 0.055)).powf(gamma);
-        #[cfg(not(feature = "std"))]
-        return libm::powf((nonlinear + 0.055) / (1. + 0.055), gamma);
+    let (c, gamma) = (nonlinear as Float, gamma as Float);
+    if c >= SRGB_DECODE_THRESHOLD {
+        float_to_f32(powf_float((c + 0.055) / (1. + 0.055), gamma))
     } else {
-        nonlinear / 12.92
+        float_to_f32(c / 12.92)
     }
 }
 
-/// Removes the `gamma` from an `f32` channel.
+/// Applies the sRGB gamma to a [`u8`] channel, returning a linear `f32` channel.
+///
+/// With the `fast_lut` feature it uses a lookup table generated at compile time.
 #[inline]
 #[cfg(any(feature = "std", feature = "no_std"))]
 #[cfg_attr(
     feature = "nightly",
     doc(cfg(any(feature = "std", feature = "no_std")))
 )]
-pub fn nonlinearize32(linear: f32, gamma: f32) -> f32 {
+pub fn linearize8(nonlinear: u8) -> f32 {
+    #[cfg(feature = "fast_lut")]
+    return SRGB8_TO_LINEAR32_LUT[nonlinear as usize];
+    #[cfg(not(feature = "fast_lut"))]
+    return linearize32(u8_to_f32(nonlinear), GAMMA_32);
+}
+
+// The lookup table for decoding `u8` sRGB channels.
+#[cfg(feature = "fast_lut")]
+static SRGB8_TO_LINEAR32_LUT: [f32; 256] = {
+    let mut lut = [0.; 256];
+    let mut i = 0;
+    while i < 256 {
+        lut[i] = crate::math::srgb_decode_const(i as f64 / 255.) as f32;
+        i += 1;
+    }
+    lut
+};
+
+/// Removes the sRGB gamma from an `f32` channel, using a fast approximation.
+///
+/// Evaluates a polynomial over successive square roots instead of calling `powf`.
+/// The maximum absolute error compared to [`nonlinearize32`] with [`GAMMA_32`]
+/// is below `4e-5`, about a hundredth of an 8-bit step.
+///
+/// It's used by all the conversions with the `fast_encode` feature.
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn nonlinearize32_fast(linear: f32) -> f32 {
     if linear >= 0.0031308 {
-        #[cfg(feature = "std")]
-        return (1.055) * linear.powf(1.0 / gamma) - 0.055;
-        #[cfg(not(feature = "std"))]
-        return (1.055) * libm::powf(linear, 1.0 / gamma) - 0.055;
+        let s1 = sqrt(linear);
+        let (s2, s3) = (sqrt(s1), sqrt(sqrt(s1)));
+
+        0.6540074 * s1 + 0.6886720 * s2 - 0.3184432 * s3 - 0.0201940 * linear - 0.0040739
     } else {
         12.92 * linear
     }
 }
 
+// Removes the sRGB gamma from an `f32` channel,
+// using the fast approximation with the `fast_encode` feature.
+#[inline(always)]
+#[cfg(any(feature = "std", feature = "no_std"))]
+pub(crate) fn encode32(linear: f32) -> f32 {
+    #[cfg(feature = "fast_encode")]
+    return nonlinearize32_fast(linear);
+    #[cfg(not(feature = "fast_encode"))]
+    return nonlinearize32(linear, GAMMA_32);
+}
+
+/// Encodes an `f32` channel using the sRGB piecewise curve with the given `gamma` exponent.
+///
+/// The standard exponent is [`GAMMA_32`]. For a pure power curve
+/// use [`TransferFunction::PureGamma`].
+#[inline]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn nonlinearize32(linear: f32, gamma: f32) -> f32 {
+    let (c, gamma) = (linear as Float, gamma as Float);
+    if c >= SRGB_ENCODE_THRESHOLD {
+        float_to_f32((1.055) * powf_float(c, 1.0 / gamma) - 0.055)
+    } else {
+        float_to_f32(12.92 * c)
+    }
+}
+
+/// Decodes in place a buffer of encoded `f32` channels into linear ones,
+/// using the given `transfer` function.
+///
+/// # Example
+/// ```
+/// # #[cfg(not(feature = "micromath"))] {
+/// use acolor::{srgb::{linearize_slice, nonlinearize_slice}, transfer::TransferFunction};
+///
+/// let mut buf = [0., 0.5, 1.];
+/// linearize_slice(&mut buf, TransferFunction::SrgbPiecewise);
+/// assert![(buf[1] - 0.21404).abs() < 1e-5];
+/// nonlinearize_slice(&mut buf, TransferFunction::SrgbPiecewise);
+/// assert![(buf[1] - 0.5).abs() < 1e-4]; // within the `fast_encode` approximation
+/// # }
+/// ```
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linearize_slice(buf: &mut [f32], transfer: TransferFunction) {
+    for c in buf {
+        *c = transfer.decode(*c);
+    }
+}
+
+/// Encodes in place a buffer of linear `f32` channels, using the given `transfer` function.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn nonlinearize_slice(buf: &mut [f32], transfer: TransferFunction) {
+    for c in buf {
+        *c = transfer.encode(*c);
+    }
+}
+
+/// Decodes a buffer of encoded [`u8`] channels into linear `f32` ones,
+/// using the given `transfer` function.
+///
+/// The sRGB curve uses [`linearize8`], honoring the `fast_lut` feature.
+///
+/// # Panics
+/// Panics if both slices have different lengths.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn linearize8_slice(src: &[u8], dst: &mut [f32], transfer: TransferFunction) {
+    assert_eq![
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    ];
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = transfer.decode8(*s);
+    }
+}
+
+/// Encodes a buffer of linear `f32` channels into [`u8`] ones,
+/// using the given `transfer` function.
+///
+/// # Panics
+/// Panics if both slices have different lengths.
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg_attr(
+    feature = "nightly",
+    doc(cfg(any(feature = "std", feature = "no_std")))
+)]
+pub fn nonlinearize8_slice(src: &[f32], dst: &mut [u8], transfer: TransferFunction) {
+    assert_eq![
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    ];
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = transfer.encode8(*s);
+    }
+}
+
+// The thresholds between the linear and the power segments of the sRGB curve.
+//
+// With the `precise` feature they are the exact values where both segments meet,
+// instead of the rounded values of the standard.
+#[cfg(all(feature = "precise", any(feature = "std", feature = "no_std")))]
+const SRGB_DECODE_THRESHOLD: Float = 0.04044823627710735;
+#[cfg(all(feature = "precise", any(feature = "std", feature = "no_std")))]
+const SRGB_ENCODE_THRESHOLD: Float = 0.003130668442500569;
+#[cfg(all(not(feature = "precise"), any(feature = "std", feature = "no_std")))]
+const SRGB_DECODE_THRESHOLD: Float = 0.04045;
+#[cfg(all(not(feature = "precise"), any(feature = "std", feature = "no_std")))]
+const SRGB_ENCODE_THRESHOLD: Float = 0.0031308;
+
 // TODO
 // #[cfg(feature="half")]
 // mod impl_half {
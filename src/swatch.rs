@@ -0,0 +1,533 @@
+// acolor::swatch
+//
+//! Palette file formats.
+//!
+//! Reads and writes a [`Palette`] as GIMP palette (`.gpl`) text,
+//! as Adobe Swatch Exchange (`.ase`) binary data, and as JSON text
+//! with the `serde` feature.
+//!
+//! # JSON schema
+//! The JSON palette is an object with an optional `name` string and a `colors` array.
+//! It's represented by [`JsonPalette`], which keeps the names,
+//! while a [`Palette`] only keeps the colors.
+//!
+//! Each color is an object with:
+//! - `name`: an optional string.
+//! - `hex`: the color as a `#rrggbb` or `#rgb` string, where an alpha is discarded.
+//! - `space` and `values`: optionally, the color as floats in a CSS predefined
+//!   color space, like `"display-p3"`, and an array with its 3 components.
+//!
+//! Either `hex` or `space` and `values` must be present.
+//! When reading, `hex` has precedence, and unknown fields are ignored.
+//!
+//! ```json
+//! {
+//!   "name": "Brand",
+//!   "colors": [
+//!     { "name": "orange", "hex": "#ff8000" },
+//!     { "hex": "#0000ff", "space": "srgb-linear", "values": [0, 0, 1] }
+//!   ]
+//! }
+//! ```
+//!
+//! # Example
+//! ```
+//! use acolor::{image::Palette, srgb::Srgb8};
+//!
+//! let palette = Palette::new(vec![Srgb8::new(255, 128, 0), Srgb8::new(0, 0, 0)]).unwrap();
+//! let gpl = palette.to_gpl("Example");
+//! assert![gpl.starts_with("GIMP Palette\nName: Example\n")];
+//! assert_eq![Palette::from_gpl(&gpl).unwrap(), palette];
+//! assert_eq![Palette::from_ase(&palette.to_ase()).unwrap(), palette];
+//! # #[cfg(feature = "serde")]
+//! assert_eq![Palette::from_json(&palette.to_json("Example")).unwrap(), palette];
+//! ```
+//!
+//! # Links
+//! - <https://developer.gimp.org/core/standards/gpl/>
+//! - <http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase>
+//! - <https://www.w3.org/TR/css-color-4/#predefined>
+//
+// # TOC
+//
+// - SwatchError
+// - Palette file formats
+// - ase
+// - json
+//   - JsonPalette
+//   - JsonColor
+//
+
+use crate::{
+    image::Palette,
+    math::{f32_to_u8, u8_to_f32},
+    srgb::Srgb8,
+};
+#[cfg(all(feature = "serde", any(feature = "std", feature = "no_std")))]
+use crate::{parse::PredefinedColor, Color};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "serde")]
+use {
+    crate::parse::PredefinedSpace,
+    serde::{Deserialize, Serialize},
+};
+
+/// An error reading a palette file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwatchError {
+    /// The file signature is missing.
+    InvalidSignature,
+    /// The data ends before the file is complete.
+    TooShort,
+    /// The line with this number, starting at 1, is not valid.
+    InvalidLine(usize),
+    /// A color uses an unsupported color model.
+    UnsupportedModel([u8; 4]),
+    /// There are more colors than a [`Palette`] can hold.
+    TooManyColors(usize),
+    /// The JSON text is not valid, at this byte offset.
+    InvalidJson(usize),
+    /// The color at this index is missing or not valid.
+    InvalidColor(usize),
+}
+
+impl fmt::Display for SwatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwatchError::InvalidSignature => write!(f, "invalid file signature"),
+            SwatchError::TooShort => write!(f, "file data too short"),
+            SwatchError::InvalidLine(n) => write!(f, "invalid line {}", n),
+            SwatchError::UnsupportedModel(m) => {
+                write!(
+                    f,
+                    "unsupported color model {:?}",
+                    String::from_utf8_lossy(m)
+                )
+            }
+            SwatchError::TooManyColors(n) => write!(f, "too many colors: {}", n),
+            SwatchError::InvalidJson(n) => write!(f, "invalid JSON at byte {}", n),
+            SwatchError::InvalidColor(i) => write!(f, "invalid color {}", i),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SwatchError {}
+
+// Returns a palette from the read colors.
+fn palette(colors: Vec<Srgb8>) -> Result<Palette, SwatchError> {
+    Palette::new(colors).map_err(|e| SwatchError::TooManyColors(e.found))
+}
+
+/// # File formats
+impl Palette {
+    /// Returns a new palette from the text of a GIMP palette (`.gpl`) file.
+    ///
+    /// The color names and the header fields are ignored.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError`] if the header is missing, a color line is not valid,
+    /// or there are more than 256 colors.
+    pub fn from_gpl(text: &str) -> Result<Palette, SwatchError> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, header)) if header.trim() == "GIMP Palette" => (),
+            _ => return Err(SwatchError::InvalidSignature),
+        }
+        let mut colors = Vec::new();
+        for (i, line) in lines {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+            let mut rgb = [0; 3];
+            let mut fields = line.split_whitespace();
+            for c in rgb.iter_mut() {
+                *c = fields
+                    .next()
+                    .and_then(|f| f.parse().ok())
+                    .ok_or(SwatchError::InvalidLine(i + 1))?;
+            }
+            colors.push(Srgb8::new(rgb[0], rgb[1], rgb[2]));
+        }
+        palette(colors)
+    }
+
+    /// Returns the text of a GIMP palette (`.gpl`) file with the given `name`.
+    ///
+    /// Each color is named after its hexadecimal representation.
+    pub fn to_gpl(&self, name: &str) -> String {
+        let mut text = format!["GIMP Palette\nName: {}\n#\n", name];
+        for c in self.colors() {
+            text += &format!["{:3} {:3} {:3}\t{}\n", c.r, c.g, c.b, c.display_hex()];
+        }
+        text
+    }
+
+    /// Returns a new palette from the data of an Adobe Swatch Exchange (`.ase`) file.
+    ///
+    /// Colors in the `RGB`, `Gray` and `CMYK` models are read, the latter with
+    /// a naive conversion. Color names, groups and spot color types are ignored.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError`] if the signature is missing, the data is truncated,
+    /// a color uses the `LAB` model, or there are more than 256 colors.
+    pub fn from_ase(data: &[u8]) -> Result<Palette, SwatchError> {
+        let mut r = Reader(data);
+        if r.take(4)? != b"ASEF" {
+            return Err(SwatchError::InvalidSignature);
+        }
+        r.take(4)?; // version
+        let blocks = r.u32()?;
+        let mut colors = Vec::new();
+        for _ in 0..blocks {
+            let kind = r.u16()?;
+            let len = r.u32()? as usize;
+            let mut block = Reader(r.take(len)?);
+            if kind != ASE_COLOR {
+                continue;
+            }
+            let name_len = block.u16()? as usize;
+            block.take(name_len * 2)?;
+            let model: [u8; 4] = block.take(4)?.try_into().unwrap_or_default();
+            let color = match &model {
+                b"RGB " => {
+                    let [r, g, b] = [block.f32()?, block.f32()?, block.f32()?].map(f32_to_u8);
+                    Srgb8::new(r, g, b)
+                }
+                b"Gray" => {
+                    let v = f32_to_u8(block.f32()?);
+                    Srgb8::new(v, v, v)
+                }
+                b"CMYK" => {
+                    let [c, m, y, k] = [block.f32()?, block.f32()?, block.f32()?, block.f32()?];
+                    let [r, g, b] = [c, m, y].map(|v| f32_to_u8((1. - v) * (1. - k)));
+                    Srgb8::new(r, g, b)
+                }
+                _ => return Err(SwatchError::UnsupportedModel(model)),
+            };
+            colors.push(color);
+        }
+        palette(colors)
+    }
+
+    /// Returns the data of an Adobe Swatch Exchange (`.ase`) file.
+    ///
+    /// Each color is stored in the `RGB` model as a global color,
+    /// named after its hexadecimal representation.
+    pub fn to_ase(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"ASEF");
+        data.extend_from_slice(&[0, 1, 0, 0]);
+        data.extend_from_slice(&(self.len() as u32).to_be_bytes());
+        for c in self.colors() {
+            let name: Vec<u16> = c.to_hex_string().encode_utf16().chain([0]).collect();
+            let mut block = Vec::new();
+            block.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            for unit in name {
+                block.extend_from_slice(&unit.to_be_bytes());
+            }
+            block.extend_from_slice(b"RGB ");
+            for v in [c.r, c.g, c.b] {
+                block.extend_from_slice(&u8_to_f32(v).to_be_bytes());
+            }
+            block.extend_from_slice(&0u16.to_be_bytes()); // global color
+            data.extend_from_slice(&ASE_COLOR.to_be_bytes());
+            data.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            data.extend(block);
+        }
+        data
+    }
+
+    /// Returns a new palette from a list of hexadecimal colors, like `"#aabbcc, #123"`.
+    ///
+    /// The colors can have 3, 4, 6 or 8 digits, with or without a leading `#`,
+    /// and the alpha of the 4 and 8 digit forms is discarded. They can be separated
+    /// by any combination of whitespace, commas and semicolons.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError::InvalidColor`] with the index of the first color
+    /// that is not valid, or [`SwatchError::TooManyColors`].
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{image::Palette, srgb::Srgb8};
+    ///
+    /// let palette = Palette::parse_hex_list("#aabbcc, #123;\n 00ff0080").unwrap();
+    /// assert_eq![palette.colors(), [
+    ///     Srgb8::new(0xaa, 0xbb, 0xcc),
+    ///     Srgb8::new(0x11, 0x22, 0x33),
+    ///     Srgb8::new(0, 0xff, 0),
+    /// ]];
+    /// ```
+    pub fn parse_hex_list(list: &str) -> Result<Palette, SwatchError> {
+        let tokens = list.split(|c: char| c.is_whitespace() || c == ',' || c == ';');
+        let colors = tokens
+            .filter(|t| !t.is_empty())
+            .enumerate()
+            .map(|(i, t)| parse_hex(t).ok_or(SwatchError::InvalidColor(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        palette(colors)
+    }
+}
+
+// Returns a color from its hexadecimal representation, with an optional `#`
+// and 3, 4, 6 or 8 digits, discarding the alpha.
+fn parse_hex(s: &str) -> Option<Srgb8> {
+    let digits = s.strip_prefix('#').unwrap_or(s);
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    let [r, g, b] = match digits.len() {
+        6 | 8 => {
+            let shift = (digits.len() as u32 - 6) * 4;
+            [value >> 16, value >> 8, value].map(|v| (v >> shift) as u8)
+        }
+        3 | 4 => {
+            let shift = (digits.len() as u32 - 3) * 4;
+            [value >> 8, value >> 4, value].map(|v| ((v >> shift) & 0xf) as u8 * 0x11)
+        }
+        _ => return None,
+    };
+    Some(Srgb8::new(r, g, b))
+}
+
+/* ase */
+
+// The block type of a color entry.
+const ASE_COLOR: u16 = 0x0001;
+
+// Reads big-endian values from the front of a byte slice.
+struct Reader<'a>(&'a [u8]);
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SwatchError> {
+        if self.0.len() < n {
+            return Err(SwatchError::TooShort);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+    fn u16(&mut self) -> Result<u16, SwatchError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+    fn u32(&mut self) -> Result<u32, SwatchError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+    fn f32(&mut self) -> Result<f32, SwatchError> {
+        self.u32().map(f32::from_bits)
+    }
+}
+
+/* json */
+
+/// A palette as JSON text, following the [schema](self#json-schema).
+///
+/// Unlike a [`Palette`], it keeps the names of the palette and its colors.
+///
+/// # Example
+/// ```
+/// use acolor::{srgb::Srgb8, swatch::{JsonColor, JsonPalette}};
+///
+/// let json = JsonPalette {
+///     name: Some("Brand".into()),
+///     colors: vec![JsonColor::named("orange", Srgb8::new(255, 128, 0))],
+/// };
+/// let text = json.to_json();
+/// assert![text.contains(r##""name": "orange""##)];
+/// assert_eq![JsonPalette::from_json(&text).unwrap(), json];
+/// assert_eq![json.to_palette().unwrap().colors(), [Srgb8::new(255, 128, 0)]];
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonPalette {
+    /// The name of the palette.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The colors.
+    pub colors: Vec<JsonColor>,
+}
+
+/// A color of a [`JsonPalette`].
+///
+/// Either `hex` or `space` and `values` must be present.
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonColor {
+    /// The name of the color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The color as a `#rrggbb` or `#rgb` string, where an alpha is discarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    /// The name of a CSS predefined color space, like `"display-p3"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+    /// The 3 components of the color in `space`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<f32>>,
+}
+
+#[cfg(feature = "serde")]
+impl JsonPalette {
+    /// Returns a new JSON palette with the given `name` and the `hex` values of the colors.
+    pub fn from_palette(palette: &Palette, name: &str) -> JsonPalette {
+        JsonPalette {
+            name: Some(name.into()),
+            colors: palette
+                .colors()
+                .iter()
+                .map(|c| JsonColor::new(*c))
+                .collect(),
+        }
+    }
+
+    /// Returns the colors as a [`Palette`], discarding the names.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError::InvalidColor`] with the index of the first color
+    /// that is not valid, or [`SwatchError::TooManyColors`].
+    pub fn to_palette(&self) -> Result<Palette, SwatchError> {
+        let colors = self
+            .colors
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c.to_srgb8().ok_or(SwatchError::InvalidColor(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        palette(colors)
+    }
+
+    /// Returns a new JSON palette from JSON text.
+    ///
+    /// Unknown fields are ignored.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError::InvalidJson`] if the text is not valid JSON,
+    /// or doesn't follow the schema.
+    pub fn from_json(text: &str) -> Result<JsonPalette, SwatchError> {
+        serde_json::from_str(text).map_err(|e| json_error(text, &e))
+    }
+
+    /// Returns the palette as pretty-printed JSON text.
+    pub fn to_json(&self) -> String {
+        // these types always serialize successfully
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl JsonColor {
+    /// Returns a new unnamed color with its `hex` value.
+    pub fn new(color: Srgb8) -> JsonColor {
+        JsonColor {
+            hex: Some(color.to_hex_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a new color with a `name` and its `hex` value.
+    pub fn named(name: &str, color: Srgb8) -> JsonColor {
+        JsonColor {
+            name: Some(name.into()),
+            ..JsonColor::new(color)
+        }
+    }
+
+    /// Returns the color from its `hex` value, which has precedence,
+    /// or else from its `space` and `values`.
+    ///
+    /// The latter are converted into sRGB without gamut mapping,
+    /// which needs either the `std` or the `no_std` feature.
+    ///
+    /// Returns `None` if they are missing or not valid.
+    pub fn to_srgb8(&self) -> Option<Srgb8> {
+        if let Some(hex) = &self.hex {
+            return parse_hex(hex);
+        }
+        let space = PredefinedSpace::from_name(self.space.as_deref()?)?;
+        let components: [f32; 3] = self.values.as_deref()?.try_into().ok()?;
+        #[cfg(any(feature = "std", feature = "no_std"))]
+        return Some(
+            PredefinedColor {
+                space,
+                components,
+                alpha: 1.,
+            }
+            .to_linear_srgba32()
+            .color_to_srgb8(),
+        );
+        #[cfg(not(any(feature = "std", feature = "no_std")))]
+        {
+            let _ = (space, components);
+            None
+        }
+    }
+}
+
+/// # JSON
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "serde")))]
+impl Palette {
+    /// Returns a new palette from JSON text, following the [schema](self#json-schema).
+    ///
+    /// The names are discarded, use [`JsonPalette`] to keep them.
+    ///
+    /// # Errors
+    /// Returns [`SwatchError`] if the text is not valid JSON, a color doesn't
+    /// follow the schema, or there are more than 256 colors.
+    pub fn from_json(text: &str) -> Result<Palette, SwatchError> {
+        JsonPalette::from_json(text)?.to_palette()
+    }
+
+    /// Returns the palette as JSON text with the given `name`,
+    /// following the [schema](self#json-schema) with `hex` colors.
+    pub fn to_json(&self, name: &str) -> String {
+        JsonPalette::from_palette(self, name).to_json()
+    }
+
+    /// Returns the palette as JSON text with the given `name`,
+    /// following the [schema](self#json-schema) with `hex` colors
+    /// and their floats in the given `space`.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{image::Palette, parse::PredefinedSpace, srgb::Srgb8};
+    ///
+    /// let palette = Palette::new(vec![Srgb8::new(0, 0, 255)]).unwrap();
+    /// let json = palette.to_json_in_space("Blue", PredefinedSpace::SrgbLinear);
+    /// assert![json.contains(r##""space": "srgb-linear""##)];
+    /// assert_eq![Palette::from_json(&json).unwrap(), palette];
+    /// ```
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    #[cfg_attr(
+        feature = "nightly",
+        doc(cfg(all(feature = "serde", any(feature = "std", feature = "no_std"))))
+    )]
+    pub fn to_json_in_space(&self, name: &str, space: PredefinedSpace) -> String {
+        let mut json = JsonPalette::from_palette(self, name);
+        for (item, c) in json.colors.iter_mut().zip(self.colors()) {
+            let p = PredefinedColor::from_linear_srgba32(space, c.color_to_linear_srgba32());
+            item.space = Some(space.name().into());
+            item.values = Some(p.components.to_vec());
+        }
+        json.to_json()
+    }
+}
+
+// Returns the error at the byte offset of the line and column of a JSON error.
+#[cfg(feature = "serde")]
+fn json_error(text: &str, e: &serde_json::Error) -> SwatchError {
+    let line = e.line().saturating_sub(1);
+    let line_start: usize = text.split_inclusive('\n').take(line).map(str::len).sum();
+    SwatchError::InvalidJson(line_start + e.column().saturating_sub(1))
+}
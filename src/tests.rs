@@ -1,6 +1,6 @@
 // acolor::tests
 //
-//!
+//! Tests.
 //
 
 use crate::all::*;
@@ -8,6 +8,7 @@ use iunorm::Unorm8;
 
 #[test]
 #[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
 fn srgb8() {
     let c = Srgb8::new(0xA, 0xB, 0xC);
 
@@ -50,6 +51,7 @@ fn srgb8_no_default() {
 
 #[test]
 #[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
 fn srgba8() {
     let c = Srgba8::new(0xA, 0xB, 0xC, 0xD);
 
@@ -70,23 +72,176 @@ fn srgba8_no_default() {
     assert_eq![c.to_srgba32().to_srgba8(), c];
 }
 
-#[cfg(feature = "approx")]
+#[test]
+fn channel_order() {
+    let c = Srgba8::new(0xA, 0xB, 0xC, 0xD);
+    assert_eq![c.to_bgra_array(), [0xC, 0xB, 0xA, 0xD]];
+    assert_eq![Srgba8::from_bgra_array(c.to_bgra_array()), c];
+    assert_eq![c.to_bgr(), [0xC, 0xB, 0xA]];
+    assert_eq![c.to_argb_u32(), 0x0D0A0B0C];
+    assert_eq![Srgba8::from_argb_u32(0x0D0A0B0C), c];
+    assert_eq![c.to_rgba_u32(), 0x0A0B0C0D];
+    assert_eq![Srgba8::from_rgba_u32(0x0A0B0C0D), c];
+    assert_eq![c.swizzle("abgr"), [0xD, 0xC, 0xB, 0xA]];
+    assert_eq![c.swizzle("B0r"), [0xC, 0, 0xA]];
+
+    let c = c.to_srgb8();
+    assert_eq![Srgb8::from_bgr(c.to_bgr()), c];
+    assert_eq![c.to_bgra_array(), [0xC, 0xB, 0xA, 0xFF]];
+    assert_eq![c.to_argb_u32(), 0xFF0A0B0C];
+    assert_eq![c.swizzle("bgra"), [0xC, 0xB, 0xA, 0xFF]];
+    const BGR: [u8; 3] = Srgb8::new(1, 2, 3).swizzle("bgr");
+    assert_eq![BGR, [3, 2, 1]];
+}
+
+#[test]
+fn hex_literals() {
+    const PALETTE: [Srgb8; 2] = [Srgb8::from_hex(0x0a0b0c), Srgb8::from_hex(0xff_fdfeff)];
+    assert_eq![PALETTE[0], Srgb8::new(0xA, 0xB, 0xC)];
+    assert_eq![PALETTE[1], Srgb8::new(0xFD, 0xFE, 0xFF)];
+    assert_eq![PALETTE[1].to_hex(), 0xfdfeff];
+    assert_eq![Srgb8::from_hex(PALETTE[0].to_hex()), PALETTE[0]];
+
+    const C: Srgba8 = Srgba8::from_hex(0x0a0b0c0d);
+    assert_eq![C, Srgba8::new(0xA, 0xB, 0xC, 0xD)];
+    assert_eq![C.to_hex(), 0x0a0b0c0d];
+}
+
+#[test]
+fn yuv_frames() {
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    let red = Srgb8::new(255, 0, 0);
+
+    // 3×3 pixels, with 2×2 chroma samples
+    let frame = [white, white, red, white, white, red, black, black, red];
+    let (mut y, mut u, mut v) = ([0; 9], [0; 4], [0; 4]);
+    srgb8_to_i420(&frame, 3, YuvFormat::BT601, [&mut y, &mut u, &mut v]);
+    assert_eq![(y[0], u[0], v[0]), (235, 128, 128)];
+    assert_eq![(y[6], u[2], v[2]), (16, 128, 128)];
+    let full = YuvFormat::new(YuvMatrix::Bt601, Range::Full);
+    let (mut fy, mut fu, mut fv) = ([0; 9], [0; 4], [0; 4]);
+    srgb8_to_i420(&frame, 3, full, [&mut fy, &mut fu, &mut fv]);
+    assert_eq![(fy[2], fu[1], fv[1]), (76, 85, 255)];
+
+    let mut decoded = [Srgb8::default(); 9];
+    i420_to_srgb8([&y, &u, &v], 3, YuvFormat::BT601, &mut decoded);
+    assert_eq![decoded[..2], [white, white]];
+    assert_eq![decoded[6..8], [black, black]];
+    assert![decoded[8].approx_eq(&red, 1)];
+
+    let (mut ny, mut uv) = ([0; 9], [0; 8]);
+    srgb8_to_nv12(&frame, 3, YuvFormat::BT601, &mut ny, &mut uv);
+    assert_eq![ny, y];
+    assert_eq![(uv[2], uv[3]), (u[1], v[1])];
+    let mut nv12_decoded = [Srgb8::default(); 9];
+    nv12_to_srgb8(&ny, &uv, 3, YuvFormat::BT601, &mut nv12_decoded);
+    assert_eq![nv12_decoded, decoded];
+
+    // the JFIF reference equations
+    let c = Srgb8::new(30, 160, 220);
+    let (r, g, b) = (30., 160., 220.);
+    let jfif = Ycbcr8::new(
+        (0.299 * r + 0.587 * g + 0.114 * b + 0.5) as u8,
+        (128. - 0.168736 * r - 0.331264 * g + 0.5 * b + 0.5) as u8,
+        (128. + 0.5 * r - 0.418688 * g - 0.081312 * b + 0.5) as u8,
+    );
+    assert_eq![Ycbcr8::from_srgb8(c, YuvFormat::JFIF), jfif];
+    assert![jfif.to_srgb8(YuvFormat::JFIF).approx_eq(&c, 1)];
+    // mixing up the ranges
+    assert_eq![
+        Ycbcr8::new(16, 128, 128).to_srgb8(YuvFormat::JFIF),
+        Srgb8::new(16, 16, 16)
+    ];
+    assert_eq![Ycbcr8::new(16, 128, 128).to_srgb8(YuvFormat::BT601), black];
+
+    // video range
+    assert_eq![
+        Srgb8::new(16, 235, 126).expand_range(),
+        Srgb8::new(0, 255, 128)
+    ];
+    assert_eq![
+        Srgb8::new(0, 255, 128).compress_range(),
+        Srgb8::new(16, 235, 126)
+    ];
+    assert_eq![Srgb8::new(2, 250, 0).expand_range(), Srgb8::new(0, 255, 0)];
+    let c = Srgba8::new(16, 235, 126, 7);
+    assert_eq![c.expand_range(), Srgba8::new(0, 255, 128, 7)];
+    assert_eq![c.expand_range().compress_range(), c];
+    let c = Ycbcr8::new(235, 16, 240);
+    assert_eq![c.expand_range(), Ycbcr8::new(255, 1, 255)];
+    assert_eq![c.expand_range().compress_range(), c];
+    assert_eq![
+        Ycbcr8::new(16, 128, 128).expand_range(),
+        Ycbcr8::new(0, 128, 128)
+    ];
+    for n in 0..=u8::MAX {
+        let full = Srgb8::new(n, n, n);
+        let expanded = full.compress_range().expand_range();
+        assert![expanded.approx_eq(&full, 1)];
+        let ycbcr = Ycbcr8::new(n, n, n);
+        let limited = Ycbcr8::from_srgb8(full, YuvFormat::BT601);
+        let jfif = Ycbcr8::from_srgb8(full, YuvFormat::JFIF);
+        assert![limited.expand_range().y.abs_diff(jfif.y) <= 1];
+        let back = ycbcr.compress_range().expand_range();
+        assert![back.y.abs_diff(n) <= 1 && back.cb.abs_diff(n) <= 1, "{n}"];
+        assert_eq![Range::Limited.quantize(Range::Limited.normalize(n)), n];
+        assert_eq![Range::Full.quantize(Range::Full.normalize(n)), n];
+    }
+
+    for matrix in [YuvMatrix::Bt601, YuvMatrix::Bt709, YuvMatrix::Bt2020] {
+        for range in [Range::Full, Range::Limited] {
+            let format = YuvFormat::new(matrix, range);
+            let c = [Srgb8::new(30, 160, 220); 4];
+            let (mut y, mut u, mut v) = ([0; 4], [0; 1], [0; 1]);
+            srgb8_to_i420(&c, 2, format, [&mut y, &mut u, &mut v]);
+            let mut decoded = [Srgb8::default(); 4];
+            i420_to_srgb8([&y, &u, &v], 2, format, &mut decoded);
+            assert![
+                decoded[3].approx_eq(&c[0], 2),
+                "{format:?}: {:?}",
+                decoded[3]
+            ];
+        }
+    }
+}
+
+#[test]
+#[should_panic = "invalid channel"]
+fn channel_order_invalid() {
+    let _: [u8; 3] = Srgb8::new(1, 2, 3).swizzle("rgx");
+}
+
+#[cfg(all(feature = "approx", not(feature = "micromath")))]
 mod approx_tests {
     use super::*;
     use approx::assert_relative_eq;
 
+    // Returns the relative tolerance of a round trip through the sRGB encoding.
+    //
+    // With `fast_encode` the encoding is only within `4e-5` of the exact curve
+    // (see `nonlinearize32_fast`), which is `4e-4` relative to a `0.1` component,
+    // and the Oklab matrices and the Oklch hue angle amplify it up to `2e-3`.
+    fn tol(exact: f32) -> f32 {
+        if cfg!(feature = "fast_encode") {
+            exact.max(2e-3)
+        } else {
+            exact
+        }
+    }
+
     #[test]
     fn srgb32() {
         let c = Srgb32::new(0.1, 0.2, 0.3);
 
         // back and forth
+        let enc = tol(f32::EPSILON);
         assert_relative_eq![c.to_srgb8().to_srgb32(), c, max_relative = 0.02];
         assert_relative_eq![c.to_srgba8(255).to_srgb32(), c, max_relative = 0.02];
         assert_eq![c.to_srgba32(1.).to_srgb32(), c];
-        assert_relative_eq![c.to_linear_srgb32().to_srgb32(), c];
-        assert_relative_eq![c.to_linear_srgba32(1.).to_srgb32(), c];
-        assert_relative_eq![c.to_oklab32().to_srgb32(), c];
-        assert_relative_eq![c.to_oklch32().to_srgb32(), c];
+        assert_relative_eq![c.to_linear_srgb32().to_srgb32(), c, max_relative = enc];
+        assert_relative_eq![c.to_linear_srgba32(1.).to_srgb32(), c, max_relative = enc];
+        assert_relative_eq![c.to_oklab32().to_srgb32(), c, max_relative = enc];
+        assert_relative_eq![c.to_oklch32().to_srgb32(), c, max_relative = enc];
     }
 
     #[test]
@@ -94,13 +249,14 @@ mod approx_tests {
         let c = Srgba32::new(0.1, 0.2, 0.3, 0.4);
 
         // back and forth
+        let enc = tol(f32::EPSILON);
         assert_relative_eq![c.to_srgb8().to_srgba32(0.4), c, max_relative = 0.02];
         assert_relative_eq![c.to_srgba8().to_srgba32(), c, max_relative = 0.02];
         assert_eq![c.to_srgb32().to_srgba32(0.4), c];
-        assert_relative_eq![c.to_linear_srgb32().to_srgba32(0.4), c];
-        assert_relative_eq![c.to_linear_srgba32().to_srgba32(), c];
-        assert_relative_eq![c.to_oklab32().to_srgba32(0.4), c];
-        assert_relative_eq![c.to_oklch32().to_srgba32(0.4), c];
+        assert_relative_eq![c.to_linear_srgb32().to_srgba32(0.4), c, max_relative = enc];
+        assert_relative_eq![c.to_linear_srgba32().to_srgba32(), c, max_relative = enc];
+        assert_relative_eq![c.to_oklab32().to_srgba32(0.4), c, max_relative = enc];
+        assert_relative_eq![c.to_oklch32().to_srgba32(0.4), c, max_relative = enc];
     }
 
     #[test]
@@ -108,9 +264,10 @@ mod approx_tests {
         let c = LinearSrgb32::new(0.1, 0.2, 0.3);
 
         // back and forth
+        let enc = tol(f32::EPSILON);
         assert_relative_eq![c.to_srgb8().to_linear_srgb32(), c, max_relative = 8e-3];
         assert_relative_eq![c.to_srgba8(255).to_linear_srgb32(), c, max_relative = 8e-3];
-        assert_relative_eq![c.to_srgb32().to_linear_srgb32(), c];
+        assert_relative_eq![c.to_srgb32().to_linear_srgb32(), c, max_relative = enc];
         assert_eq![c.to_linear_srgba32(1.).to_linear_srgb32(), c];
         assert_relative_eq![c.to_oklab32().to_linear_srgb32(), c];
         assert_relative_eq![c.to_oklch32().to_linear_srgb32(), c];
@@ -121,9 +278,10 @@ mod approx_tests {
         let c = LinearSrgba32::new(0.1, 0.2, 0.3, 0.4);
 
         // back and forth
+        let enc = tol(f32::EPSILON);
         assert_relative_eq![c.to_srgb8().to_linear_srgba32(0.4), c, max_relative = 8e-3];
         assert_relative_eq![c.to_srgba8().to_linear_srgba32(), c, max_relative = 8e-3];
-        assert_relative_eq![c.to_srgb32().to_linear_srgba32(0.4), c];
+        assert_relative_eq![c.to_srgb32().to_linear_srgba32(0.4), c, max_relative = enc];
         assert_eq![c.to_linear_srgb32().to_linear_srgba32(0.4), c];
         assert_relative_eq![c.to_oklab32().to_linear_srgba32(0.4), c];
         assert_relative_eq![c.to_oklch32().to_linear_srgba32(0.4), c];
@@ -136,8 +294,8 @@ mod approx_tests {
         // back and forth
         assert_relative_eq![c.to_srgb8().to_oklab32(), c, max_relative = 3e-3];
         assert_relative_eq![c.to_srgba8(255).to_oklab32(), c, max_relative = 3e-3];
-        assert_relative_eq![c.to_srgb32().to_oklab32(), c, max_relative = 3e-6];
-        assert_relative_eq![c.to_srgba32(1.).to_oklab32(), c, max_relative = 3e-6];
+        assert_relative_eq![c.to_srgb32().to_oklab32(), c, max_relative = tol(3e-6)];
+        assert_relative_eq![c.to_srgba32(1.).to_oklab32(), c, max_relative = tol(3e-6)];
         assert_relative_eq![c.to_linear_srgb32().to_oklab32(), c, max_relative = 3e-6];
         assert_relative_eq![c.to_linear_srgba32(1.).to_oklab32(), c, max_relative = 3e-6];
         assert_relative_eq![c.to_oklch32().to_oklab32(), c];
@@ -150,10 +308,2257 @@ mod approx_tests {
         // back and forth
         assert_relative_eq![c.to_srgb8().to_oklch32(), c, max_relative = 0.1];
         assert_relative_eq![c.to_srgba8(255).to_oklch32(), c, max_relative = 0.1];
-        assert_relative_eq![c.to_srgb32().to_oklch32(), c, max_relative = 2e-5];
-        assert_relative_eq![c.to_srgba32(1.).to_oklch32(), c, max_relative = 2e-5];
+        assert_relative_eq![c.to_srgb32().to_oklch32(), c, max_relative = tol(2e-5)];
+        assert_relative_eq![c.to_srgba32(1.).to_oklch32(), c, max_relative = tol(2e-5)];
         assert_relative_eq![c.to_linear_srgb32().to_oklch32(), c, max_relative = 3e-5];
         assert_relative_eq![c.to_linear_srgba32(1.).to_oklch32(), c, max_relative = 3e-5];
         assert_relative_eq![c.to_oklab32().to_oklch32(), c];
     }
 }
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn batch() {
+    let src = [Srgb8::new(0xA, 0xB, 0xC), Srgb8::new(0xF0, 0x80, 0x10)];
+
+    let mut linear = [LinearSrgb32::default(); 2];
+    convert_slice(&src, &mut linear);
+    assert_eq![linear[1], src[1].to_linear_srgb32()];
+
+    // in place, leaving the alpha untouched
+    let mut buf = [[0.2, 0.4, 0.6, 0.5]];
+    srgb_to_linear_in_place(&mut buf);
+    linear_to_oklab_in_place(&mut buf);
+    assert_eq![buf[0][3], 0.5];
+    oklab_to_linear_in_place(&mut buf);
+    linear_to_srgb_in_place(&mut buf);
+    let c = Srgba32::from_array(buf[0]);
+    assert_eq![c.to_srgba8(), Srgba32::new(0.2, 0.4, 0.6, 0.5).to_srgba8()];
+
+    #[cfg(feature = "alloc")]
+    {
+        let linear = srgb8_slice_to_linear(&src);
+        assert_eq![linear_slice_to_srgb8(&linear), src];
+        assert_eq![oklab_slice_to_srgb8(&srgb8_slice_to_oklab(&src)), src];
+        assert_eq![convert_vec::<_, Srgba8>(&src)[0], src[0].to_srgba8(u8::MAX)];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn batch_iterators() {
+    let src = [Srgb8::new(10, 20, 30), Srgb8::new(200, 100, 50)];
+    let mut lab = src.iter().convert::<Oklab32>();
+    assert_eq![lab.len(), 2];
+    assert_eq![lab.next(), Some(src[0].to_oklab32())];
+    assert_eq![lab.next_back(), Some(src[1].to_oklab32())];
+    assert_eq![lab.next(), None];
+
+    let owned: [Srgba8; 2] = {
+        let mut it = src.into_iter().convert::<Srgba8>();
+        [it.next().unwrap(), it.next().unwrap()]
+    };
+    assert_eq![owned[1], Srgba8::new(200, 100, 50, 255)];
+
+    let lightness = src.iter().map_color(|c: Oklab32| c.l);
+    assert![lightness.eq(src.iter().map(|c| c.to_oklab32().l))];
+    let inverted = src
+        .iter()
+        .map_color(|c: Srgb8| Srgb8::new(255 - c.r, 255 - c.g, 255 - c.b))
+        .convert::<Srgb8>();
+    assert![inverted.eq([Srgb8::new(245, 235, 225), Srgb8::new(55, 155, 205)])];
+}
+
+#[test]
+#[cfg(not(feature = "micromath"))]
+fn batch_bytes() {
+    let mut buf = [1, 2, 3, 4, 5, 6];
+    map_bytes_in_place(&mut buf, ByteLayout::Bgr, |c| {
+        assert_eq![c.a, u8::MAX];
+        Srgba8::new(c.r, c.g, c.b + 10, 0)
+    });
+    assert_eq![buf, [11, 2, 3, 14, 5, 6]];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        let src = [10, 20, 30, 40, 200, 100, 50, 60];
+        let (mut r, mut g, mut b) = ([0.; 2], [0.; 2], [0.; 2]);
+        bytes_to_linear_planes(&src, ByteLayout::Rgba, [&mut r, &mut g, &mut b]);
+        assert_eq![r[1], Srgb8::new(200, 100, 50).to_linear_srgb32().r];
+
+        let mut dst = [0, 0, 0, 40, 0, 0, 0, 60];
+        linear_planes_to_bytes([&r, &g, &b], ByteLayout::Rgba, &mut dst);
+        assert_eq![dst, src];
+
+        let mut buf = src;
+        map_bytes_oklch_in_place(&mut buf, ByteLayout::Rgba, |c| c);
+        assert_eq![buf, src];
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn batch_parallel() {
+    let src: Vec<Srgb8> = (0..=255).map(|n| Srgb8::new(n, 255 - n, n / 2)).collect();
+    let mut dst = vec![Oklab32::default(); src.len()];
+    par_convert_slice(&src, &mut dst);
+    assert_eq![dst, convert_vec::<_, Oklab32>(&src)];
+    assert_eq![par_convert_vec::<_, Oklab32>(&src), dst];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn linearize8_lut() {
+    for n in 0..=u8::MAX {
+        #[cfg(not(feature = "fast_lut"))]
+        assert_eq![linearize8(n), linearize32(Unorm8(n).to_f32(), GAMMA_32)];
+        // the table is computed at compile time with a higher precision
+        #[cfg(feature = "fast_lut")]
+        assert![(linearize8(n) - linearize32(Unorm8(n).to_f32(), GAMMA_32)).abs() < 1e-6];
+        assert_eq![Srgb8::new(n, n, n).to_linear_srgb32().to_srgb8().r, n];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn nonlinearize32_fast_error() {
+    for n in 0..=100_000 {
+        let linear = n as f32 / 100_000.;
+        let diff = nonlinearize32_fast(linear) - nonlinearize32(linear, GAMMA_32);
+        assert![diff.abs() < 4e-5, "{linear}: {diff}"];
+    }
+}
+
+// micromath's `powf` is only roughly approximated, so the exact-value tests are
+// disabled with it, and this checks instead the error bound documented in `math`.
+#[test]
+#[cfg(feature = "micromath")]
+fn micromath_error_bound() {
+    for n in 0..=u8::MAX {
+        let exact = libm::powf((Unorm8(n).to_f32() + 0.055) / 1.055, 2.4);
+        let linear = Srgb8::new(n, n, n).to_linear_srgb32();
+        // below 11 the curve is linear and doesn't need `powf`
+        if n > 10 {
+            assert![(linear.r - exact).abs() < 0.15, "{n}: {} {exact}", linear.r];
+        }
+        assert![linear.to_srgb8().r.abs_diff(n) <= 32, "{n}"];
+        let c = Srgb8::new(n, 255 - n, n / 2);
+        let d = c.to_oklab32().to_srgb8();
+        assert![c.zip_with(d, u8::abs_diff).iter().all(|x| x <= 128)];
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn lut3d() {
+    let cube = "# comment\nTITLE \"Invert\"\nLUT_3D_SIZE 2\n\n\
+        1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0\n";
+    let lut = Lut3d::parse_cube(cube).unwrap();
+    assert_eq![lut.title(), Some("Invert")];
+    assert_eq![lut.size(), 2];
+
+    let c = Srgb32::new(0.25, 0.5, 0.875);
+    let inverted = Srgb32::new(0.75, 0.5, 0.125);
+    assert_eq![lut.apply(c), inverted];
+    assert_eq![
+        lut.clone()
+            .with_interpolation(LutInterpolation::Tetrahedral)
+            .apply(c),
+        inverted
+    ];
+
+    let identity = Lut3d::identity(17).unwrap();
+    for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+        let identity = identity.clone().with_interpolation(interpolation);
+        let mut colors = [c, Srgb32::new(1., 0., 0.3), Srgb32::new(-1., 2., 0.)];
+        identity.apply_slice(&mut colors);
+        assert_eq![colors[2], Srgb32::new(0., 1., 0.)];
+        for (a, b) in [c, Srgb32::new(1., 0., 0.3)].iter().zip(colors) {
+            let diff = [a.r - b.r, a.g - b.g, a.b - b.b];
+            assert![diff.iter().all(|d| *d < 1e-6 && *d > -1e-6), "{diff:?}"];
+        }
+    }
+
+    assert_eq![Lut3d::parse_cube("1 1 1"), Err(CubeError::MissingSize)];
+    assert_eq![
+        Lut3d::parse_cube("LUT_1D_SIZE 2"),
+        Err(CubeError::Unsupported1d)
+    ];
+    assert_eq![
+        Lut3d::parse_cube("LUT_3D_SIZE 2\nFOO 1"),
+        Err(CubeError::InvalidLine(2))
+    ];
+    assert_eq![
+        Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0"),
+        Err(CubeError::WrongEntryCount {
+            expected: 8,
+            found: 1
+        })
+    ];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn icc_matrix_trc() {
+    use alloc::vec::Vec;
+
+    // builds an sRGB-like v4 profile with a parametric curve
+    let tags: [(&[u8; 4], Vec<u8>); 7] = {
+        let xyz = |v: [f32; 3]| {
+            let mut t = b"XYZ \0\0\0\0".to_vec();
+            v.iter()
+                .for_each(|n| t.extend(((n * 65536.) as i32).to_be_bytes()));
+            t
+        };
+        let mut para = b"para\0\0\0\0\0\x03\0\0".to_vec();
+        [2.4, 1. / 1.055, 0.055 / 1.055, 1. / 12.92, 0.04045]
+            .iter()
+            .for_each(|n: &f32| para.extend(((n * 65536.) as i32).to_be_bytes()));
+        [
+            (b"rXYZ", xyz([0.4360747, 0.2225045, 0.0139322])),
+            (b"gXYZ", xyz([0.3850649, 0.7168786, 0.0971045])),
+            (b"bXYZ", xyz([0.1430804, 0.0606169, 0.7141733])),
+            (b"wtpt", xyz([0.9642, 1., 0.8249])),
+            (b"rTRC", para.clone()),
+            (b"gTRC", para.clone()),
+            (b"bTRC", para),
+        ]
+    };
+    let mut data = [0u8; 128].to_vec();
+    data[8] = 4;
+    data[16..20].copy_from_slice(b"RGB ");
+    data[20..24].copy_from_slice(b"XYZ ");
+    data[36..40].copy_from_slice(b"acsp");
+    data.extend((tags.len() as u32).to_be_bytes());
+    let mut offset = 132 + tags.len() * 12;
+    for (sig, tag) in &tags {
+        data.extend(*sig);
+        data.extend((offset as u32).to_be_bytes());
+        data.extend((tag.len() as u32).to_be_bytes());
+        offset += tag.len();
+    }
+    tags.iter().for_each(|(_, tag)| data.extend(tag));
+
+    let profile = IccProfile::parse(&data).unwrap();
+    assert_eq![profile.version(), (4, 0)];
+    let transform = IccTransform::new(&profile);
+
+    for c in [
+        Srgb8::new(0, 0, 0),
+        Srgb8::new(255, 128, 3),
+        Srgb8::new(10, 200, 90),
+    ] {
+        let device = transform.to_device(c);
+        let expected = Srgb32::to_array(c.to_srgb32());
+        for (d, e) in device.iter().zip(expected) {
+            assert![(d - e) < 1e-3 && (e - d) < 1e-3, "{device:?} {expected:?}"];
+        }
+        assert_eq![transform.from_device(device).to_srgb8(), c];
+    }
+
+    assert_eq![IccProfile::parse(&data[..100]), Err(IccError::TooShort)];
+    data[36] = 0;
+    assert_eq![IccProfile::parse(&data), Err(IccError::InvalidSignature)];
+
+    // degenerate tables
+    assert_eq![IccCurve::Table(Vec::new()).eval(0.3), 0.3];
+    assert_eq![IccCurve::Table(Vec::new()).eval_inverse(0.3), 0.3];
+    assert_eq![IccCurve::Table([u16::MAX].to_vec()).eval(0.3), 1.];
+    assert_eq![IccCurve::Table([0].to_vec()).eval_inverse(0.3), 1.];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn transfer_hdr() {
+    let close = |a: f32, b: f32| (a - b) < 1e-4 && (b - a) < 1e-4;
+
+    // PQ: 100 cd/m² is encoded at about 0.508
+    assert![close(pq_inverse_eotf(100. / PQ_MAX_NITS), 0.50808)];
+    assert![close(pq_eotf(1.), 1.)];
+    assert_eq![pq_eotf(0.), 0.];
+
+    // HLG: the reference white is at 0.5 and 1.0 maps to 1.0
+    assert![close(hlg_oetf(1. / 12.), 0.5)];
+    assert![close(hlg_oetf(1.), 1.)];
+    assert![close(hlg_system_gamma(1000.), HLG_SYSTEM_GAMMA)];
+
+    for n in 0..=100 {
+        let v = n as f32 / 100.;
+        assert![close(pq_eotf(pq_inverse_eotf(v)), v)];
+        assert![close(hlg_inverse_oetf(hlg_oetf(v)), v)];
+        let rgb = [v, v * 0.5, 1. - v];
+        let back = hlg_inverse_eotf(hlg_eotf(rgb, 1.2), 1.2);
+        assert![
+            rgb.iter().zip(back).all(|(a, b)| close(*a, b)),
+            "{rgb:?} {back:?}"
+        ];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn transfer_function() {
+    use TransferFunction as Tf;
+    let close = |a: f32, b: f32| (a - b) < 1e-4 && (b - a) < 1e-4;
+
+    for tf in [
+        Tf::SrgbPiecewise,
+        Tf::PureGamma(2.2),
+        Tf::Linear,
+        Tf::Rec709,
+        Tf::Pq,
+        Tf::Hlg,
+    ] {
+        for n in 0..=100 {
+            let v = n as f32 / 100.;
+            assert![close(tf.decode(tf.encode(v)), v), "{tf:?} {v}"];
+        }
+        for n in 0..=u8::MAX {
+            assert_eq![tf.encode8(tf.decode8(n)), n, "{tf:?}"];
+            assert_eq![tf.decode8_range(n, Range::Full), tf.decode8(n)];
+        }
+        for n in 16..=235 {
+            let linear = tf.decode8_range(n, Range::Limited);
+            assert_eq![tf.encode8_range(linear, Range::Limited), n, "{tf:?}"];
+        }
+    }
+    // the limited range keeps the headroom above white
+    assert![Tf::Rec709.encode8_range(1.05, Range::Limited) > 235];
+    assert_eq![Tf::Rec709.encode8_range(-1., Range::Limited), 0];
+    // the default conversions use the sRGB piecewise curve
+    let c = Srgb32::new(0.2, 0.5, 0.8);
+    assert_eq![
+        c.to_linear_srgb32(),
+        c.to_linear_srgb32_with(Tf::SrgbPiecewise)
+    ];
+    assert_eq![
+        Srgb8::new(10, 20, 30).to_linear_srgb32_with(Tf::Linear).g,
+        Unorm8(20).to_f32()
+    ];
+    // a pure 2.2 gamma differs from the sRGB curve in the shadows
+    assert![Tf::PureGamma(2.2).decode(0.02) < Tf::SrgbPiecewise.decode(0.02)];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn transfer_slices() {
+    use TransferFunction as Tf;
+    let bytes: [u8; 256] = core::array::from_fn(|i| i as u8);
+    for tf in [Tf::SrgbPiecewise, Tf::GAMMA_22, Tf::Rec709, Tf::Linear] {
+        let mut linear = [0.; 256];
+        linearize8_slice(&bytes, &mut linear, tf);
+        assert_eq![linear[200], tf.decode8(200)];
+        let mut back = [0; 256];
+        nonlinearize8_slice(&linear, &mut back, tf);
+        assert_eq![back, bytes];
+
+        let mut buf = linear;
+        nonlinearize_slice(&mut buf, tf);
+        assert_eq![buf[100], tf.encode(linear[100])];
+        linearize_slice(&mut buf, tf);
+        // `fast_encode` encodes sRGB within `4e-5`, and decoding scales it by a slope below 2.3
+        let tolerance = match tf {
+            Tf::SrgbPiecewise if cfg!(feature = "fast_encode") => 1e-4,
+            _ => 1e-6,
+        };
+        assert![(buf[100] - linear[100]).abs() < tolerance, "{tf:?}"];
+    }
+}
+
+#[test]
+#[should_panic = "lengths differ"]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn transfer_slices_lengths() {
+    linearize8_slice(&[0; 3], &mut [0.; 4], TransferFunction::Linear);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn transfer_gamma22() {
+    let tf = TransferFunction::GAMMA_22;
+    assert_eq![tf, TransferFunction::PureGamma(2.2)];
+    for n in 0..=u8::MAX {
+        let c = Srgb8::new(n, n, n);
+        let linear = c.to_linear_srgb32_with(tf);
+        let expected = crate::math::powf(Unorm8(n).to_f32(), GAMMA_22);
+        assert![(linear.r - expected) < 1e-6 && (expected - linear.r) < 1e-6];
+        assert_eq![Srgb8::from_linear_srgb32_with(linear, tf), c];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn batch_config() {
+    let config = ColorConfig::GAMMA_22;
+    let mut buf = [[0.5, 0.25, 1.0, 0.3]];
+    srgb_to_linear_in_place_with(&mut buf, config);
+    assert_eq![buf[0][1], TransferFunction::GAMMA_22.decode(0.25)];
+    assert_eq![buf[0][3], 0.3];
+    linear_to_srgb_in_place_with(&mut buf, config);
+    assert![(buf[0][1] - 0.25) < 1e-6 && (0.25 - buf[0][1]) < 1e-6];
+
+    let bytes = [10, 100, 200, 7];
+    let (mut r, mut g, mut b) = ([0.; 1], [0.; 1], [0.; 1]);
+    bytes_to_linear_planes_with(&bytes, ByteLayout::Rgba, config, [&mut r, &mut g, &mut b]);
+    assert_eq![
+        g[0],
+        Srgb8::new(0, 100, 0)
+            .to_linear_srgb32_with(config.transfer)
+            .g
+    ];
+    let mut out = [0, 0, 0, 7];
+    linear_planes_to_bytes_with([&r, &g, &b], ByteLayout::Rgba, config, &mut out);
+    assert_eq![out, bytes];
+
+    // the default config matches the default conversions
+    let mut a = [50, 60, 70];
+    let mut b = a;
+    map_bytes_oklch_in_place(&mut a, ByteLayout::Rgb, |c| c);
+    map_bytes_oklch_in_place_with(&mut b, ByteLayout::Rgb, ColorConfig::default(), |c| c);
+    assert_eq![a, b];
+}
+
+#[test]
+#[cfg(not(feature = "micromath"))]
+fn const_conversions() {
+    const PALETTE: [Oklch32; 3] = [
+        Oklch32::new(0.6, 0.2, 30.),
+        Oklch32::new(0.8, 0.1, 135.),
+        Oklch32::new(0.4, 0.15, 300.),
+    ];
+    const LINEAR: [LinearSrgb32; 3] = [
+        PALETTE[0].to_linear_srgb32_const(),
+        PALETTE[1].to_linear_srgb32_const(),
+        PALETTE[2].to_linear_srgb32_const(),
+    ];
+    const BYTES: Srgb8 = Srgb32::new(0.5, 1., 0.).to_srgb8();
+    assert_eq![BYTES, Srgb8::new(128, 255, 0)];
+    assert_eq![Srgb8::new(128, 255, 0).to_srgb32().g, 1.];
+
+    let close = |a: f32, b: f32| (a - b) < 1e-6 && (b - a) < 1e-6;
+    for (lch, linear) in PALETTE.iter().zip(LINEAR) {
+        let lab = lch.to_oklab32_const();
+        let back = lab.to_oklch32_const();
+        assert![close(back.c, lch.c) && (back.h - lch.h) < 1e-3 && (lch.h - back.h) < 1e-3];
+        assert_eq![lab.to_linear_srgb32(), linear];
+        #[cfg(any(feature = "std", feature = "no_std"))]
+        {
+            let exact = lch.to_oklab32();
+            assert![
+                close(lab.a, exact.a) && close(lab.b, exact.b),
+                "{lab:?} {exact:?}"
+            ];
+            let exact = lab.to_oklch32();
+            assert![
+                close(back.c, exact.c) && (back.h - exact.h) < 1e-3 && (exact.h - back.h) < 1e-3
+            ];
+        }
+    }
+    // all quadrants
+    for h in (0..360).step_by(15) {
+        let lab = Oklch32::new(0.5, 0.1, h as f32).to_oklab32_const();
+        let back = Oklch32::from_oklab32_const(lab);
+        let diff = back.h - h as f32;
+        assert![diff < 1e-3 && diff > -1e-3, "{h}: {back:?}"];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn from_into_color() {
+    fn to_lab<C: IntoColor<Oklab32>>(c: C) -> Oklab32 {
+        c.into_color()
+    }
+    let c = Srgb8::new(200, 100, 50);
+    assert_eq![to_lab(c), c.to_oklab32()];
+    assert_eq![to_lab(c.to_oklab32()), c.to_oklab32()];
+    assert_eq![Srgba8::from_color(c), c.to_srgba8(255)];
+    let linear: LinearSrgb32 = c.into_color();
+    assert_eq![linear, c.to_linear_srgb32()];
+    assert_eq![Srgb8::from_color(c.to_oklch32()), c];
+}
+
+#[test]
+#[cfg(not(feature = "micromath"))]
+fn any_color() {
+    let c = AnyColor::from(Srgb8::new(10, 20, 30));
+    assert_eq![c.space(), ColorSpace::Srgb8];
+    assert_eq![ColorSpace::Oklch32.name(), "Oklch32"];
+    assert_eq![ColorSpace::ALL.len(), 8];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        for space in ColorSpace::ALL {
+            let converted = c.convert_to(*space);
+            assert_eq![converted.space(), *space];
+            assert_eq![converted.color_to_srgb8(), Srgb8::new(10, 20, 30)];
+        }
+        assert_eq![c.color_green(), Unorm8(20).to_f32()];
+        assert_eq![c.color_to_array4()[3], 1.];
+        assert_eq![Oklab32::from_color(c), Srgb8::new(10, 20, 30).to_oklab32()];
+    }
+}
+
+#[test]
+fn generic_components() {
+    const C: Srgb<u8> = Srgb::new(1, 2, 3);
+    const A: [u8; 3] = Srgb8::to_array(C);
+    assert_eq![A, [1, 2, 3]];
+    assert_eq![Srgb8::from_tuple((1, 2, 3)), C];
+
+    let c64: Srgba64 = Srgba32::new(0.5, 0.25, 0.125, 1.).into();
+    assert_eq![Srgba64::to_array(c64), [0.5, 0.25, 0.125, 1.]];
+    let lab: Oklab64 = Oklab32::new(0.5, 0.1, -0.1).into();
+    assert_eq![lab.l, 0.5];
+    assert_eq![Oklch64::from_array([0.5, 0.1, 90.]).h, 90.];
+}
+
+#[test]
+fn components_iteration() {
+    let c = Srgba8::new(1, 2, 3, 4);
+    assert_eq![c.iter().map(u32::from).sum::<u32>(), 10];
+    assert_eq![c.into_iter().max(), Some(4)];
+    assert_eq![(&c).into_iter().count(), 4];
+
+    let mut lab = Oklab32::new(0.5, 0.1, 0.2);
+    for x in &mut lab {
+        *x *= 2.;
+    }
+    assert_eq![Oklab32::to_array(lab), [1., 0.2, 0.4]];
+    assert_eq![Oklch32::new(0.5, 0.1, 90.).iter().last(), Some(90.)];
+}
+
+#[test]
+fn components_index() {
+    let mut c = Srgb32::new(0.1, 0.2, 0.3);
+    c[2] = 0.5;
+    assert_eq![c[Channel::Blue], 0.5];
+    for ch in Channel::RGB {
+        c[ch] *= 2.;
+    }
+    assert_eq![c, Srgb32::new(0.2, 0.4, 1.)];
+    assert_eq![Oklch32::new(0.5, 0.1, 90.)[2], 90.];
+    assert_eq![Srgba8::new(1, 2, 3, 4)[Channel::Alpha], 4];
+}
+
+#[test]
+#[should_panic]
+fn components_index_out_of_bounds() {
+    let _ = Srgb8::new(1, 2, 3)[Channel::Alpha];
+}
+
+#[test]
+fn components_arithmetic() {
+    let a = Srgb8::new(200, 100, 10);
+    assert_eq![a + Srgb8::new(100, 100, 100), Srgb8::new(255, 200, 110)];
+    assert_eq![a - Srgb8::new(100, 200, 5), Srgb8::new(100, 0, 5)];
+    assert_eq![a * 2, Srgb8::new(255, 200, 20)];
+    assert_eq![a / 2, Srgb8::new(100, 50, 5)];
+    assert_eq![a / 0, Srgb8::new(255, 255, 255)];
+
+    // averaging
+    let mut sum = LinearSrgb32::new(0., 0., 0.);
+    for c in [
+        LinearSrgb32::new(0.5, 0., 1.),
+        LinearSrgb32::new(0.25, 1., 0.),
+    ] {
+        sum += c;
+    }
+    assert_eq![sum / 2., LinearSrgb32::new(0.375, 0.5, 0.5)];
+
+    let mut lab = Oklab64::from_array([0.5, 0.1, -0.1]);
+    lab *= 2.;
+    assert_eq![
+        lab - Oklab64::from_array([0.5, 0.1, -0.1]),
+        Oklab64::from_array([0.5, 0.1, -0.1])
+    ];
+
+    // the hue wraps around, and isn't scaled
+    let lch = Oklch32::new(0.4, 0.1, 350.) + Oklch32::new(0.2, 0.1, 30.);
+    assert_eq![lch.h, 20.];
+    assert_eq![(lch / 2.).h, 20.];
+    assert_eq![
+        (Oklch64::from_array([0.5, 0.1, 10.]) - Oklch64::from_array([0.1, 0., 30.])).h,
+        340.
+    ];
+}
+
+#[test]
+fn components_map() {
+    let c = Srgb8::new(10, 20, 30);
+    let c32: Srgb32 = c.map(|x| x as f32 / 10.);
+    assert_eq![c32, Srgb32::new(1., 2., 3.)];
+    assert_eq![
+        c.zip_with(Srgb8::new(5, 25, 30), u8::max),
+        Srgb8::new(10, 25, 30)
+    ];
+
+    let a = Srgba8::new(10, 20, 30, 40);
+    assert_eq![a.map_rgb(|x| x * 2), Srgba8::new(20, 40, 60, 40)];
+    assert_eq![
+        a.zip_with_rgb(Srgba8::new(1, 1, 1, 1), u8::wrapping_sub),
+        Srgba8::new(9, 19, 29, 40)
+    ];
+}
+
+#[test]
+fn components_bytes() {
+    let c = Srgba8::new(1, 2, 3, 4);
+    assert_eq![c.to_bytes(), [1, 2, 3, 4]];
+    assert_eq![Srgba8::from_bytes([1, 2, 3, 4]), c];
+
+    let f = LinearSrgb32::new(0.5, -1., 2.);
+    let bytes = f.to_le_bytes();
+    assert_eq![bytes[4..8], (-1f32).to_le_bytes()];
+    assert_eq![LinearSrgb32::from_le_bytes(bytes), f];
+    let lab = Oklab64::from_array([0.5, 0.1, -0.1]);
+    assert_eq![Oklab64::from_le_bytes(lab.to_le_bytes()), lab];
+
+    #[cfg(all(feature = "unsafe", not(feature = "safe")))]
+    {
+        let mut buf = [10, 20, 30, 40, 50, 60];
+        let colors = Srgb8::slice_from_bytes_mut(&mut buf).unwrap();
+        colors[1].g = 0;
+        assert_eq![Srgb8::slice_as_bytes(colors), &[10, 20, 30, 40, 0, 60]];
+        assert_eq![colors[0].as_bytes(), &[10, 20, 30]];
+        assert![Srgb8::slice_from_bytes(&buf[..5]).is_none()];
+    }
+}
+
+#[test]
+fn from_arrays_and_slices() {
+    let c: Srgba8 = [1, 2, 3, 4].into();
+    assert_eq![c, Srgba8::new(1, 2, 3, 4)];
+    let a: [u8; 4] = c.into();
+    assert_eq![a, [1, 2, 3, 4]];
+
+    let buf = [0.1, 0.2, 0.3, 0.4];
+    assert_eq![
+        Oklab32::try_from(&buf[..3]),
+        Ok(Oklab32::from_array([0.1, 0.2, 0.3]))
+    ];
+    assert_eq![
+        LinearSrgb32::try_from(&buf[..]),
+        Err(LengthError {
+            expected: 3,
+            found: 4
+        })
+    ];
+    assert_eq![Srgba32::try_from(&buf[..]).map(|c| c.a), Ok(0.4)];
+
+    let t: (f32, f32, f32) = Oklch32::from((0.5, 0.1, 400.)).into();
+    assert_eq![t, (0.5, 0.1, 400.)];
+}
+
+#[test]
+fn components_setters() {
+    const C: Srgba8 = Srgba8::new(1, 2, 3, 4).with_green(20).with_alpha(255);
+    assert_eq![C, Srgba8::new(1, 20, 3, 255)];
+    assert_eq![LinearSrgb32::new(0., 0., 0.).with_blue(1.).b, 1.];
+    let lch = Oklch32::new(0.5, 0.1, 30.).with_hue(200.).with_chroma(0.2);
+    assert_eq![(lch.c, lch.h), (0.2, 200.)];
+    assert_eq![Oklab32::new(0.5, 0.1, 0.1).with_lightness(0.7).l, 0.7];
+}
+
+#[test]
+fn alpha_policy() {
+    assert_eq![Srgba8::opaque(1, 2, 3), Srgba8::new(1, 2, 3, 255)];
+    assert_eq![LinearSrgba32::transparent(0.5, 0.5, 0.5).a, 0.];
+    let c = Srgb8::new(1, 2, 3);
+    assert_eq![
+        c.add_alpha(AlphaPolicy::Transparent),
+        Srgba8::new(1, 2, 3, 0)
+    ];
+    assert_eq![c.add_alpha(AlphaPolicy::default()), c.to_srgba8(u8::MAX)];
+    assert_eq![Srgb64::new(0., 0., 0.).add_alpha(AlphaPolicy::Opaque).a, 1.];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn display() {
+    use alloc::{format, string::ToString};
+
+    let c = Srgba8::new(255, 0, 128, 51);
+    assert_eq![c.to_string(), "rgb(255 0 128 / 0.2)"];
+    assert_eq![c.display_hex().to_string(), "#ff008033"];
+    assert_eq![Srgb32::new(1., 0., 0.).display_hex().to_string(), "#ff0000"];
+    assert_eq![
+        format!["{:.1}", Oklch32::new(0.5, 0.1, 90.)],
+        "oklch(0.5 0.1 90.0)"
+    ];
+    assert_eq![
+        format!["{}", Oklab64::from_array([0.5, 0., 0.])],
+        "oklab(0.5 0 0)"
+    ];
+    assert_eq![
+        format!["{:.2}", Srgba32::new(1., 0.5, 0., 1.).display_tuple()],
+        "(1.00, 0.50, 0.00, 1.00)"
+    ];
+
+    assert_eq![c.to_hex_string(), "#ff008033"];
+    assert_eq![Srgb8::new(1, 2, 3).to_css_string(), "rgb(1 2 3)"];
+    assert_eq![
+        LinearSrgb32::new(1., 0., 0.5).to_tuple_string(),
+        "(1, 0, 0.5)"
+    ];
+
+    assert_eq![c.to_canvas_style(), "rgba(255, 0, 128, 0.200)"];
+    assert_eq![Srgba8::new(1, 2, 3, 255).to_canvas_style(), "#010203"];
+    assert_eq![Srgb8::new(1, 2, 3).to_canvas_style(), "#010203"];
+    assert_eq![
+        Srgba32::new(1., 0., 0., 0.).to_canvas_style(),
+        "rgba(255, 0, 0, 0.000)"
+    ];
+}
+
+#[test]
+fn parse_oklab_oklch() {
+    let lab: Oklab32 = "OKLAB( 0.5  none 100% )".parse().unwrap();
+    assert_eq![Oklab32::to_array(lab), [0.5, 0., 0.4]];
+    assert_eq![
+        "oklch(50% 0.2 -90deg / 0.5)"
+            .parse::<Oklch32>()
+            .map(Oklch32::to_array),
+        Ok([0.5, 0.2, 270.])
+    ];
+    assert_eq![
+        "oklch(1 0 200grad)".parse::<Oklch32>().map(|c| c.h),
+        Ok(180.)
+    ];
+    assert_eq![
+        "oklab(0.5 0.1)".parse::<Oklab32>(),
+        Err(ParseColorError::WrongComponentCount)
+    ];
+    assert_eq![
+        "oklch(0.5 0.1 x)".parse::<Oklch32>(),
+        Err(ParseColorError::InvalidComponent)
+    ];
+    assert_eq![
+        "oklch(0.5 0.1 1 / y)".parse::<Oklch32>(),
+        Err(ParseColorError::InvalidComponent)
+    ];
+    assert_eq![
+        "lab(0.5 0.1 0.1)".parse::<Oklab32>(),
+        Err(ParseColorError::InvalidSyntax)
+    ];
+}
+
+#[test]
+#[cfg(feature = "fixed")]
+fn fixed_point() {
+    for v in [0, 1, 127, 128, 254, 255] {
+        let c = Srgba8::new(v, v, v, v);
+        assert_eq![SrgbaU0F16::from_srgba8(c).to_srgba8(), c];
+    }
+    let c = SrgbU0F16::from(Srgb32::new(1., 0.5, -1.));
+    assert_eq![c.to_srgb32(), Srgb32::new(1., 0.5, 0.)];
+    assert_eq![c.to_srgb8(), Srgb8::new(255, 128, 0)];
+
+    let lab = Oklab32::from_array([0.5, -0.25, 0.125]);
+    assert_eq![OklabI16F16::from(lab).to_oklab32(), lab];
+}
+
+#[test]
+#[cfg(feature = "palette")]
+fn palette_conversions() {
+    let c = Srgba8::new(10, 20, 30, 40);
+    assert_eq![Srgba8::from(palette::Srgba::<u8>::from(c)), c];
+    let lin = LinearSrgb32::new(0.25, 0.5, 0.75);
+    let p = palette::LinSrgb::<f32>::from(lin);
+    assert_eq![[p.red, p.green, p.blue], [0.25, 0.5, 0.75]];
+    assert_eq![LinearSrgb32::from(p), lin];
+
+    let lab = Oklab32::new(0.5, -0.25, 0.125);
+    assert_eq![Oklab32::from(palette::Oklab::<f32>::from(lab)), lab];
+    let lch = palette::Oklch::<f32>::new(0.5, 0.1, -90.);
+    assert_eq![Oklch32::from(lch), Oklch32::new(0.5, 0.1, 270.)];
+}
+
+#[test]
+#[cfg(not(feature = "micromath"))]
+fn oklab8() {
+    let lab = Oklab32::from_array([0.5, -0.1, 0.2]);
+    let q = Oklab8::from(lab);
+    assert_eq![q, Oklab8::new(128, 103, 179)];
+    let back = q.to_oklab32();
+    for (x, y) in back.into_iter().zip(lab) {
+        assert![(x - y) * (x - y) < (0.51 / 255.) * (0.51 / 255.)];
+    }
+    assert_eq![
+        Oklab8::from_oklab32(Oklab32::from_array([2., -1., 1.])),
+        Oklab8::new(255, 0, 255)
+    ];
+    assert_eq![Oklab8::from_bits(q.to_bits()), q];
+    assert_eq![q.to_bits(), 0x0080_67B3];
+    assert_eq![q.squared_distance(&Oklab8::new(130, 102, 176)), 14];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    for c in [
+        Srgb8::new(0, 0, 0),
+        Srgb8::new(255, 255, 255),
+        Srgb8::new(200, 30, 90),
+    ] {
+        let d = Oklab8::from_srgb8(c).to_srgb8();
+        assert![c.zip_with(d, u8::abs_diff).iter().all(|x| x <= 2)];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn color_float_accessors() {
+    let c = Srgb8::new(255, 0, 0);
+    let lch = c.to_oklch32();
+    assert_eq![c.color_luminosity_f32(), lch.l];
+    assert_eq![c.color_hue_f32(), lch.h];
+    assert_eq![c.color_chroma_f32(), lch.c];
+    assert![c.color_hue_f32() > 25. && c.color_hue_f32() < 35.];
+    assert_eq![c.color_saturation_f32(), 1.];
+    assert_eq![Srgb32::new(0.5, 0.25, 0.5).color_saturation_f32(), 0.5];
+    assert_eq![Srgb8::new(0, 0, 0).color_saturation_f32(), 0.];
+    assert_eq![lch.color_hue_f32(), lch.h];
+    assert_eq![AnyColor::from(c).color_chroma_f32(), lch.c];
+
+    assert_eq![c.color_whiteness_f32(), 0.];
+    assert_eq![c.color_blackness_f32(), 0.];
+    let c = Srgb32::new(0.75, 0.25, 0.5);
+    assert_eq![c.color_whiteness_f32(), 0.25];
+    assert_eq![c.color_blackness_f32(), 0.25];
+    assert_eq![Srgb8::new(0, 0, 0).color_blackness_f32(), 1.];
+    assert_eq![Srgba8::new(255, 255, 255, 0).color_whiteness_f32(), 1.];
+}
+
+#[test]
+fn dyn_color() {
+    let colors: [&dyn DynColor; 3] = [
+        &Srgb8::new(1, 2, 3),
+        &Oklab32::new(0.5, 0., 0.),
+        &AnyColor::from(Srgba32::new(1., 1., 1., 0.5)),
+    ];
+    let spaces = colors.map(|c| c.color_space());
+    assert_eq![
+        spaces,
+        [ColorSpace::Srgb8, ColorSpace::Oklab32, ColorSpace::Srgba32]
+    ];
+    assert_eq![
+        colors[0].to_any_color(),
+        AnyColor::Srgb8(Srgb8::new(1, 2, 3))
+    ];
+
+    #[cfg(feature = "alloc")]
+    {
+        use alloc::{boxed::Box, vec, vec::Vec};
+        let boxed: Vec<Box<dyn DynColor>> = vec![Box::new(Srgb32::new(0., 0., 0.))];
+        assert_eq![boxed[0].color_space(), ColorSpace::Srgb32];
+    }
+}
+
+#[test]
+fn convert_by_space() {
+    assert_eq![ColorSpace::Srgba8.components(), 4];
+    assert_eq![
+        AnyColor::from_components(ColorSpace::Srgb8, &[1., 0., 0.5]),
+        Ok(AnyColor::Srgb8(Srgb8::new(255, 0, 128)))
+    ];
+    assert_eq![
+        AnyColor::from_components(ColorSpace::Oklab32, &[0.5, 0.]),
+        Err(LengthError {
+            expected: 3,
+            found: 2
+        })
+    ];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        let out = convert(
+            &[0.5, 0.5, 0.5, 0.25],
+            ColorSpace::LinearSrgba32,
+            ColorSpace::LinearSrgb32,
+        );
+        assert_eq![out, Ok([0.5, 0.5, 0.5, 1.])];
+        let lab = convert(&[1., 1., 1.], ColorSpace::Srgb32, ColorSpace::Oklab32).unwrap();
+        assert![(lab[0] - 1.) * (lab[0] - 1.) < 1e-6];
+        assert![convert(&[1.; 5], ColorSpace::Srgba32, ColorSpace::Srgb8).is_err()];
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn octree_quantizer() {
+    let mut q = OctreeQuantizer::new(4);
+    assert![q.is_empty()];
+    assert![q.palette().is_empty()];
+
+    // a gradient of every gray plus a few saturated colors
+    q.extend((0..=255).map(|v| Srgb8::new(v, v, v)));
+    q.extend([Srgb8::new(255, 0, 0); 300]);
+    assert_eq![q.len(), 4];
+    let palette = q.palette();
+    assert_eq![palette.len(), 4];
+    assert_eq![palette[0], Srgb8::new(255, 0, 0)];
+
+    for i in 0..10_000_u32 {
+        let [r, g, b, _] = i.wrapping_mul(2_654_435_761).to_le_bytes();
+        q.insert(Srgb8::new(r, g, b));
+    }
+    assert_eq![q.len(), 4];
+
+    let mut one = OctreeQuantizer::new(0);
+    one.extend([Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255)]);
+    assert_eq![one.palette(), [Srgb8::new(128, 128, 128)]];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn kmeans_dominant_colors() {
+    use alloc::vec::Vec;
+
+    assert![kmeans_oklab(&[], 3, 8).is_empty()];
+
+    let (red, green, blue) = (
+        Srgb8::new(220, 20, 20),
+        Srgb8::new(20, 200, 20),
+        Srgb8::new(20, 20, 220),
+    );
+    let mut pixels = Vec::new();
+    for (c, n) in [(red, 50), (green, 30), (blue, 20)] {
+        for i in 0..n {
+            pixels.push(Srgb8::new(c.r, c.g, c.b.wrapping_add(i % 3)));
+        }
+    }
+    let colors = kmeans_oklab(&pixels, 3, 32);
+    assert_eq![colors.len(), 3];
+    let weights: Vec<f32> = colors.iter().map(|c| c.weight).collect();
+    assert_eq![weights, [0.5, 0.3, 0.2]];
+    for (c, expected) in colors.iter().zip([red, green, blue]) {
+        assert![c.color.r.abs_diff(expected.r) <= 2 && c.color.g.abs_diff(expected.g) <= 2];
+        assert![c.color.b.abs_diff(expected.b) <= 2, "{:?}", c.color];
+    }
+
+    // fewer distinct colors than clusters
+    let colors = kmeans_oklab(&[red, red, blue], 5, 8);
+    assert_eq![colors.len(), 2];
+    assert_eq![colors[0].color, red];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn dither_floyd_steinberg() {
+    let palette = [Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255)];
+
+    // exact palette colors are kept
+    let image = [palette[1], palette[0], palette[0], palette[1]];
+    assert_eq![floyd_steinberg(&image, 2, &palette), [1, 0, 0, 1]];
+
+    // a mid gray averages to half the light intensity in linear space
+    let gray = Srgb8::new(188, 188, 188);
+    let image = [gray; 64];
+    let indices = floyd_steinberg_with(&image, 8, &palette, DitherSpace::Linear);
+    assert_eq![indices.len(), 64];
+    let whites = indices.iter().filter(|i| **i == 1).count();
+    assert![(30..=34).contains(&whites), "{whites}"];
+
+    // a partial last row
+    assert_eq![floyd_steinberg(&image[..5], 4, &palette).len(), 5];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn average_colors() {
+    assert![average_color::<Srgb8>(&[]).is_none()];
+    assert![average_color_oklab::<Srgb8>(&[]).is_none()];
+
+    // averaging in linear light, not encoded sRGB
+    let bw = [Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255)];
+    let avg = average_color(&bw).unwrap();
+    assert_eq![avg, LinearSrgba32::new(0.5, 0.5, 0.5, 1.)];
+    assert_eq![avg.to_srgb8(), Srgb8::new(188, 188, 188)];
+
+    // transparent colors don't contribute
+    let colors = [Srgba8::new(255, 0, 0, 255), Srgba8::new(0, 0, 255, 0)];
+    let avg = average_color(&colors).unwrap();
+    assert_eq![avg, LinearSrgba32::new(1., 0., 0., 0.5)];
+    let lab = average_color_oklab(&colors).unwrap();
+    assert_eq![lab, Srgb8::new(255, 0, 0).to_oklab32()];
+
+    let avg = weighted_average_color(&bw, &[3., 1.]).unwrap();
+    assert_eq![avg.to_srgba8(), Srgba8::new(137, 137, 137, 255)];
+    assert![weighted_average_color(&bw, &[0., 0.]).is_none()];
+    let lab = weighted_average_color_oklab(&bw, &[1., 1.]).unwrap();
+    assert![(lab.l - 0.5).abs() < 1e-4 && lab.a.abs() < 1e-4];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn image_buf() {
+    use alloc::vec;
+
+    assert_eq![
+        ImageBuf::new(2, 2, vec![0_u8; 3]),
+        Err(LengthError {
+            expected: 4,
+            found: 3
+        })
+    ];
+    let mut img = ImageBuf::new(3, 2, vec![0_u8, 1, 2, 3, 4, 5]).unwrap();
+    assert_eq![(img.width(), img.height()), (3, 2)];
+    assert_eq![img.get(2, 1), Some(&5)];
+    assert_eq![img.get(3, 0), None];
+    *img.get_mut(0, 1).unwrap() = 9;
+    assert_eq![img.rows().nth(1), Some(&[9, 4, 5][..])];
+    for row in img.rows_mut() {
+        row.reverse();
+    }
+    assert_eq![img.pixels(), [2, 1, 0, 5, 4, 9]];
+
+    img.map_in_place(|v| v * 2);
+    let img = img.map(|v| Srgb8::new(v, v, v));
+    assert_eq![img.into_pixels()[1], Srgb8::new(2, 2, 2)];
+
+    let img = ImageBuf::filled(2, 3, Srgb8::new(1, 2, 3));
+    assert_eq![img.rows().len(), 3];
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        let linear: ImageBuf<LinearSrgb32> = img.convert();
+        assert_eq![linear.pixels()[5], Srgb8::new(1, 2, 3).to_linear_srgb32()];
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn indexed_image() {
+    use alloc::vec;
+
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    assert![Palette::new(vec![black; 257]).is_err()];
+    let palette = Palette::new(vec![black, white]).unwrap();
+    assert_eq![palette.get(1), Some(white)];
+    assert_eq![palette.get(2), None];
+
+    assert![IndexedImage::new(2, 2, palette.clone(), vec![0; 3]).is_err()];
+    let indexed = IndexedImage::new(2, 2, palette, vec![0, 1, 1, 5]).unwrap();
+    let img = indexed.decode();
+    assert_eq![img.pixels(), [black, white, white, black]];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        let indexed = IndexedImage::encode(&img, 8);
+        assert_eq![indexed.palette().len(), 2];
+        assert_eq![indexed.decode(), img];
+
+        let empty = IndexedImage::encode(&ImageBuf::new(0, 0, vec![]).unwrap(), 8);
+        assert![empty.palette().is_empty() && empty.indices().is_empty()];
+    }
+}
+
+#[test]
+#[cfg(not(feature = "micromath"))]
+fn parse_predefined_color() {
+    let c: PredefinedColor = "color(display-p3 1 50% none / 0.5)".parse().unwrap();
+    assert_eq![c.space, PredefinedSpace::DisplayP3];
+    assert_eq![c.components, [1., 0.5, 0.]];
+    assert_eq![c.alpha, 0.5];
+    assert_eq![
+        "COLOR(xyz 0 0 0)".parse::<PredefinedColor>().unwrap().space,
+        PredefinedSpace::XyzD65
+    ];
+    assert_eq![
+        "color(cmyk 0 0 0)".parse::<PredefinedColor>(),
+        Err(ParseColorError::InvalidSyntax)
+    ];
+    assert_eq![
+        "color(srgb 0 0)".parse::<PredefinedColor>(),
+        Err(ParseColorError::WrongComponentCount)
+    ];
+    for space in PredefinedSpace::ALL {
+        assert_eq![PredefinedSpace::from_name(space.name()), Some(space)];
+    }
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        assert_eq![
+            "color(srgb 1 0.5 0)".parse::<Srgb32>(),
+            Ok(Srgb32::new(1., 0.5, 0.))
+        ];
+        let white = LinearSrgb32::new(1., 1., 1.);
+        let close = |a: LinearSrgb32, b: LinearSrgb32| {
+            let d = [a.r - b.r, a.g - b.g, a.b - b.b];
+            d.iter().all(|d| d.abs() < 1e-3)
+        };
+        for space in PredefinedSpace::ALL {
+            #[cfg(feature = "alloc")]
+            {
+                use alloc::string::ToString;
+                let c = PredefinedColor::from_linear_srgba32(space, white.to_linear_srgba32(0.5));
+                let parsed: LinearSrgba32 = c.to_string().parse().unwrap();
+                assert![close(parsed.to_linear_srgb32(), white), "{space:?}"];
+                assert_eq![parsed.a, 0.5];
+            }
+
+            // round trip of an arbitrary color through each space
+            let c = LinearSrgb32::new(0.2, 0.7, 0.05);
+            let p = PredefinedColor::from_linear_srgba32(space, c.to_linear_srgba32(1.));
+            assert![
+                close(p.to_linear_srgba32().to_linear_srgb32(), c),
+                "{space:?}"
+            ];
+        }
+        // white is 1 in all the rgb spaces
+        let rec2020 = PredefinedColor::from_linear_srgba32(
+            PredefinedSpace::Rec2020,
+            white.to_linear_srgba32(1.),
+        );
+        assert![
+            rec2020.components.iter().all(|c| (c - 1.).abs() < 1e-3),
+            "{rec2020}"
+        ];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn lighting_presets() {
+    let [x, y] = kelvin_to_xy(6504.);
+    assert![(x - 0.3135).abs() < 1e-3 && (y - 0.3236).abs() < 1e-3];
+
+    let mut last_blue = 0.;
+    for preset in Lighting::ALL {
+        let w = preset.white_point();
+        assert![w.r.max(w.g).max(w.b) == 1.];
+        assert![w.b >= last_blue, "{preset:?}"];
+        last_blue = w.b;
+    }
+    assert_eq![
+        Lighting::DaylightD65.white_point(),
+        LinearSrgb32::new(1., 1., 1.)
+    ];
+    let shade = Lighting::Shade.white_point();
+    assert![shade.b == 1. && shade.r < 1.];
+    assert_eq![
+        LinearSrgb32::from_kelvin(100.),
+        LinearSrgb32::from_kelvin(KELVIN_MIN)
+    ];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn tonal_palette() {
+    // a vivid color out of the sRGB gamut is mapped into it
+    let c = Oklch32 {
+        l: 0.6,
+        c: 0.4,
+        h: 145.,
+    }
+    .gamut_map_srgb();
+    assert![c.c < 0.4 && c.c > 0.1 && c.l == 0.6 && c.h == 145.];
+    let rgb = c.to_linear_srgb32();
+    assert![[rgb.r, rgb.g, rgb.b]
+        .iter()
+        .all(|v| *v > -1e-3 && *v < 1. + 1e-3)];
+    let gray = Oklch32 {
+        l: 0.5,
+        c: 0.,
+        h: 0.,
+    };
+    assert_eq![gray.gamut_map_srgb(), gray];
+
+    // shifting the hue keeps the lightness and the color in the gamut
+    let green = Srgb8::new(0, 255, 0).to_oklch32();
+    for degrees in [60., 120., 180., 240., -90.] {
+        let c = green.shift_hue_preserving_lightness(degrees);
+        assert![c.l == green.l && c.c <= green.c];
+        assert![(c.h - Hue::new(green.h + degrees).degrees()).abs() < 1e-3];
+        let rgb = c.to_linear_srgb32();
+        assert![[rgb.r, rgb.g, rgb.b]
+            .iter()
+            .all(|v| *v > -1e-3 && *v < 1. + 1e-3)];
+    }
+
+    let seed = Srgb8::new(0, 100, 200);
+    let palette = TonalPalette::from_seed(seed);
+    let lightness: [f32; 13] = TonalPalette::TONES.map(|t| palette.tone_oklch32(t).l);
+    assert![lightness.windows(2).all(|w| w[0] < w[1])];
+    assert_eq![palette.tone(0), Srgb8::new(0, 0, 0)];
+    assert_eq![palette.tone(200), Srgb8::new(255, 255, 255)];
+    let mid = palette.tone(50).to_oklch32();
+    assert![(mid.h - seed.to_oklch32().h).abs() < 1.];
+}
+
+#[test]
+#[cfg(feature = "themes")]
+fn theme_palettes() {
+    use crate::themes;
+
+    for (name, palette) in &themes::THEMES {
+        assert_eq![themes::by_name(name), Some(palette)];
+        assert![!palette.is_empty()];
+    }
+    assert_eq![themes::by_name("nord"), Some(&themes::NORD)];
+    assert_eq![themes::by_name("unknown"), None];
+    assert_eq![
+        themes::SOLARIZED.get(15),
+        Some(Srgb8::new(0x85, 0x99, 0x00))
+    ];
+    assert_eq![themes::DRACULA.len(), 11];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn theme_from_seed() {
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    assert_eq![relative_luminance(white), 1.];
+    assert_eq![wcag_contrast(black, black), 1.];
+    assert_eq![wcag_contrast(white, black), wcag_contrast(black, white)];
+
+    for seed in [
+        Srgb8::new(30, 140, 200),
+        Srgb8::new(250, 230, 40),
+        Srgb8::new(128, 128, 128),
+    ] {
+        for mode in [ThemeMode::Light, ThemeMode::Dark] {
+            let t = Theme::from_seed(seed, mode);
+            let bg = t.background;
+            assert![wcag_contrast(t.text, bg) >= WCAG_AAA, "{seed:?} {mode:?}"];
+            assert![wcag_contrast(t.primary, bg) >= WCAG_AA, "{seed:?} {mode:?}"];
+            assert![wcag_contrast(t.error, bg) >= WCAG_AA, "{seed:?} {mode:?}"];
+            assert![
+                wcag_contrast(t.accent, bg) >= WCAG_AA_LARGE,
+                "{seed:?} {mode:?}"
+            ];
+            let (lb, ls) = (relative_luminance(bg), relative_luminance(t.surface));
+            match mode {
+                ThemeMode::Light => assert![lb > 0.8 && ls < lb],
+                ThemeMode::Dark => assert![lb < 0.05 && ls > lb],
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn theme_dark_palette() {
+    use alloc::vec;
+    let light = Palette::new(vec![
+        Srgb8::new(30, 100, 200),
+        Srgb8::new(248, 246, 240),
+        Srgb8::new(40, 40, 40),
+        Srgb8::new(200, 40, 40),
+    ])
+    .unwrap();
+    let dark = dark_palette(&light);
+    assert_eq![dark.len(), light.len()];
+
+    let (light_bg, dark_bg) = (light.get(1).unwrap(), dark.get(1).unwrap());
+    assert![relative_luminance(dark_bg) < 0.05];
+    for i in [0, 2, 3] {
+        let (l, d) = (light.get(i).unwrap(), dark.get(i).unwrap());
+        assert![wcag_contrast(d, dark_bg) >= wcag_contrast(l, light_bg) - 1e-3];
+        if i == 2 {
+            continue; // gray, without a meaningful hue
+        }
+        let (lh, dh) = (l.to_oklch32().h, d.to_oklch32().h);
+        let diff = (lh - dh).abs();
+        assert![diff.min(360. - diff) < 10., "{i}: {lh} {dh}"];
+    }
+    assert![dark_palette(&Palette::default()).is_empty()];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn theme_accessible_palette() {
+    let (bg, seed) = (Srgb8::new(20, 22, 30), Srgb8::new(40, 60, 140));
+    let palette = AccessiblePalette::new()
+        .fixed_role(bg)
+        .role(seed)
+        .role(Srgb8::new(160, 160, 170))
+        .contrast(1, 0, MinContrast::Wcag(WCAG_AA))
+        .contrast(2, 0, MinContrast::Apca(75.))
+        .contrast(2, 1, MinContrast::Wcag(WCAG_AA_LARGE))
+        .solve()
+        .unwrap();
+    assert_eq![palette.len(), 3];
+    assert_eq![palette[0], bg];
+    assert![wcag_contrast(palette[1], bg) >= WCAG_AA];
+    assert![MinContrast::Apca(75.).is_met_by(palette[2], bg)];
+    assert![wcag_contrast(palette[2], palette[1]) >= WCAG_AA_LARGE];
+    // lightened just enough, keeping the hue
+    assert![wcag_contrast(palette[1], bg) < WCAG_AA + 0.5];
+    let (sh, ph) = (seed.to_oklch32().h, palette[1].to_oklch32().h);
+    assert![(sh - ph).abs() < 10.];
+
+    let gray = Srgb8::new(128, 128, 128);
+    let roles = AccessiblePalette::new().fixed_role(gray).role(gray);
+    assert_eq![
+        roles.clone().contrast(0, 2, MinContrast::Wcag(2.)).solve(),
+        Err(AccessiblePaletteError::InvalidRole(0))
+    ];
+    assert_eq![
+        roles.contrast(0, 1, MinContrast::Wcag(WCAG_AAA)).solve(),
+        Err(AccessiblePaletteError::Unsatisfiable(0))
+    ];
+    assert![AccessiblePalette::new().solve().unwrap().is_empty()];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn cvd_daltonize() {
+    let (red, green) = (Srgb8::new(200, 60, 50), Srgb8::new(90, 140, 40));
+    let gray = Srgb8::new(120, 120, 120);
+    for d in [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+        assert_eq![d.simulate(red, 0.), red];
+        assert_eq![d.daltonize(red, 0.), red];
+        assert![d.simulate(gray, 1.).approx_eq(&gray, 1)];
+        assert![d.daltonize(gray, 1.).approx_eq(&gray, 1)];
+    }
+    for d in [Deficiency::Protan, Deficiency::Deutan] {
+        let seen = |c| d.simulate(c, 1.);
+        let before = EuclidOklab.difference(seen(red), seen(green));
+        let after =
+            EuclidOklab.difference(seen(d.daltonize(red, 1.)), seen(d.daltonize(green, 1.)));
+        assert![after > before * 1.3, "{d:?}: {before} {after}"];
+        let half = EuclidOklab.difference(red, d.simulate(red, 0.5));
+        assert![half > 0. && half < EuclidOklab.difference(red, seen(red))];
+    }
+
+    let translucent = Srgba8::new(200, 60, 50, 128);
+    assert_eq![Deficiency::Deutan.daltonize(translucent, 1.).a, 128];
+    let mut colors = [red, green];
+    Deficiency::Deutan.daltonize_in_place(&mut colors, 1.);
+    assert_eq![colors[1], Deficiency::Deutan.daltonize(green, 1.)];
+    let mut bytes = [red.b, red.g, red.r, 7];
+    Deficiency::Deutan.daltonize_bytes_in_place(&mut bytes, ByteLayout::Bgra, 1.);
+    let c = Deficiency::Deutan.daltonize(red, 1.);
+    assert_eq![bytes, [c.b, c.g, c.r, 7]];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn cvd_safe_palettes() {
+    use alloc::vec;
+    let colors = vec![
+        Srgb8::new(200, 60, 50),
+        Srgb8::new(90, 140, 40),
+        Srgb8::new(150, 110, 40),
+        Srgb8::new(60, 90, 200),
+        Srgb8::new(120, 60, 200),
+    ];
+    let safe = cvd_safe_palette(&Palette::new(colors.clone()).unwrap(), 0.06).unwrap();
+    assert_eq![safe.len(), colors.len()];
+    for d in [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+        for i in 0..5 {
+            for j in i + 1..5 {
+                let (a, b) = (safe.colors()[i], safe.colors()[j]);
+                let delta = EuclidOklab.difference(d.simulate(a, 1.), d.simulate(b, 1.));
+                assert![delta >= 0.06 - 1e-3, "{d:?} {i} {j}: {delta}"];
+            }
+        }
+    }
+    for (a, b) in colors.iter().zip(safe.colors()) {
+        assert![EuclidOklab.difference(*a, *b) < 0.15];
+    }
+
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    let distinct = Palette::new(vec![black, white]).unwrap();
+    assert_eq![cvd_safe_palette(&distinct, 0.5), Some(distinct)];
+    let grays = Palette::new(vec![black, Srgb8::new(128, 128, 128), white]).unwrap();
+    assert![cvd_safe_palette(&grays, 0.9).is_none()];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn contrast_ramp_steps() {
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    assert_eq![apca_contrast(black, black), 0.];
+    assert![apca_contrast(Srgb8::new(120, 120, 120), white) > 60.];
+    assert![MinContrast::Apca(100.).is_met_by(black, white)];
+    assert![!MinContrast::Wcag(21.5).is_met_by(black, white)];
+
+    let ramp: [Srgb8; 7] = contrast_ramp(white, black, MinContrast::Wcag(1.5), None).unwrap();
+    let ratios: [f32; 6] = core::array::from_fn(|i| wcag_contrast(ramp[i], ramp[i + 1]));
+    assert![
+        ratios.iter().all(|r| (r - ratios[0]).abs() < 0.25),
+        "{ratios:?}"
+    ];
+
+    let ramp = contrast_ramp::<4>(
+        Srgb8::new(250, 200, 60),
+        Srgb8::new(120, 20, 60),
+        MinContrast::Apca(10.),
+        Some((white, MinContrast::Wcag(1.3))),
+    );
+    assert![ramp.is_ok()];
+    assert_eq![
+        contrast_ramp::<3>(white, white, MinContrast::Wcag(1.1), None),
+        Err(ContrastRampError::Adjacent(0))
+    ];
+    assert_eq![
+        contrast_ramp::<3>(
+            black,
+            white,
+            MinContrast::Wcag(1.1),
+            Some((black, MinContrast::Wcag(2.)))
+        ),
+        Err(ContrastRampError::Background(0))
+    ];
+    assert_eq![
+        contrast_ramp::<0>(black, white, MinContrast::Wcag(1.), None),
+        Ok([])
+    ];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn oklch_max_srgb_chroma() {
+    // the color at the max chroma must be on the gamut boundary
+    let on_boundary = |l, c, h| {
+        let rgb = Oklch32 { l, c, h }.to_linear_srgb32();
+        let (min, max) = (rgb.r.min(rgb.g).min(rgb.b), rgb.r.max(rgb.g).max(rgb.b));
+        min > -1e-3 && max < 1. + 1e-3 && (min.abs() < 1e-3 || (max - 1.).abs() < 1e-3)
+    };
+    for h in (0..360).step_by(5) {
+        let h = h as f32;
+        let cusp = Oklch32::srgb_cusp(h);
+        assert![(cusp.c - Oklch32::max_srgb_chroma(cusp.l, h)).abs() < 1e-3];
+        for l in 1..20 {
+            let l = l as f32 / 20.;
+            let c = Oklch32::max_srgb_chroma(l, h);
+            assert![on_boundary(l, c, h), "l {l} h {h}: {c}"];
+        }
+    }
+    assert_eq![Oklch32::max_srgb_chroma(0., 100.), 0.];
+    assert_eq![Oklch32::max_srgb_chroma(1., 100.), 0.];
+}
+
+#[test]
+fn approx_eq_colors() {
+    let c = Srgb32::new(0.2, 0.4, 0.6);
+    assert![Oklch32::new(0.5, 0.1, 359.9).approx_eq(&Oklch32::new(0.5, 0.1, 0.1), 0.5)];
+    assert![Oklch64::from_array([0.5, 0.1, -10.])
+        .approx_eq(&Oklch64::from_array([0.5, 0.1, 350.]), 1e-9)];
+    assert![c.approx_eq(&Srgb32::new(0.2005, 0.3995, 0.6), 1e-3)];
+    assert![!c.approx_eq(&Srgb32::new(0.2, 0.4, 0.602), 1e-3)];
+    assert![!Srgba32::new(0.1, 0.1, 0.1, 0.5).approx_eq(&Srgba32::new(0.1, 0.1, 0.1, 0.6), 0.05)];
+    assert![!Oklch32 {
+        l: 0.5,
+        c: 0.1,
+        h: f32::NAN
+    }
+    .approx_eq(
+        &Oklch32 {
+            l: 0.5,
+            c: 0.1,
+            h: f32::NAN
+        },
+        1.
+    )];
+    assert![Oklab64 {
+        l: 0.5,
+        a: 0.1,
+        b: -0.1
+    }
+    .approx_eq(
+        &Oklab64 {
+            l: 0.5,
+            a: 0.1,
+            b: -0.1
+        },
+        0.
+    )];
+}
+
+#[test]
+fn oklab_clamp_policies() {
+    assert_eq![
+        Oklab32::new_unchecked(1.5, -0.7, 0.9),
+        Oklab32 {
+            l: 1.5,
+            a: -0.7,
+            b: 0.9
+        }
+    ];
+    assert_eq![
+        Oklab32::new_with_policy(-1., -0.7, 0.9, ClampPolicy::WrapHue),
+        Oklab32::new(-1., -0.7, 0.9)
+    ];
+    assert_eq![
+        Oklch32::new_with_policy(0.5, 0.7, 720., ClampPolicy::WrapHue),
+        Oklch32 {
+            l: 0.5,
+            c: 0.5,
+            h: 0.
+        }
+    ];
+    assert_eq![
+        Oklch32::new_with_policy(0.5, 0.2, -1e-9, ClampPolicy::WrapHue).h,
+        0.
+    ];
+    assert_eq![
+        Oklch32::new_with_policy(0.5, 0.7, 480., ClampPolicy::Unchecked),
+        Oklch32::new_unchecked(0.5, 0.7, 480.)
+    ];
+    assert_eq![
+        Oklch32::new_with_policy(0.5, 0.7, 480., ClampPolicy::Clamp).h,
+        360.
+    ];
+}
+
+#[test]
+fn oklab_lightness_range() {
+    assert_eq![Oklab32::L_MAX, 1.];
+    assert_eq![Oklch32::L_MAX, 1.];
+    assert_eq![Oklab32::new(2., 0., 0.).l, Oklab32::L_MAX];
+    assert_eq![Oklch32::new(50., 0., 0.).l, Oklch32::L_MAX];
+    assert_eq![
+        Oklab32::from_percent(50., 0.1, 0.),
+        Oklab32::new(0.5, 0.1, 0.)
+    ];
+    assert_eq![Oklch32::from_percent(120., 0.1, 90.).l, 1.];
+}
+
+#[test]
+fn hue_wrapping() {
+    assert_eq![Hue::new(720.), Hue::ZERO];
+    assert_eq![Hue::new(-1e-9).degrees(), 0.];
+    assert_eq![(Hue::new(300.) + Hue::new(100.)).degrees(), 40.];
+    assert_eq![(Hue::new(10.) - 30.).degrees(), 340.];
+    let mut h = Hue::new(90.);
+    h -= 180.;
+    assert_eq![h.degrees(), 270.];
+    h += 100.;
+    assert_eq![h.degrees(), 10.];
+
+    assert_eq![Hue::new(10.).diff(Hue::new(350.)), -20.];
+    assert_eq![Hue::new(0.).diff(Hue::new(180.)), 180.];
+    assert_eq![Hue::new(180.).diff(Hue::new(0.)), 180.];
+    assert_eq![Hue::new(350.).lerp(Hue::new(30.), 0.5).degrees(), 10.];
+    assert![(Hue::from_radians(core::f32::consts::PI).degrees() - 180.).abs() < 1e-4];
+    assert![(Hue::new(90.).radians() - core::f32::consts::FRAC_PI_2).abs() < 1e-6];
+
+    let c = Oklch32::new_unchecked(0.5, 0.1, 400.);
+    assert_eq![c.hue().degrees(), 40.];
+    assert_eq![c.rotate_hue(-50.).h, 350.];
+
+    assert_eq![Oklch32::new(0.5, 0.1, 360.).h, 0.];
+    assert_eq![Oklch32::new(0.5, 0.1, 480.).h, 120.];
+    assert_eq![Oklch32::new(0.5, 0.1, -90.).h, 270.];
+    assert_eq![Oklch32::from_percent(50., 0.1, 840.).h, 120.];
+}
+
+#[test]
+fn saturating_u8_arithmetic() {
+    let c = Srgb8::new(200, 100, 10);
+    assert_eq![
+        c.saturating_add(Srgb8::new(100, 100, 100)),
+        Srgb8::new(255, 200, 110)
+    ];
+    assert_eq![
+        c.saturating_sub(Srgb8::new(100, 100, 100)),
+        Srgb8::new(100, 0, 0)
+    ];
+    assert_eq![c.saturating_add(c), c + c];
+    assert_eq![c.scale(0.5), Srgb8::new(100, 50, 5)];
+    assert_eq![c.scale(2.), Srgb8::new(255, 200, 20)];
+    assert_eq![c.scale(-1.), Srgb8::new(0, 0, 0)];
+    assert_eq![c.scale(f32::NAN), Srgb8::new(0, 0, 0)];
+    assert_eq![
+        Srgba8::new(10, 20, 30, 255).scale(0.25),
+        Srgba8::new(3, 5, 8, 64)
+    ];
+}
+
+#[test]
+fn bit_depth_scaling() {
+    for v in 0..=255_u8 {
+        assert_eq![scale_u10_to_u8(scale_u8_to_u10(v)), v];
+        assert_eq![scale_u12_to_u8(scale_u8_to_u12(v)), v];
+        assert_eq![scale_u16_to_u8(scale_u8_to_u16(v)), v];
+        assert_eq![scale_u8_to_u16(v), v as u16 * 257];
+        assert_eq![f32_to_unorm(unorm_to_f32(v as u16, 8), 8), v as u16];
+    }
+    for v in 0..=1023_u16 {
+        assert_eq![scale_u12_to_u10(scale_u10_to_u12(v)), v];
+        assert_eq![scale_u16_to_u10(scale_u10_to_u16(v)), v];
+    }
+    assert_eq![scale_u16_to_u12(u16::MAX), 4095];
+    assert_eq![scale_u12_to_u16(4095), u16::MAX];
+    assert_eq![scale_u10_to_u8(2000), 255];
+    assert_eq![scale_bits(1, 1, 16), u16::MAX];
+    assert_eq![f32_to_unorm(2., 10), 1023];
+    assert_eq![f32_to_unorm(-1., 10), 0];
+    assert_eq![f32_to_unorm(f32::NAN, 10), 0];
+    assert_eq![unorm_to_f32(5000, 12), 1.];
+}
+
+#[test]
+fn rounding_modes() {
+    use Rounding::*;
+    // 0.5 / 255 and 1.5 / 255 lie exactly halfway, 0.3 / 255 doesn't
+    let [half, one_half, low] = [0.5_f32, 1.5, 0.3].map(|v| v / 255.);
+    assert_eq![f32_to_unorm_with(half, 8, NearestEven), 0];
+    assert_eq![f32_to_unorm_with(one_half, 8, NearestEven), 2];
+    assert_eq![f32_to_unorm_with(half, 8, NearestAway), 1];
+    assert_eq![f32_to_unorm_with(one_half, 8, Floor), 1];
+    assert_eq![f32_to_unorm_with(low, 8, Uniform), 0];
+    assert_eq![f32_to_unorm_with(1., 8, Uniform), 255];
+    assert_eq![f32_to_unorm_with(low, 8, Stochastic(0)), 1];
+    assert_eq![f32_to_unorm_with(low, 8, Stochastic(u32::MAX)), 0];
+    assert_eq![f32_to_unorm_with(1., 10, Stochastic(0)), 1023];
+    for mode in [Uniform, NearestEven, NearestAway, Floor, Stochastic(7)] {
+        assert_eq![f32_to_unorm_with(f32::NAN, 16, mode), 0];
+        assert_eq![f32_to_unorm_with(-1., 16, mode), 0];
+        assert_eq![f32_to_unorm_with(2., 16, mode), u16::MAX];
+    }
+    // the uniform mode matches the `From` impls
+    for v in 0..=1000 {
+        let f = v as f32 / 1000.;
+        assert_eq![
+            f32_to_unorm_with(f, 8, Uniform) as u8,
+            Srgb8::from_srgb32(Srgb32::new(f, f, f)).r
+        ];
+    }
+
+    #[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+    {
+        let linear = [LinearSrgb32::new(0.5, 0.5, 0.5)];
+        let config = ColorConfig::DEFAULT;
+        assert_eq![
+            linear_slice_to_srgb8_with(&linear, config),
+            linear_slice_to_srgb8(&linear)
+        ];
+        let floor = linear_slice_to_srgb8_with(&linear, config.with_rounding(Floor));
+        let nearest = linear_slice_to_srgb8_with(&linear, config.with_rounding(NearestEven));
+        assert_eq![(floor[0].r, nearest[0].r), (187, 188)];
+    }
+}
+
+#[test]
+fn dithered_quantization() {
+    // a simple deterministic uniform noise
+    let mut state = 0x2545_f491_u32;
+    let mut noise = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        (state >> 8) as f32 / (1 << 24) as f32
+    };
+    // a value a quarter of a step above level 100, which would always quantize
+    // to 100, averages to 100.25 over many samples, like every other value
+    let c = Srgb32::new(100.25 / 255., 0.5, 0.9);
+    assert_eq![c.to_srgb8().r, 100];
+    let n = 4096;
+    let mut sum = [0_u32; 3];
+    for _ in 0..n {
+        let d = c.to_srgb8_dithered(&mut noise);
+        sum[0] += d.r as u32;
+        sum[1] += d.g as u32;
+        sum[2] += d.b as u32;
+        assert![(99..=101).contains(&d.r)];
+    }
+    for (sum, v) in sum.iter().zip([c.r, c.g, c.b]) {
+        let avg = *sum as f32 / n as f32;
+        assert![(avg - v * 255.).abs() < 0.05, "{avg} {}", v * 255.];
+    }
+
+    let c = Srgba32::new(0.2, 0.4, 0.6, 0.8);
+    assert_eq![c.to_srgba8_dithered(|| 0.5), c.to_srgba8()];
+}
+
+#[test]
+fn round_trip_u8_f32() {
+    for n in 0..=255_u8 {
+        let c = Srgb8::new(n, n, 255 - n);
+        assert_eq![c.to_srgb32().to_srgb8(), c];
+        assert_eq![
+            Srgba8::new(n, 0, n, n).to_srgba32().to_srgba8(),
+            Srgba8::new(n, 0, n, n)
+        ];
+    }
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn round_trip_u8_linear_exact() {
+    for n in 0..=255_u8 {
+        let c = Srgb8::new(n, n, 255 - n);
+        assert_eq![c.to_linear_srgb32().to_srgb8_exact(), c];
+        let c = Srgba8::new(n, 255 - n, n, n);
+        assert_eq![c.to_linear_srgba32().to_srgba8_exact(), c];
+        for tf in [TransferFunction::GAMMA_22, TransferFunction::Rec709] {
+            assert_eq![tf.encode8_exact(tf.decode8(n)), n];
+        }
+    }
+    let tf = TransferFunction::SrgbPiecewise;
+    assert_eq![tf.encode8_exact(-1.), 0];
+    assert_eq![tf.encode8_exact(2.), 255];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn mix_in_spaces() {
+    let (a, b) = (Srgb32::new(1., 0., 0.), Srgb32::new(0., 0., 1.));
+    for space in [
+        MixSpace::LinearSrgb,
+        MixSpace::Srgb,
+        MixSpace::Oklab,
+        MixSpace::Oklch(HueMode::Shorter),
+    ] {
+        assert![a.mix_in(space, &b, 0.).approx_eq(&a, 1e-4), "{space:?}"];
+        assert![a.mix_in(space, &b, 1.).approx_eq(&b, 1e-4), "{space:?}"];
+    }
+    assert![a
+        .mix_in(MixSpace::Srgb, &b, 0.5)
+        .approx_eq(&Srgb32::new(0.5, 0., 0.5), 1e-4)];
+
+    // hue paths
+    assert_eq![HueMode::Shorter.lerp(350., 30., 0.5), 10.];
+    assert_eq![HueMode::Longer.lerp(350., 30., 0.5), 190.];
+    assert_eq![HueMode::Increasing.lerp(30., 350., 0.5), 190.];
+    assert_eq![HueMode::Decreasing.lerp(30., 350., 0.5), 10.];
+    assert_eq![HueMode::Decreasing.lerp(350., 30., 0.5), 190.];
+
+    // an achromatic color takes the hue of the other
+    let white = Oklch32::new_unchecked(1., 0., 0.);
+    let blue = Oklch32::new(0.5, 0.15, 250.);
+    let mid = white.mix_in(MixSpace::Oklch(HueMode::Shorter), &blue, 0.5);
+    assert![(mid.h - 250.).abs() < 0.5, "{mid:?}"];
+
+    let (t0, t1) = (Srgba8::new(0, 0, 0, 0), Srgba8::new(255, 255, 255, 255));
+    assert_eq![t0.mix_in(MixSpace::Oklab, &t1, 0.5).a, 128];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn gradient_builder() {
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    let red = Srgb8::new(255, 0, 0);
+    let g = Gradient::builder()
+        .space(MixSpace::Srgb)
+        .stop(0., black)
+        .stop(0.5, red)
+        .stop(0.5, white)
+        .stop(1., black)
+        .build()
+        .unwrap();
+    assert_eq![g.sample::<Srgb8>(-1.), black];
+    assert_eq![g.sample::<Srgb8>(2.), black];
+    assert![g.sample::<Srgb8>(0.25).approx_eq(&Srgb8::new(128, 0, 0), 1)];
+    assert_eq![g.sample::<Srgb8>(0.5), white];
+    assert_eq![g.sample::<Srgb8>(0.4999), red];
+    let colors: alloc::vec::Vec<Srgb8> = g.colors(3);
+    assert_eq![colors.len(), 3];
+    assert_eq![(colors[0], colors[2]), (black, black)];
+
+    let g = Gradient::builder()
+        .space(MixSpace::Oklch(HueMode::Shorter))
+        .hue(HueMode::Longer)
+        .ease(Ease::SmoothStep)
+        .stop(0., red)
+        .build()
+        .unwrap();
+    assert_eq![g.space(), MixSpace::Oklch(HueMode::Longer)];
+    assert_eq![g.ease(), Ease::SmoothStep];
+    assert_eq![g.colors::<Srgb8>(2), [red, red]];
+    assert_eq![
+        Gradient::builder()
+            .hue(HueMode::Longer)
+            .stop(0., red)
+            .build()
+            .unwrap()
+            .space(),
+        MixSpace::Oklab
+    ];
+
+    assert_eq![Gradient::builder().build(), Err(GradientError::NoStops)];
+    let err = Gradient::builder().stop(0.5, red).stop(0.2, red).build();
+    assert_eq![err, Err(GradientError::DecreasingPosition(1))];
+    let err = Gradient::builder().stop(f32::NAN, red).build();
+    assert_eq![err, Err(GradientError::InvalidPosition(0))];
+
+    assert_eq![Ease::EaseIn.apply(0.5), 0.25];
+    assert_eq![Ease::EaseOut.apply(0.5), 0.75];
+    assert_eq![Ease::SmoothStep.apply(0.5), 0.5];
+    assert_eq![Ease::Linear.apply(1.5), 1.];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn gradient_steps() {
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    // most of the change happens at the end of the gradient
+    let g = Gradient::builder()
+        .stop(0., white)
+        .stop(0.9, Srgb8::new(250, 250, 250))
+        .stop(1., black)
+        .build()
+        .unwrap();
+    let evenly: alloc::vec::Vec<Oklab32> = g.colors(6);
+    assert![evenly[0].squared_distance(&evenly[1]) < 0.001];
+
+    let steps: alloc::vec::Vec<Oklab32> = g.steps(6, 0.15).unwrap();
+    assert_eq![steps.len(), 6];
+    assert![steps[0].approx_eq(&white.to_oklab32(), 1e-3)];
+    assert![steps[5].approx_eq(&black.to_oklab32(), 1e-3)];
+    for w in steps.windows(2) {
+        let d = w[0].squared_distance(&w[1]).sqrt();
+        assert![(0.15..0.25).contains(&d)];
+    }
+    assert![g.steps::<Srgb8>(6, 0.25).is_none()];
+    assert![g.steps::<Srgb8>(0, 1.).unwrap().is_empty()];
+    assert_eq![g.steps::<Srgb8>(1, 1.).unwrap(), [white]];
+
+    let flat = Gradient::builder().stop(0., black).build().unwrap();
+    assert![flat.steps::<Srgb8>(2, 0.01).is_none()];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn chromaticity() {
+    let close = |a: [f32; 2], b: [f32; 2]| (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4;
+    // the sRGB primaries and white point
+    assert![close(Srgb8::new(0, 255, 0).chromaticity_xy(), [0.30, 0.60])];
+    assert![close(
+        Srgb32::new(0., 0., 1.).chromaticity_xy(),
+        [0.15, 0.06]
+    )];
+    assert![close(Srgb8::new(128, 128, 128).chromaticity_xy(), D65_XY)];
+    assert_eq![Srgb8::new(0, 0, 0).chromaticity_xy(), D65_XY];
+    assert![close(
+        Oklab32::new(1., 0., 0.).chromaticity_uv(),
+        [0.1978, 0.4683]
+    )];
+
+    let white = Srgb8::new(255, 255, 255).color_to_xyz();
+    assert![(white[1] - 1.).abs() < 1e-4];
+    let red = Srgb8::new(255, 0, 0);
+    assert![(red.color_to_xyz()[1] - relative_luminance(red)).abs() < 1e-4];
+    let xy = red.chromaticity_xy();
+    assert![close(uv_to_xy(xy_to_uv(xy)), xy)];
+    assert![close(red.chromaticity_uv(), xy_to_uv(xy))];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn observers() {
+    let close =
+        |a: [f32; 2], b: [f32; 2], e: f32| (a[0] - b[0]).abs() < e && (a[1] - b[1]).abs() < e;
+    // points of the spectral loci from the tabulated functions,
+    // which the analytic approximations match within a few hundredths
+    let o2 = Observer::Cie1931;
+    assert![close(wavelength_to_xy(520., o2), [0.0743, 0.8338], 0.02)];
+    assert![close(wavelength_to_xy(600., o2), [0.6270, 0.3725], 0.01)];
+    let o10 = Observer::Cie1964;
+    assert![close(wavelength_to_xy(600., o10), [0.6135, 0.3865], 0.03)];
+    assert![(o2.color_matching(555.)[1] - 1.).abs() < 0.01];
+    assert![(o10.color_matching(560.)[1] - 1.).abs() < 0.02];
+    assert_eq![o10.color_matching(200.)[2], 0.];
+
+    // an equal energy spectrum is at the center of the diagram
+    let flat = [1.; 41];
+    for observer in [o2, o10] {
+        let [x, y, z] = spectrum_to_xyz(&flat, 380., 10., observer);
+        assert![(y - 1.).abs() < 1e-5];
+        assert![close(
+            [x / (x + y + z), y / (x + y + z)],
+            [1. / 3., 1. / 3.],
+            0.01
+        )];
+    }
+    let red: [f32; 41] = core::array::from_fn(|i| if i > 25 { 1. } else { 0. });
+    let (a, b) = (
+        spectrum_to_xyz(&red, 380., 10., o2),
+        spectrum_to_xyz(&red, 380., 10., o10),
+    );
+    assert![a != b && (a[0] - b[0]).abs() < 0.05];
+    assert_eq![spectrum_to_xyz(&[], 380., 10., o2), [0.; 3]];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn color_difference_metrics() {
+    // pairs from Sharma, Wu & Dalal's CIEDE2000 test data
+    let pairs = [
+        ([50., 2.6772, -79.7751], [50., 0., -82.7485], 2.0425),
+        ([50., -1., 2.], [50., 0., 0.], 2.3669),
+        ([50., 2.5, 0.], [73., 25., -18.], 27.1492),
+        (
+            [22.7233, 20.0904, -46.694],
+            [23.0331, 14.973, -42.5619],
+            2.0373,
+        ),
+        ([2.0776, 0.0795, -1.135], [0.9033, -0.0636, -0.5514], 0.9082),
+    ];
+    for (a, b, expected) in pairs {
+        assert![(ciede2000(a, b) - expected).abs() < 1e-3];
+        assert![(ciede2000(b, a) - expected).abs() < 1e-3];
+    }
+
+    let white = cielab(Srgb8::new(255, 255, 255));
+    assert![(white[0] - 100.).abs() < 1e-2 && white[1].abs() < 1e-2 && white[2].abs() < 1e-2];
+
+    let (black, white) = (Srgb8::new(0, 0, 0), Srgb8::new(255, 255, 255));
+    let (red, orange) = (Srgb8::new(255, 0, 0), Srgb8::new(255, 128, 0));
+    assert![(Cie76.difference(black, white) - 100.).abs() < 1e-2];
+    assert![(Ciede2000.difference(black, white) - 100.).abs() < 1e-2];
+    assert![(EuclidOklab.difference(black, white) - 1.).abs() < 1e-3];
+    assert![ApcaLightness.difference(black, white) > 100.];
+    assert_eq![ApcaLightness.difference(red, red), 0.];
+    for d in [
+        Cie76.difference(red, orange),
+        Ciede2000.difference(red, orange),
+    ] {
+        assert![d > 10.];
+    }
+
+    let palette = [black, white, red, orange];
+    let gray = Srgb8::new(200, 200, 200);
+    assert_eq![nearest(&Ciede2000, gray, &palette), Some(1)];
+    assert_eq![nearest(&Cie76, Srgb8::new(250, 100, 0), &palette), Some(3)];
+    assert_eq![nearest(&EuclidOklab, gray, &[] as &[Srgb8]), None];
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+#[cfg(not(feature = "micromath"))]
+fn weighted_differences() {
+    let (a, b) = (Srgb8::new(200, 60, 50), Srgb8::new(90, 140, 160));
+    let euclid = EuclidOklab.difference(a, b);
+    assert![(WeightedOklab::default().difference(a, b) - euclid).abs() < 1e-5];
+    assert![(WeightedOklch::default().difference(a, b) - euclid).abs() < 1e-5];
+    assert_eq![WeightedOklch::new(1., 1., 0.).difference(a, a), 0.];
+
+    // grays only differ in lightness
+    let (dark, light) = (Srgb8::new(60, 60, 60), Srgb8::new(180, 180, 180));
+    let zero_l = WeightedOklab::new(0., 1., 1.).difference(dark, light);
+    assert![zero_l < 1e-3];
+    assert![WeightedOklch::new(0., 1., 1.).difference(dark, light) < 1e-3];
+    let doubled = WeightedOklab::new(2., 1., 1.).difference(dark, light);
+    assert![(doubled - 2. * EuclidOklab.difference(dark, light)).abs() < 1e-5];
+
+    // weighting the lightness changes the nearest match
+    let palette = [Srgb8::new(40, 40, 40), Srgb8::new(40, 20, 160)];
+    let text = Srgb8::new(20, 10, 90);
+    assert_eq![nearest(&EuclidOklab, text, &palette), Some(1)];
+    assert_eq![
+        nearest(&WeightedOklch::new(4., 1., 0.25), text, &palette),
+        Some(0)
+    ];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn palette_nearest() {
+    let palette =
+        Palette::new(alloc::vec![Srgb8::new(40, 40, 40), Srgb8::new(40, 20, 160)]).unwrap();
+    let text = Srgb8::new(20, 10, 90);
+    assert_eq![palette.nearest(&EuclidOklab, text), Some(1)];
+    assert_eq![
+        palette.nearest(&WeightedOklch::new(4., 1., 0.25), text),
+        Some(0)
+    ];
+    assert_eq![
+        palette.nearest(&WeightedOklab::default(), Srgb8::new(42, 40, 40)),
+        Some(0)
+    ];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn palette_sorted_by_difference() {
+    use alloc::vec::Vec;
+    let total = |p: &Palette| -> f32 {
+        p.colors()
+            .windows(2)
+            .map(|w| Ciede2000.difference(w[0], w[1]))
+            .sum()
+    };
+    let colors: Vec<Srgb8> = (0..24)
+        .map(|i| Oklch32::new(0.6 + (i % 3) as f32 * 0.1, 0.1, (i * 105 % 360) as f32).to_srgb8())
+        .collect();
+    let palette = Palette::new(colors).unwrap();
+    let sorted = palette.sorted_by_difference(&Ciede2000);
+    assert_eq![sorted.len(), palette.len()];
+    for c in palette.colors() {
+        assert![sorted.colors().contains(c)];
+    }
+    assert![total(&sorted) < total(&palette) / 2.];
+
+    let two = Palette::new([Srgb8::new(9, 9, 9), Srgb8::new(1, 1, 1)].to_vec()).unwrap();
+    assert_eq![two.sorted_by_difference(&Cie76), two];
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn swatch_gpl_ase() {
+    let gpl = "GIMP Palette\nName: Test\nColumns: 2\n# comment\n\n255 128   0\tOrange\n  0   0 255 Blue\n";
+    let palette = Palette::from_gpl(gpl).unwrap();
+    assert_eq![
+        palette.colors(),
+        [Srgb8::new(255, 128, 0), Srgb8::new(0, 0, 255)]
+    ];
+    assert_eq![Palette::from_gpl(&palette.to_gpl("Test")).unwrap(), palette];
+    assert_eq![
+        Palette::from_gpl("Name: x"),
+        Err(SwatchError::InvalidSignature)
+    ];
+    assert_eq![
+        Palette::from_gpl("GIMP Palette\n1 2 300\n"),
+        Err(SwatchError::InvalidLine(2))
+    ];
+
+    let ase = palette.to_ase();
+    assert_eq![&ase[..12], b"ASEF\0\x01\0\0\0\0\0\x02"];
+    assert_eq![Palette::from_ase(&ase).unwrap(), palette];
+    assert_eq![
+        Palette::from_ase(&ase[..ase.len() - 1]),
+        Err(SwatchError::TooShort)
+    ];
+    assert_eq![
+        Palette::from_ase(b"ASEX"),
+        Err(SwatchError::InvalidSignature)
+    ];
+
+    // a group with a gray and a CMYK color
+    let mut data = b"ASEF\0\x01\0\0\0\0\0\x04".to_vec();
+    data.extend_from_slice(&[0xc0, 0x01, 0, 0, 0, 2, 0, 0]);
+    for (model, values) in [(b"Gray", &[0.5][..]), (b"CMYK", &[0., 1., 1., 0.][..])] {
+        let mut block = [0, 1, 0, 0].to_vec();
+        block.extend_from_slice(model);
+        for v in values {
+            block.extend_from_slice(&f32::to_be_bytes(*v));
+        }
+        block.extend_from_slice(&[0, 2]);
+        data.extend_from_slice(&[0, 1]);
+        data.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        data.extend(block);
+    }
+    data.extend_from_slice(&[0xc0, 0x02, 0, 0, 0, 0]);
+    let palette = Palette::from_ase(&data).unwrap();
+    assert_eq![
+        palette.colors(),
+        [Srgb8::new(128, 128, 128), Srgb8::new(255, 0, 0)]
+    ];
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn swatch_json() {
+    use alloc::format;
+
+    let json = r##"{
+        "name": "Brand \"A\"",
+        "version": [1, 2.5e0, true, null, {}],
+        "colors": [
+            { "name": "orange!", "hex": "#ff8000" },
+            { "hex": "#00F", "space": "srgb", "values": [1, 1, 1] }
+        ]
+    }"##;
+    let palette = Palette::from_json(json).unwrap();
+    assert_eq![
+        palette.colors(),
+        [Srgb8::new(255, 128, 0), Srgb8::new(0, 0, 255)]
+    ];
+
+    let out = palette.to_json("Brand \"A\"");
+    assert![out.contains(r#""name": "Brand \"A\"""#)];
+    assert_eq![Palette::from_json(&out).unwrap(), palette];
+    assert_eq![
+        Palette::from_json(&Palette::default().to_json("")).unwrap(),
+        Palette::default()
+    ];
+
+    // names are kept by the schema struct
+    let named = JsonPalette::from_json(json).unwrap();
+    assert_eq![named.name.as_deref(), Some("Brand \"A\"")];
+    assert_eq![named.colors[0].name.as_deref(), Some("orange!")];
+    assert_eq![JsonPalette::from_json(&named.to_json()).unwrap(), named];
+    let escaped = r##"{"colors": [{"name": "\ud83c\udfa8", "hex": "#000"}]}"##;
+    let named = JsonPalette::from_json(escaped).unwrap();
+    assert_eq![named.colors[0].name.as_deref(), Some("🎨")];
+
+    assert_eq![
+        Palette::from_json("{\"colors\": [}"),
+        Err(SwatchError::InvalidJson(12))
+    ];
+    assert![matches![
+        Palette::from_json("[]"),
+        Err(SwatchError::InvalidJson(_))
+    ]];
+    let deep = "[".repeat(100_000);
+    assert![matches![
+        Palette::from_json(&deep),
+        Err(SwatchError::InvalidJson(_))
+    ]];
+    for bad in ["+1", "1.", ".5"] {
+        let bad = format!["{{\"colors\": [{{\"space\": \"srgb\", \"values\": [{bad}, 0, 0]}}]}}"];
+        assert![matches![
+            Palette::from_json(&bad),
+            Err(SwatchError::InvalidJson(_))
+        ]];
+    }
+    let bad = r##"{"colors": [{"hex": "#ff8000"}, {"hex": "#12345"}]}"##;
+    assert_eq![Palette::from_json(bad), Err(SwatchError::InvalidColor(1))];
+    let bad = r##"{"colors": [{"space": "srgb", "values": [1, 0]}]}"##;
+    assert_eq![Palette::from_json(bad), Err(SwatchError::InvalidColor(0))];
+
+    #[cfg(any(feature = "std", feature = "no_std"))]
+    {
+        let json = r##"{"colors": [{"space": "srgb-linear", "values": [0, 0.2158605, 1]}]}"##;
+        let palette = Palette::from_json(json).unwrap();
+        assert_eq![palette.colors(), [Srgb8::new(0, 128, 255)]];
+        let out = palette.to_json_in_space("", PredefinedSpace::DisplayP3);
+        assert![out.contains(r#""space": "display-p3""#)];
+        let floats = out.replace("\"hex\"", "\"old\"");
+        let back = Palette::from_json(&floats).unwrap();
+        assert![back.colors()[0].approx_eq(&palette.colors()[0], 1)];
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn palette_parse_hex_list() {
+    let palette = Palette::parse_hex_list(" #aabbcc,#112233 ;\tFFF\n#0008, 12345678,,").unwrap();
+    assert_eq![
+        palette.colors(),
+        [
+            Srgb8::new(0xaa, 0xbb, 0xcc),
+            Srgb8::new(0x11, 0x22, 0x33),
+            Srgb8::new(0xff, 0xff, 0xff),
+            Srgb8::new(0, 0, 0),
+            Srgb8::new(0x12, 0x34, 0x56),
+        ]
+    ];
+    assert_eq![Palette::parse_hex_list(""), Ok(Palette::default())];
+    assert_eq![
+        Palette::parse_hex_list("#fff #ggg"),
+        Err(SwatchError::InvalidColor(1))
+    ];
+    assert_eq![
+        Palette::parse_hex_list("#fff, #12345"),
+        Err(SwatchError::InvalidColor(1))
+    ];
+    assert_eq![
+        Palette::parse_hex_list("#+ff"),
+        Err(SwatchError::InvalidColor(0))
+    ];
+    let many = "#000 ".repeat(257);
+    assert_eq![
+        Palette::parse_hex_list(&many),
+        Err(SwatchError::TooManyColors(257))
+    ];
+}
+
+#[test]
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "no_std")))]
+fn shader_codegen() {
+    let colors = [Srgb8::new(255, 255, 255), Srgb8::new(0, 0, 0)];
+    let palette = Palette::new(colors.to_vec()).unwrap();
+
+    let glsl = ShaderCodegen::new(ShaderLang::Glsl, ShaderEncoding::Srgb);
+    assert_eq![
+        glsl.palette("PAL", &palette),
+        "const vec3 PAL[2] = vec3[2](vec3(1.0, 1.0, 1.0), vec3(0.0, 0.0, 0.0));"
+    ];
+    let wgsl = ShaderCodegen::new(ShaderLang::Wgsl, ShaderEncoding::Srgb).with_alpha();
+    assert_eq![
+        wgsl.colors("PAL", &colors[..1]),
+        "const PAL: array<vec4<f32>, 1> = array<vec4<f32>, 1>(vec4<f32>(1.0, 1.0, 1.0, 1.0));"
+    ];
+    let hlsl = ShaderCodegen::new(ShaderLang::Hlsl, ShaderEncoding::Linear);
+    assert_eq![
+        hlsl.colors("PAL", &colors),
+        "static const float3 PAL[2] = { float3(1.0, 1.0, 1.0), float3(0.0, 0.0, 0.0) };"
+    ];
+
+    let gray = Srgb8::new(128, 128, 128);
+    let linear = hlsl.color("GRAY", gray);
+    let encoded = ShaderCodegen::new(ShaderLang::Hlsl, ShaderEncoding::Srgb).color("GRAY", gray);
+    assert![linear.starts_with("static const float3 GRAY = float3(0.21")];
+    assert![encoded.starts_with("static const float3 GRAY = float3(0.50")];
+}
+
+#[test]
+#[cfg(all(feature = "tiny-skia", any(feature = "std", feature = "no_std")))]
+fn tiny_skia_premultiplication() {
+    let c = Srgba32::new(1., 0.5, 0., 0.5);
+    assert_eq![tiny_skia::Color::from(c).alpha(), 0.5];
+
+    let encoded: tiny_skia::PremultipliedColor = c.into();
+    let linear = c.to_tiny_skia_premultiplied_linear();
+    assert_eq![(encoded.red(), linear.red()), (0.5, 0.5)];
+    assert_eq![encoded.green(), 0.25];
+    assert![(linear.green() - 0.5 * 0.21404).abs() < 1e-4];
+
+    let c = Srgba8::new(255, 128, 0, 128).to_tiny_skia_premultiplied_linear();
+    assert_eq![[c.red(), c.green(), c.blue(), c.alpha()], [128, 28, 0, 128]];
+}
+
+#[test]
+#[cfg(all(feature = "fixed", any(feature = "std", feature = "no_std")))]
+fn fixed_point_integer_conversions() {
+    let mut max_error = 0f32;
+    for v in 0..=255 {
+        let lin = LinearSrgbU0F16::from_srgb8(Srgb8::new(v, v, v));
+        let expected = Srgb8::new(v, 0, 0).to_linear_srgb32().r;
+        assert![(lin.r.to_bits() as f32 - expected * 65535.).abs() <= 0.51];
+        assert_eq![lin.to_srgb8(), Srgb8::new(v, v, v)];
+    }
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                let c = Srgb8::new(r, g, b);
+                let lab = OklabI16F16::from_srgb8(c);
+                let expected = c.to_oklab32();
+                assert_eq![lab.to_srgb8(), c];
+                for (x, y) in lab.to_oklab32().into_iter().zip(expected) {
+                    max_error = max_error.max((x - y).abs());
+                }
+            }
+        }
+    }
+    assert![max_error < 2e-4, "{}", max_error];
+
+    let c = Srgba8::new(200, 30, 90, 128);
+    assert_eq![LinearSrgbaU0F16::from_srgba8(c).to_srgba8(), c];
+    let lab = OklabI16F16::from_array([I16F16::MAX, I16F16::MIN, I16F16::MAX]);
+    assert_eq![lab.to_srgb8(), Srgb8::new(255, 255, 0)];
+}
+
+#[test]
+#[cfg(all(feature = "precise", any(feature = "std", feature = "no_std")))]
+fn precise_conversions() {
+    let white = Srgb32::new(1., 1., 1.);
+    assert_eq![white.to_linear_srgb32().to_srgb32(), white];
+    assert_eq![white.to_oklab32().l, 1.];
+
+    for v in 0..=255 {
+        let c = Srgb32::new(v as f32 / 255., 0.5, 1.);
+        let linear = c.to_linear_srgb32();
+        for (x, y) in linear.to_srgb32().into_iter().zip(c) {
+            assert![(x - y).abs() <= f32::EPSILON * y];
+        }
+        for (x, y) in linear
+            .to_oklab32()
+            .to_linear_srgb32()
+            .into_iter()
+            .zip(linear)
+        {
+            assert![(x - y).abs() < 1e-6];
+        }
+    }
+}
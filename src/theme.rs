@@ -0,0 +1,397 @@
+// acolor::theme
+//
+//! User interface themes derived from a single color.
+//!
+//! # Example
+//! ```
+//! use acolor::{contrast::{wcag_contrast, WCAG_AA}, srgb::Srgb8, theme::{Theme, ThemeMode}};
+//!
+//! let theme = Theme::from_seed(Srgb8::new(30, 140, 200), ThemeMode::Light);
+//! assert![wcag_contrast(theme.text, theme.background) >= WCAG_AA];
+//! assert![wcag_contrast(theme.primary, theme.background) >= WCAG_AA];
+//! ```
+//
+// # TOC
+//
+// - ThemeMode
+// - Theme
+// - dark_palette
+// - AccessiblePalette
+// - AccessiblePaletteError
+//
+
+#[cfg(feature = "alloc")]
+use crate::contrast::{relative_luminance, MinContrast};
+#[cfg(feature = "alloc")]
+use crate::image::Palette;
+use crate::{
+    contrast::{wcag_contrast, WCAG_AA, WCAG_AAA, WCAG_AA_LARGE},
+    hue::Hue,
+    oklab::Oklch32,
+    srgb::Srgb8,
+    Color,
+};
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, core::fmt};
+
+/// Whether a [`Theme`] has a light or a dark background.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ThemeMode {
+    /// Light background and dark text.
+    #[default]
+    Light,
+    /// Dark background and light text.
+    Dark,
+}
+
+/// The colors of a user interface theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// The color of the main background.
+    pub background: Srgb8,
+    /// The color of elevated surfaces, like cards and menus.
+    pub surface: Srgb8,
+    /// The main brand color, with the hue and chroma of the seed.
+    pub primary: Srgb8,
+    /// A secondary color, with the complementary hue of the primary.
+    pub accent: Srgb8,
+    /// The color for errors.
+    pub error: Srgb8,
+    /// The color of the text.
+    pub text: Srgb8,
+}
+
+// The Oklch hue of the error color.
+const ERROR_HUE: f32 = 25.;
+
+impl Theme {
+    /// Derives a theme from a `seed` color.
+    ///
+    /// The background and surface are nearly neutral, tinted with the seed hue.
+    /// The lightness of the rest of colors is adjusted to guarantee their contrast
+    /// against the background: [`WCAG_AAA`] for the text, [`WCAG_AA`] for
+    /// the primary and error colors, and [`WCAG_AA_LARGE`] for the accent.
+    pub fn from_seed<C: Color>(seed: C, mode: ThemeMode) -> Theme {
+        let Oklch32 { l, c, h } = seed.color_to_oklch32();
+        let tint = |l: f32, max_c: f32| {
+            Oklch32 {
+                l,
+                c: c.min(max_c),
+                h,
+            }
+            .gamut_map_srgb()
+            .to_srgb8()
+        };
+        let (background, surface, text_l) = match mode {
+            ThemeMode::Light => (tint(0.98, 0.01), tint(0.95, 0.02), 0.25),
+            ThemeMode::Dark => (tint(0.18, 0.02), tint(0.24, 0.03), 0.92),
+        };
+        let fit = |color: Oklch32, min| with_contrast(color, background, min, mode);
+
+        Theme {
+            background,
+            surface,
+            primary: fit(Oklch32 { l, c, h }, WCAG_AA),
+            accent: fit(
+                Oklch32 {
+                    l,
+                    c,
+                    h: (Hue::new(h) + 180.).degrees(),
+                },
+                WCAG_AA_LARGE,
+            ),
+            error: fit(
+                Oklch32 {
+                    l: 0.6,
+                    c: c.max(0.15),
+                    h: ERROR_HUE,
+                },
+                WCAG_AA,
+            ),
+            text: fit(
+                Oklch32 {
+                    l: text_l,
+                    c: c.min(0.02),
+                    h,
+                },
+                WCAG_AAA,
+            ),
+        }
+    }
+}
+
+/* dark mode */
+
+// The Oklab lightness range of the colors of a dark palette.
+#[cfg(feature = "alloc")]
+const DARK_LIGHTNESS: (f32, f32) = (0.15, 0.95);
+
+/// Derives a dark variant from a `light` palette.
+///
+/// The Oklab lightness of each color is inverted into a range that avoids pure black
+/// and white, keeping its hue and its chroma relative to the maximum available
+/// in sRGB for its new lightness.
+///
+/// The lightest color is taken as the background. The contrast of each of the other
+/// colors against it is then re-validated, lightening them as needed so that they
+/// keep at least their original contrast against the new background.
+///
+/// # Example
+/// ```
+/// use acolor::{image::Palette, srgb::Srgb8, theme::dark_palette};
+///
+/// let light = Palette::new(vec![Srgb8::new(250, 250, 250), Srgb8::new(20, 20, 20)]).unwrap();
+/// let dark = dark_palette(&light);
+/// assert![dark.get(0).unwrap().r < 50 && dark.get(1).unwrap().r > 200];
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+pub fn dark_palette(light: &Palette) -> Palette {
+    let colors = light.colors();
+    let Some(bg_index) = colors
+        .iter()
+        .enumerate()
+        .max_by(|a, b| relative_luminance(*a.1).total_cmp(&relative_luminance(*b.1)))
+        .map(|(i, _)| i)
+    else {
+        return Palette::default();
+    };
+
+    let (min, max) = DARK_LIGHTNESS;
+    let invert = |color: Srgb8| {
+        let Oklch32 { l, c, h } = color.to_oklch32();
+        let relative_c = c / Oklch32::max_srgb_chroma(l, h).max(f32::EPSILON);
+        let l = min + (1. - l.clamp(0., 1.)) * (max - min);
+        Oklch32 {
+            l,
+            c: relative_c.min(1.) * Oklch32::max_srgb_chroma(l, h),
+            h,
+        }
+    };
+    let background = invert(colors[bg_index]).gamut_map_srgb().to_srgb8();
+
+    let dark: Vec<Srgb8> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            if i == bg_index {
+                background
+            } else {
+                let contrast = wcag_contrast(*color, colors[bg_index]);
+                with_contrast(invert(*color), background, contrast, ThemeMode::Dark)
+            }
+        })
+        .collect();
+    Palette::new(dark).expect("the same length as the original palette")
+}
+
+// Returns the color with its lightness moved away from the background
+// until it reaches the `min` contrast against it, or the lightness limit.
+fn with_contrast(mut color: Oklch32, background: Srgb8, min: f32, mode: ThemeMode) -> Srgb8 {
+    let step = match mode {
+        ThemeMode::Light => -0.01,
+        ThemeMode::Dark => 0.01,
+    };
+    loop {
+        let srgb = color.gamut_map_srgb().to_srgb8();
+        if wcag_contrast(srgb, background) >= min || !(0. ..=1.).contains(&(color.l + step)) {
+            return srgb;
+        }
+        color.l += step;
+    }
+}
+
+/* accessible palette */
+
+// The lightness step of the accessible palette solver.
+#[cfg(feature = "alloc")]
+const SOLVER_STEP: f32 = 0.005;
+// The maximum number of lightness steps of the accessible palette solver.
+#[cfg(feature = "alloc")]
+const SOLVER_ITERATIONS: usize = 10_000;
+
+/// A set of color roles with minimum pairwise contrast requirements,
+/// solved into a palette.
+///
+/// Each role starts from a seed color, like the background, the surface,
+/// the text or the primary color, and is identified by the order in which it's added.
+/// Solving keeps the hue and chroma of each seed, and moves the Oklch lightness
+/// of the adjustable roles the least needed to meet all the contrast requirements,
+/// while keeping the order of luminance of the seeds of each pair of related roles.
+///
+/// # Example
+/// ```
+/// use acolor::{
+///     contrast::{wcag_contrast, MinContrast, WCAG_AA, WCAG_AAA},
+///     srgb::Srgb8,
+///     theme::AccessiblePalette,
+/// };
+///
+/// let palette = AccessiblePalette::new()
+///     .fixed_role(Srgb8::new(250, 248, 240)) // 0: background
+///     .role(Srgb8::new(235, 230, 215)) // 1: surface
+///     .role(Srgb8::new(90, 90, 90)) // 2: text
+///     .role(Srgb8::new(70, 160, 230)) // 3: primary
+///     .contrast(2, 0, MinContrast::Wcag(WCAG_AAA))
+///     .contrast(2, 1, MinContrast::Wcag(WCAG_AAA))
+///     .contrast(3, 1, MinContrast::Wcag(WCAG_AA))
+///     .solve()
+///     .unwrap();
+/// assert_eq![palette[0], Srgb8::new(250, 248, 240)];
+/// assert![wcag_contrast(palette[2], palette[1]) >= WCAG_AAA];
+/// assert![wcag_contrast(palette[3], palette[1]) >= WCAG_AA];
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccessiblePalette {
+    // the seed of each role, and whether it can be adjusted
+    roles: Vec<(Srgb8, bool)>,
+    constraints: Vec<(usize, usize, MinContrast)>,
+}
+
+#[cfg(feature = "alloc")]
+impl AccessiblePalette {
+    /// Returns a new palette without roles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a role seeded with a `color`, whose lightness can be adjusted.
+    pub fn role<C: Color>(mut self, color: C) -> Self {
+        self.roles.push((color.color_to_srgb8(), true));
+        self
+    }
+    /// Adds a role with a `color` that is kept as is, like a brand color
+    /// or a given background.
+    pub fn fixed_role<C: Color>(mut self, color: C) -> Self {
+        self.roles.push((color.color_to_srgb8(), false));
+        self
+    }
+    /// Requires a `min` contrast between the roles at the indices `a` and `b`.
+    pub fn contrast(mut self, a: usize, b: usize, min: MinContrast) -> Self {
+        self.constraints.push((a, b, min));
+        self
+    }
+
+    /// Returns the number of roles.
+    pub fn len(&self) -> usize {
+        self.roles.len()
+    }
+    /// Returns `true` if there are no roles.
+    pub fn is_empty(&self) -> bool {
+        self.roles.is_empty()
+    }
+
+    /// Returns the colors of the roles, in order, meeting all the contrast requirements.
+    ///
+    /// The first unmet requirement is repeatedly addressed by either darkening
+    /// its darker role or lightening its lighter one, as given by their seeds,
+    /// whichever leaves fewer requirements unmet, or else keeps the role nearer to its seed.
+    ///
+    /// # Errors
+    /// Returns [`AccessiblePaletteError`] if a requirement refers to a missing role,
+    /// or if the requirements can't all be met.
+    pub fn solve(&self) -> Result<Vec<Srgb8>, AccessiblePaletteError> {
+        for (i, (a, b, _)) in self.constraints.iter().enumerate() {
+            if *a >= self.roles.len() || *b >= self.roles.len() {
+                return Err(AccessiblePaletteError::InvalidRole(i));
+            }
+        }
+        let seeds: Vec<Oklch32> = self.roles.iter().map(|(c, _)| c.to_oklch32()).collect();
+        let mut lightness: Vec<f32> = seeds.iter().map(|c| c.l).collect();
+        let color = |i: usize, l: f32| {
+            let (seed, adjustable) = self.roles[i];
+            if adjustable {
+                let Oklch32 { c, h, .. } = seeds[i];
+                Oklch32 { l, c, h }.gamut_map_srgb().to_srgb8()
+            } else {
+                seed
+            }
+        };
+
+        // the darker and the lighter role of each requirement, which are kept in that order
+        let polarities: Vec<(usize, usize)> = self
+            .constraints
+            .iter()
+            .map(|(a, b, _)| {
+                if relative_luminance(self.roles[*a].0) < relative_luminance(self.roles[*b].0) {
+                    (*a, *b)
+                } else {
+                    (*b, *a)
+                }
+            })
+            .collect();
+
+        let mut unmet = 0;
+        for _ in 0..SOLVER_ITERATIONS {
+            let colors: Vec<Srgb8> = (0..self.roles.len())
+                .map(|i| color(i, lightness[i]))
+                .collect();
+            let Some(k) = self
+                .constraints
+                .iter()
+                .position(|(a, b, min)| !min.is_met_by(colors[*a], colors[*b]))
+            else {
+                return Ok(colors);
+            };
+            unmet = k;
+            let (dark, light) = polarities[k];
+            // the candidate moves, ranked by the number of requirements they leave unmet
+            // and then by their resulting distance to the seed lightness
+            let moves = [(dark, -SOLVER_STEP), (light, SOLVER_STEP)];
+            let best = moves
+                .iter()
+                .filter(|(i, step)| {
+                    self.roles[*i].1 && (0. ..=1.).contains(&(lightness[*i] + step))
+                })
+                .map(|(i, step)| {
+                    let (mut moved, l) = (colors.clone(), lightness[*i] + step);
+                    moved[*i] = color(*i, l);
+                    (*i, *step, self.unmet(&moved), (l - seeds[*i].l).abs())
+                })
+                .min_by(|x, y| x.2.cmp(&y.2).then(x.3.total_cmp(&y.3)));
+            match best {
+                Some((i, step, _, _)) => lightness[i] += step,
+                None => return Err(AccessiblePaletteError::Unsatisfiable(k)),
+            }
+        }
+        Err(AccessiblePaletteError::Unsatisfiable(unmet))
+    }
+
+    // Returns the number of contrast requirements that the `colors` don't meet.
+    fn unmet(&self, colors: &[Srgb8]) -> usize {
+        self.constraints
+            .iter()
+            .filter(|(a, b, min)| !min.is_met_by(colors[*a], colors[*b]))
+            .count()
+    }
+}
+
+/// An error solving an [`AccessiblePalette`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessiblePaletteError {
+    /// The contrast requirement at this index refers to a missing role.
+    InvalidRole(usize),
+    /// The contrast requirement at this index can't be met along with the rest.
+    Unsatisfiable(usize),
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for AccessiblePaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessiblePaletteError::InvalidRole(i) => {
+                write!(f, "contrast requirement {} refers to a missing role", i)
+            }
+            AccessiblePaletteError::Unsatisfiable(i) => {
+                write!(f, "contrast requirement {} can't be met", i)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AccessiblePaletteError {}
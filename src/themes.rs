@@ -0,0 +1,135 @@
+// acolor::themes
+//
+//! Popular theme palettes.
+//!
+//! # Example
+//! ```
+//! use acolor::{srgb::Srgb8, themes};
+//!
+//! assert_eq![themes::NORD.get(0), Some(Srgb8::new(0x2e, 0x34, 0x40))];
+//! assert_eq![themes::by_name("Dracula"), Some(&themes::DRACULA)];
+//! ```
+//
+// # TOC
+//
+// - SOLARIZED
+// - NORD
+// - GRUVBOX_DARK
+// - DRACULA
+// - THEMES
+// - by_name
+//
+
+use crate::{image::Palette, srgb::Srgb8};
+
+/// The [Solarized] palette, shared by its dark and light variants.
+///
+/// In order: `base03`, `base02`, `base01`, `base00`, `base0`, `base1`, `base2`, `base3`,
+/// `yellow`, `orange`, `red`, `magenta`, `violet`, `blue`, `cyan` and `green`.
+///
+/// [Solarized]: https://ethanschoonover.com/solarized/
+pub const SOLARIZED: Palette = Palette::from_static(&[
+    Srgb8::from_hex(0x002b36),
+    Srgb8::from_hex(0x073642),
+    Srgb8::from_hex(0x586e75),
+    Srgb8::from_hex(0x657b83),
+    Srgb8::from_hex(0x839496),
+    Srgb8::from_hex(0x93a1a1),
+    Srgb8::from_hex(0xeee8d5),
+    Srgb8::from_hex(0xfdf6e3),
+    Srgb8::from_hex(0xb58900),
+    Srgb8::from_hex(0xcb4b16),
+    Srgb8::from_hex(0xdc322f),
+    Srgb8::from_hex(0xd33682),
+    Srgb8::from_hex(0x6c71c4),
+    Srgb8::from_hex(0x268bd2),
+    Srgb8::from_hex(0x2aa198),
+    Srgb8::from_hex(0x859900),
+]);
+
+/// The [Nord] palette.
+///
+/// In order: `nord0` to `nord15`, the Polar Night, Snow Storm,
+/// Frost and Aurora colors.
+///
+/// [Nord]: https://www.nordtheme.com/docs/colors-and-palettes
+pub const NORD: Palette = Palette::from_static(&[
+    Srgb8::from_hex(0x2e3440),
+    Srgb8::from_hex(0x3b4252),
+    Srgb8::from_hex(0x434c5e),
+    Srgb8::from_hex(0x4c566a),
+    Srgb8::from_hex(0xd8dee9),
+    Srgb8::from_hex(0xe5e9f0),
+    Srgb8::from_hex(0xeceff4),
+    Srgb8::from_hex(0x8fbcbb),
+    Srgb8::from_hex(0x88c0d0),
+    Srgb8::from_hex(0x81a1c1),
+    Srgb8::from_hex(0x5e81ac),
+    Srgb8::from_hex(0xbf616a),
+    Srgb8::from_hex(0xd08770),
+    Srgb8::from_hex(0xebcb8b),
+    Srgb8::from_hex(0xa3be8c),
+    Srgb8::from_hex(0xb48ead),
+]);
+
+/// The [Gruvbox] dark palette.
+///
+/// In order, like the 16 terminal colors: `bg`, `red`, `green`, `yellow`, `blue`,
+/// `purple`, `aqua`, `gray`, and the bright `gray`, `red`, `green`, `yellow`,
+/// `blue`, `purple`, `aqua` and `fg`.
+///
+/// [Gruvbox]: https://github.com/morhetz/gruvbox
+pub const GRUVBOX_DARK: Palette = Palette::from_static(&[
+    Srgb8::from_hex(0x282828),
+    Srgb8::from_hex(0xcc241d),
+    Srgb8::from_hex(0x98971a),
+    Srgb8::from_hex(0xd79921),
+    Srgb8::from_hex(0x458588),
+    Srgb8::from_hex(0xb16286),
+    Srgb8::from_hex(0x689d6a),
+    Srgb8::from_hex(0xa89984),
+    Srgb8::from_hex(0x928374),
+    Srgb8::from_hex(0xfb4934),
+    Srgb8::from_hex(0xb8bb26),
+    Srgb8::from_hex(0xfabd2f),
+    Srgb8::from_hex(0x83a598),
+    Srgb8::from_hex(0xd3869b),
+    Srgb8::from_hex(0x8ec07c),
+    Srgb8::from_hex(0xebdbb2),
+]);
+
+/// The [Dracula] palette.
+///
+/// In order: `background`, `current line`, `foreground`, `comment`, `cyan`,
+/// `green`, `orange`, `pink`, `purple`, `red` and `yellow`.
+///
+/// [Dracula]: https://draculatheme.com/contribute
+pub const DRACULA: Palette = Palette::from_static(&[
+    Srgb8::from_hex(0x282a36),
+    Srgb8::from_hex(0x44475a),
+    Srgb8::from_hex(0xf8f8f2),
+    Srgb8::from_hex(0x6272a4),
+    Srgb8::from_hex(0x8be9fd),
+    Srgb8::from_hex(0x50fa7b),
+    Srgb8::from_hex(0xffb86c),
+    Srgb8::from_hex(0xff79c6),
+    Srgb8::from_hex(0xbd93f9),
+    Srgb8::from_hex(0xff5555),
+    Srgb8::from_hex(0xf1fa8c),
+]);
+
+/// All the theme palettes, with their names.
+pub static THEMES: [(&str, Palette); 4] = [
+    ("Solarized", SOLARIZED),
+    ("Nord", NORD),
+    ("Gruvbox Dark", GRUVBOX_DARK),
+    ("Dracula", DRACULA),
+];
+
+/// Returns the theme palette with the given name, ignoring case.
+pub fn by_name(name: &str) -> Option<&'static Palette> {
+    THEMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, palette)| palette)
+}
@@ -0,0 +1,80 @@
+// acolor::tonal
+//
+//! Tonal palettes, for app theming.
+//!
+//! A tonal palette keeps the hue and chroma of a seed color,
+//! and varies its lightness in steps called tones, from `0` (black) to `100` (white),
+//! in the style of Material Design.
+//!
+//! # Example
+//! ```
+//! use acolor::{srgb::Srgb8, tonal::TonalPalette};
+//!
+//! let palette = TonalPalette::from_seed(Srgb8::new(103, 80, 164));
+//! assert_eq![palette.tone(0), Srgb8::new(0, 0, 0)];
+//! assert_eq![palette.tone(100), Srgb8::new(255, 255, 255)];
+//! let ladder = palette.tones();
+//! assert![ladder.windows(2).all(|w| w[0].r <= w[1].r)];
+//! ```
+//!
+//! # Links
+//! - <https://m3.material.io/styles/color/system/how-the-system-works>
+//
+
+use crate::{oklab::Oklch32, srgb::Srgb8, Color};
+
+/// A palette of tones sharing the hue and chroma of a seed color.
+///
+/// The tones are Oklch lightness steps, mapped into the sRGB gamut by reducing
+/// their chroma when needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TonalPalette {
+    hue: f32,
+    chroma: f32,
+}
+
+impl TonalPalette {
+    /// The tones of the standard Material ladder.
+    pub const TONES: [u8; 13] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99, 100];
+
+    /// Returns a new tonal palette with the hue and chroma of the `seed` color.
+    pub fn from_seed<C: Color>(seed: C) -> TonalPalette {
+        let c = seed.color_to_oklch32();
+        TonalPalette::new(c.h, c.c)
+    }
+
+    /// Returns a new tonal palette with the given Oklch `hue` and `chroma`.
+    pub const fn new(hue: f32, chroma: f32) -> TonalPalette {
+        TonalPalette { hue, chroma }
+    }
+
+    /// Returns the Oklch hue.
+    pub const fn hue(&self) -> f32 {
+        self.hue
+    }
+    /// Returns the Oklch chroma, before gamut mapping.
+    pub const fn chroma(&self) -> f32 {
+        self.chroma
+    }
+
+    /// Returns the color at the given `tone`, clamped to `0..=100`.
+    pub fn tone_oklch32(&self, tone: u8) -> Oklch32 {
+        let l = tone.min(100) as f32 / 100.;
+        Oklch32 {
+            l,
+            c: self.chroma,
+            h: self.hue,
+        }
+        .gamut_map_srgb()
+    }
+
+    /// Returns the color at the given `tone`, clamped to `0..=100`.
+    pub fn tone(&self, tone: u8) -> Srgb8 {
+        self.tone_oklch32(tone).to_srgb8()
+    }
+
+    /// Returns the colors of the standard [`TONES`][Self::TONES] ladder.
+    pub fn tones(&self) -> [Srgb8; 13] {
+        TonalPalette::TONES.map(|t| self.tone(t))
+    }
+}
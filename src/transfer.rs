@@ -0,0 +1,313 @@
+// acolor::transfer
+//
+//! Transfer functions.
+//!
+//! Includes the BT.2100 high dynamic range curves: Perceptual Quantizer (PQ)
+//! and Hybrid Log-Gamma (HLG).
+//!
+//! # Pure gamma 2.2
+//!
+//! The default conversions use the sRGB piecewise curve. To match content
+//! authored with a plain 2.2 power curve use [`TransferFunction::GAMMA_22`]
+//! with the conversion methods ending in `_with`, for example:
+//! ```
+//! # #[cfg(all(any(feature = "std", feature = "no_std"), not(feature = "micromath")))] {
+//! use acolor::{srgb::Srgb8, transfer::TransferFunction};
+//!
+//! let texel = Srgb8::new(30, 120, 240);
+//! let linear = texel.to_linear_srgb32_with(TransferFunction::GAMMA_22);
+//! assert_eq![Srgb8::from_linear_srgb32_with(linear, TransferFunction::GAMMA_22), texel];
+//! # }
+//! ```
+//!
+//! # Links
+//! - <https://www.itu.int/rec/R-REC-BT.2100>
+//! - <https://en.wikipedia.org/wiki/Perceptual_quantizer>
+//! - <https://en.wikipedia.org/wiki/Hybrid_log%E2%80%93gamma>
+//
+// # TOC
+//
+// - TransferFunction
+// - pq
+// - hlg
+// - rec709
+//
+
+use crate::{
+    depth::{f32_to_unorm_with, Rounding},
+    math::{exp, f32_to_u8, ln, log10, powf, sqrt, u8_to_f32},
+    srgb::{encode32, linearize32, linearize8},
+    yuv::Range,
+    GAMMA_22, GAMMA_32,
+};
+
+/// A transfer function, relating encoded and linear values of a channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TransferFunction {
+    /// The sRGB piecewise curve, a linear segment followed by a 2.4 power.
+    ///
+    /// It approximates a pure 2.2 gamma, but isn't the same.
+    /// This is the curve used by default in all the conversions.
+    #[default]
+    SrgbPiecewise,
+
+    /// A pure power curve with the given exponent.
+    PureGamma(f32),
+
+    /// The identity, for already linear values.
+    Linear,
+
+    /// The BT.709 and BT.2020 camera curve.
+    Rec709,
+
+    /// The PQ curve, normalized so that `1.0` is [`PQ_MAX_NITS`].
+    Pq,
+
+    /// The HLG curve, scene referred, without the system gamma.
+    ///
+    /// See [`hlg_eotf`] for the display referred version.
+    Hlg,
+}
+
+impl TransferFunction {
+    /// A pure power curve with an exponent of [`GAMMA_22`].
+    pub const GAMMA_22: TransferFunction = TransferFunction::PureGamma(GAMMA_22);
+
+    /// Decodes an encoded `f32` channel into a linear one.
+    pub fn decode(self, encoded: f32) -> f32 {
+        match self {
+            TransferFunction::SrgbPiecewise => linearize32(encoded, GAMMA_32),
+            TransferFunction::PureGamma(g) => powf(encoded.max(0.), g),
+            TransferFunction::Linear => encoded,
+            TransferFunction::Rec709 => rec709_inverse_oetf(encoded),
+            TransferFunction::Pq => pq_eotf(encoded),
+            TransferFunction::Hlg => hlg_inverse_oetf(encoded),
+        }
+    }
+
+    /// Encodes a linear `f32` channel.
+    pub fn encode(self, linear: f32) -> f32 {
+        match self {
+            TransferFunction::SrgbPiecewise => encode32(linear),
+            TransferFunction::PureGamma(g) => powf(linear.max(0.), 1. / g),
+            TransferFunction::Linear => linear,
+            TransferFunction::Rec709 => rec709_oetf(linear),
+            TransferFunction::Pq => pq_inverse_eotf(linear),
+            TransferFunction::Hlg => hlg_oetf(linear),
+        }
+    }
+
+    /// Decodes an encoded [`u8`] channel into a linear `f32` one.
+    ///
+    /// The sRGB curve uses [`linearize8`], honoring the `fast_lut` feature.
+    #[inline]
+    pub fn decode8(self, encoded: u8) -> f32 {
+        match self {
+            TransferFunction::SrgbPiecewise => linearize8(encoded),
+            _ => self.decode(u8_to_f32(encoded)),
+        }
+    }
+
+    /// Encodes a linear `f32` channel into a [`u8`] one.
+    #[inline]
+    pub fn encode8(self, linear: f32) -> u8 {
+        f32_to_u8(self.encode(linear))
+    }
+
+    /// Encodes a linear `f32` channel into a [`u8`] one, using the given `rounding`.
+    #[inline]
+    pub fn encode8_with(self, linear: f32, rounding: Rounding) -> u8 {
+        f32_to_unorm_with(self.encode(linear), 8, rounding) as u8
+    }
+
+    /// Decodes an encoded [`u8`] channel in the given code value `range` into a linear `f32` one.
+    ///
+    /// Video encoded with [`Rec709`][Self::Rec709] usually has a limited range.
+    ///
+    /// # Example
+    /// ```
+    /// use acolor::{transfer::TransferFunction, yuv::Range};
+    ///
+    /// let rec709 = TransferFunction::Rec709;
+    /// assert_eq![rec709.decode8_range(16, Range::Limited), 0.];
+    /// assert_eq![rec709.decode8_range(235, Range::Limited), 1.];
+    /// assert_eq![rec709.encode8_range(1., Range::Limited), 235];
+    /// ```
+    #[inline]
+    pub fn decode8_range(self, encoded: u8, range: Range) -> f32 {
+        match range {
+            Range::Full => self.decode8(encoded),
+            Range::Limited => self.decode(range.normalize(encoded)),
+        }
+    }
+
+    /// Encodes a linear `f32` channel into a [`u8`] one in the given code value `range`.
+    #[inline]
+    pub fn encode8_range(self, linear: f32, range: Range) -> u8 {
+        range.quantize(self.encode(linear))
+    }
+
+    /// Encodes a linear `f32` channel into the [`u8`] one whose [`decode8`][Self::decode8]
+    /// value is the nearest.
+    ///
+    /// This guarantees that decoding any `u8` and encoding it back returns the same value,
+    /// at the cost of decoding several candidates, so it's slower than [`encode8`][Self::encode8].
+    pub fn encode8_exact(self, linear: f32) -> u8 {
+        // the largest value that decodes at or below `linear`
+        let (mut lo, mut hi) = (0_u8, u8::MAX);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            if self.decode8(mid) <= linear {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        if lo < u8::MAX && self.decode8(lo + 1) - linear < linear - self.decode8(lo) {
+            lo + 1
+        } else {
+            lo
+        }
+    }
+}
+
+/* pq */
+
+const PQ_M1: f32 = 2610. / 16384.;
+const PQ_M2: f32 = 2523. / 4096. * 128.;
+const PQ_C1: f32 = 3424. / 4096.;
+const PQ_C2: f32 = 2413. / 4096. * 32.;
+const PQ_C3: f32 = 2392. / 4096. * 32.;
+
+/// The peak luminance of the PQ signal range, in cd/m².
+pub const PQ_MAX_NITS: f32 = 10_000.;
+
+/// The PQ electro-optical transfer function.
+///
+/// Converts a non-linear signal in `0..=1` into display linear light,
+/// normalized so that `1.0` is [`PQ_MAX_NITS`].
+pub fn pq_eotf(encoded: f32) -> f32 {
+    let e = powf(encoded.max(0.), 1. / PQ_M2);
+    powf((e - PQ_C1).max(0.) / (PQ_C2 - PQ_C3 * e), 1. / PQ_M1)
+}
+
+/// The PQ inverse electro-optical transfer function.
+///
+/// Converts normalized display linear light into a non-linear signal in `0..=1`.
+pub fn pq_inverse_eotf(linear: f32) -> f32 {
+    let y = powf(linear.max(0.), PQ_M1);
+    powf((PQ_C1 + PQ_C2 * y) / (1. + PQ_C3 * y), PQ_M2)
+}
+
+/* hlg */
+
+const HLG_A: f32 = 0.17883277;
+const HLG_B: f32 = 0.28466892; // 1 - 4a
+const HLG_C: f32 = 0.55991073; // 0.5 - a·ln(4a)
+
+/// The nominal HLG system gamma, for a display with a peak luminance of 1000 cd/m².
+pub const HLG_SYSTEM_GAMMA: f32 = 1.2;
+
+/// The HLG opto-electronic transfer function.
+///
+/// Converts normalized scene linear light in `0..=1`
+/// into a non-linear signal in `0..=1`.
+pub fn hlg_oetf(linear: f32) -> f32 {
+    let e = linear.max(0.);
+    if e <= 1. / 12. {
+        sqrt(3. * e)
+    } else {
+        HLG_A * ln(12. * e - HLG_B) + HLG_C
+    }
+}
+
+/// The HLG inverse opto-electronic transfer function.
+///
+/// Converts a non-linear signal in `0..=1` into normalized scene linear light.
+pub fn hlg_inverse_oetf(encoded: f32) -> f32 {
+    let e = encoded.max(0.);
+    if e <= 0.5 {
+        e * e / 3.
+    } else {
+        (exp((e - HLG_C) / HLG_A) + HLG_B) / 12.
+    }
+}
+
+/// Returns the HLG system gamma for a display with the given peak luminance in cd/m².
+///
+/// It equals [`HLG_SYSTEM_GAMMA`] for a 1000 cd/m² display.
+pub fn hlg_system_gamma(peak_nits: f32) -> f32 {
+    HLG_SYSTEM_GAMMA + 0.42 * log10(peak_nits / 1000.)
+}
+
+/// The HLG opto-optical transfer function.
+///
+/// Converts normalized scene linear RGB into normalized display linear RGB,
+/// applying the system `gamma` to the BT.2020 luminance.
+pub fn hlg_ootf(scene: [f32; 3], gamma: f32) -> [f32; 3] {
+    let y = hlg_luminance(scene);
+    if y <= 0. {
+        return [0.; 3];
+    }
+    let k = powf(y, gamma - 1.);
+    [scene[0] * k, scene[1] * k, scene[2] * k]
+}
+
+/// The HLG inverse opto-optical transfer function.
+///
+/// Converts normalized display linear RGB into normalized scene linear RGB.
+pub fn hlg_inverse_ootf(display: [f32; 3], gamma: f32) -> [f32; 3] {
+    let y = hlg_luminance(display);
+    if y <= 0. {
+        return [0.; 3];
+    }
+    let k = powf(y, (1. - gamma) / gamma);
+    [display[0] * k, display[1] * k, display[2] * k]
+}
+
+/// The HLG electro-optical transfer function.
+///
+/// Converts a non-linear RGB signal into normalized display linear RGB,
+/// for the given system `gamma`.
+pub fn hlg_eotf(encoded: [f32; 3], gamma: f32) -> [f32; 3] {
+    let scene = encoded.map(hlg_inverse_oetf);
+    hlg_ootf(scene, gamma)
+}
+
+/// The HLG inverse electro-optical transfer function.
+///
+/// Converts normalized display linear RGB into a non-linear RGB signal,
+/// for the given system `gamma`.
+pub fn hlg_inverse_eotf(linear: [f32; 3], gamma: f32) -> [f32; 3] {
+    hlg_inverse_ootf(linear, gamma).map(hlg_oetf)
+}
+
+// The BT.2020 luminance.
+#[inline]
+fn hlg_luminance([r, g, b]: [f32; 3]) -> f32 {
+    0.2627 * r + 0.6780 * g + 0.0593 * b
+}
+
+/* rec709 */
+
+/// The BT.709 opto-electronic transfer function.
+///
+/// Converts scene linear light into a non-linear signal.
+pub fn rec709_oetf(linear: f32) -> f32 {
+    if linear < 0.018 {
+        4.5 * linear
+    } else {
+        1.099 * powf(linear, 0.45) - 0.099
+    }
+}
+
+/// The BT.709 inverse opto-electronic transfer function.
+///
+/// Converts a non-linear signal into scene linear light.
+pub fn rec709_inverse_oetf(encoded: f32) -> f32 {
+    if encoded < 0.081 {
+        encoded / 4.5
+    } else {
+        powf((encoded + 0.099) / 1.099, 1. / 0.45)
+    }
+}
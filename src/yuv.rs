@@ -0,0 +1,430 @@
+// acolor::yuv
+//
+//! YCbCr colors and planar YUV frames.
+//!
+//! Both the limited range of video and the full range of JPEG (JFIF) are supported,
+//! as selected by the [`Range`] of a [`YuvFormat`]. Decoding with the wrong range
+//! gives washed-out or crushed images.
+//!
+//! Converts between rows of [`Srgb8`] pixels and the 4:2:0 chroma subsampled
+//! layouts used by video codecs and cameras:
+//! - I420: a `Y` plane followed by separate `U` (Cb) and `V` (Cr) planes.
+//! - NV12: a `Y` plane followed by a single plane of interleaved `U`, `V` pairs.
+//!
+//! The planes are tightly packed. The chroma planes have half the width and height
+//! of the frame, rounded up, and each of their samples covers a block of 2×2 pixels.
+//!
+//! # Example
+//! ```
+//! use acolor::{srgb::Srgb8, yuv::{i420_to_srgb8, srgb8_to_i420, YuvFormat}};
+//!
+//! let frame = [Srgb8::new(200, 30, 60); 4 * 2];
+//! let (mut y, mut u, mut v) = ([0; 4 * 2], [0; 2], [0; 2]);
+//! srgb8_to_i420(&frame, 4, YuvFormat::BT709, [&mut y, &mut u, &mut v]);
+//! assert_eq![y[0], 75];
+//!
+//! let mut decoded = [Srgb8::default(); 4 * 2];
+//! i420_to_srgb8([&y, &u, &v], 4, YuvFormat::BT709, &mut decoded);
+//! assert![decoded[0].approx_eq(&frame[0], 2)];
+//! ```
+//!
+//! # Links
+//! - <https://en.wikipedia.org/wiki/YCbCr>
+//! - <https://learn.microsoft.com/en-us/windows/win32/medfound/recommended-8-bit-yuv-formats-for-video-rendering>
+//
+// # TOC
+//
+// - YuvMatrix
+// - Range
+//   - Srgb8 & Srgba8 range
+// - YuvFormat
+// - Ycbcr8
+// - i420
+// - nv12
+//
+
+use crate::srgb::{Srgb8, Srgba8};
+
+/// The matrix coefficients that derive luma and chroma from gamma encoded RGB.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, for standard definition video.
+    Bt601,
+    /// ITU-R BT.709, for high definition video.
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020, for ultra high definition video.
+    Bt2020,
+}
+
+impl YuvMatrix {
+    /// Returns the `(Kr, Kb)` luma weights of the red and blue components.
+    #[inline]
+    pub const fn coefficients(self) -> (f32, f32) {
+        match self {
+            YuvMatrix::Bt601 => (0.299, 0.114),
+            YuvMatrix::Bt709 => (0.2126, 0.0722),
+            YuvMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+/// The range of the 8-bit code values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Range {
+    /// The full `0..=255` range.
+    Full,
+    /// The limited range of video, `16..=235` for luma and RGB, and `16..=240` for chroma.
+    #[default]
+    Limited,
+}
+
+impl Range {
+    /// Returns the normalized value of a luma or RGB `code` value,
+    /// where the limits of the range map to `0` and `1`.
+    ///
+    /// The limited range may return values outside `0..=1`.
+    #[inline]
+    pub const fn normalize(self, code: u8) -> f32 {
+        match self {
+            Range::Full => code as f32 / 255.,
+            Range::Limited => (code as f32 - 16.) / 219.,
+        }
+    }
+
+    /// Returns the luma or RGB code value of a normalized `value`, rounded and clamped.
+    #[inline]
+    pub const fn quantize(self, value: f32) -> u8 {
+        // the cast saturates
+        match self {
+            Range::Full => (value * 255. + 0.5) as u8,
+            Range::Limited => (16. + value * 219. + 0.5) as u8,
+        }
+    }
+}
+
+// Returns the full range of a limited range luma or RGB code value, clamped.
+const fn expand_code(v: u8) -> u8 {
+    let v = (v as i32 - 16) * 255;
+    clamp_code((v + 219 / 2).div_euclid(219))
+}
+// Returns the limited range of a full range luma or RGB code value.
+const fn compress_code(v: u8) -> u8 {
+    (16 + (v as i32 * 219 + 255 / 2) / 255) as u8
+}
+// Returns the full range of a limited range chroma code value, clamped.
+const fn expand_chroma(v: u8) -> u8 {
+    let v = (v as i32 - 128) * 255;
+    clamp_code(128 + (v + 224 / 2).div_euclid(224))
+}
+// Returns the limited range of a full range chroma code value.
+const fn compress_chroma(v: u8) -> u8 {
+    let v = (v as i32 - 128) * 224;
+    (128 + (v + 255 / 2).div_euclid(255)) as u8
+}
+const fn clamp_code(v: i32) -> u8 {
+    if v < 0 {
+        0
+    } else if v > u8::MAX as i32 {
+        u8::MAX
+    } else {
+        v as u8
+    }
+}
+
+/// # Video range
+impl Srgb8 {
+    /// Returns the color with its limited range code values expanded into the full range.
+    ///
+    /// The values below `16` or above `235` are clamped.
+    #[inline]
+    pub const fn expand_range(&self) -> Srgb8 {
+        Srgb8::new(
+            expand_code(self.r),
+            expand_code(self.g),
+            expand_code(self.b),
+        )
+    }
+    /// Returns the color with its full range code values compressed into the limited range.
+    #[inline]
+    pub const fn compress_range(&self) -> Srgb8 {
+        Srgb8::new(
+            compress_code(self.r),
+            compress_code(self.g),
+            compress_code(self.b),
+        )
+    }
+}
+
+/// # Video range
+impl Srgba8 {
+    /// Returns the color with its limited range code values expanded into the full range.
+    ///
+    /// The values below `16` or above `235` are clamped. The alpha is left untouched.
+    #[inline]
+    pub const fn expand_range(&self) -> Srgba8 {
+        let Srgb8 { r, g, b } = self.to_srgb8().expand_range();
+        Srgba8::new(r, g, b, self.a)
+    }
+    /// Returns the color with its full range code values compressed into the limited range.
+    ///
+    /// The alpha is left untouched.
+    #[inline]
+    pub const fn compress_range(&self) -> Srgba8 {
+        let Srgb8 { r, g, b } = self.to_srgb8().compress_range();
+        Srgba8::new(r, g, b, self.a)
+    }
+}
+
+/// The encoding of a YUV frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct YuvFormat {
+    /// The matrix coefficients.
+    pub matrix: YuvMatrix,
+    /// The range of the code values.
+    pub range: Range,
+}
+
+impl YuvFormat {
+    /// Standard definition video: BT.601 in limited range.
+    pub const BT601: YuvFormat = YuvFormat::new(YuvMatrix::Bt601, Range::Limited);
+    /// High definition video: BT.709 in limited range.
+    pub const BT709: YuvFormat = YuvFormat::new(YuvMatrix::Bt709, Range::Limited);
+    /// Ultra high definition video: BT.2020 in limited range.
+    pub const BT2020: YuvFormat = YuvFormat::new(YuvMatrix::Bt2020, Range::Limited);
+    /// JPEG images, as specified by JFIF: BT.601 in full range.
+    pub const JFIF: YuvFormat = YuvFormat::new(YuvMatrix::Bt601, Range::Full);
+
+    /// Returns a new format.
+    #[inline]
+    pub const fn new(matrix: YuvMatrix, range: Range) -> YuvFormat {
+        YuvFormat { matrix, range }
+    }
+
+    // Returns the `(offset, scale)` of the luma and of the chroma code values.
+    const fn scales(self) -> ((f32, f32), f32) {
+        match self.range {
+            Range::Full => ((0., 255.), 255.),
+            Range::Limited => ((16., 219.), 224.),
+        }
+    }
+
+    // Returns the `[y, u, v]` code values of a color, with chroma centered at `128`.
+    fn encode(self, c: Srgb8) -> [f32; 3] {
+        let (kr, kb) = self.matrix.coefficients();
+        let [r, g, b] = [c.r, c.g, c.b].map(|c| c as f32 / 255.);
+        let y = kr * r + (1. - kr - kb) * g + kb * b;
+        let (pb, pr) = ((b - y) / (2. * (1. - kb)), (r - y) / (2. * (1. - kr)));
+        let ((offset, scale), chroma) = self.scales();
+        [offset + scale * y, 128. + chroma * pb, 128. + chroma * pr]
+    }
+
+    // Returns the color of the given code values.
+    fn decode(self, y: u8, u: u8, v: u8) -> Srgb8 {
+        let (kr, kb) = self.matrix.coefficients();
+        let ((offset, scale), chroma) = self.scales();
+        let y = (y as f32 - offset) / scale;
+        let (pb, pr) = ((u as f32 - 128.) / chroma, (v as f32 - 128.) / chroma);
+        let r = y + 2. * (1. - kr) * pr;
+        let b = y + 2. * (1. - kb) * pb;
+        let g = (y - kr * r - kb * b) / (1. - kr - kb);
+        let [r, g, b] = [r, g, b].map(|c| to_u8(c * 255.));
+        Srgb8::new(r, g, b)
+    }
+}
+
+/* Ycbcr8 */
+
+/// A color in 8-bit YCbCr code values, with the chroma centered at `128`.
+///
+/// # Example
+/// ```
+/// use acolor::{srgb::Srgb8, yuv::{Ycbcr8, YuvFormat}};
+///
+/// let white = Srgb8::new(255, 255, 255);
+/// assert_eq![Ycbcr8::from_srgb8(white, YuvFormat::JFIF), Ycbcr8::new(255, 128, 128)];
+/// assert_eq![Ycbcr8::from_srgb8(white, YuvFormat::BT601), Ycbcr8::new(235, 128, 128)];
+/// assert_eq![Ycbcr8::new(235, 128, 128).to_srgb8(YuvFormat::BT709), white];
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Ycbcr8 {
+    /// Luma.
+    pub y: u8,
+    /// Blue-difference chroma.
+    pub cb: u8,
+    /// Red-difference chroma.
+    pub cr: u8,
+}
+
+impl Ycbcr8 {
+    /// Returns a new color from its code values.
+    #[inline]
+    pub const fn new(y: u8, cb: u8, cr: u8) -> Ycbcr8 {
+        Ycbcr8 { y, cb, cr }
+    }
+
+    /// Returns the code values of an sRGB color in the given `format`.
+    #[inline]
+    pub fn from_srgb8(c: Srgb8, format: YuvFormat) -> Ycbcr8 {
+        let [y, cb, cr] = format.encode(c).map(to_u8);
+        Ycbcr8 { y, cb, cr }
+    }
+    /// Returns the sRGB color of the code values in the given `format`.
+    #[inline]
+    pub fn to_srgb8(&self, format: YuvFormat) -> Srgb8 {
+        format.decode(self.y, self.cb, self.cr)
+    }
+
+    /// Returns the color with its limited range code values expanded into the full range.
+    ///
+    /// The luma values out of `16..=235` and the chroma values out of `16..=240` are clamped.
+    #[inline]
+    pub const fn expand_range(&self) -> Ycbcr8 {
+        Ycbcr8::new(
+            expand_code(self.y),
+            expand_chroma(self.cb),
+            expand_chroma(self.cr),
+        )
+    }
+    /// Returns the color with its full range code values compressed into the limited range.
+    #[inline]
+    pub const fn compress_range(&self) -> Ycbcr8 {
+        Ycbcr8::new(
+            compress_code(self.y),
+            compress_chroma(self.cb),
+            compress_chroma(self.cr),
+        )
+    }
+}
+
+// Rounds and clamps a code value.
+#[inline]
+fn to_u8(v: f32) -> u8 {
+    (v + 0.5).clamp(0., 255.) as u8
+}
+
+// Returns the height of the frame and the width and height of its chroma planes,
+// checking the frame dimensions.
+fn chroma_dimensions(pixels: usize, width: usize) -> (usize, usize, usize) {
+    assert![width > 0, "the width must not be 0"];
+    assert![
+        pixels % width == 0,
+        "the number of pixels is not a multiple of the width"
+    ];
+    let height = pixels / width;
+    (height, width.div_ceil(2), height.div_ceil(2))
+}
+
+// Writes the luma of each pixel, and passes the index and the average `u`, `v`
+// code values of each 2×2 block of pixels to `chroma`.
+fn downsample(
+    src: &[Srgb8],
+    width: usize,
+    format: YuvFormat,
+    luma: &mut [u8],
+    mut chroma: impl FnMut(usize, u8, u8),
+) {
+    let (height, cw, ch) = chroma_dimensions(src.len(), width);
+    assert![
+        luma.len() == src.len(),
+        "the luma plane length differs from the number of pixels"
+    ];
+    for cy in 0..ch {
+        for cx in 0..cw {
+            let (mut sum, mut n) = ([0.; 2], 0.);
+            for y in 2 * cy..(2 * cy + 2).min(height) {
+                for x in 2 * cx..(2 * cx + 2).min(width) {
+                    let [l, u, v] = format.encode(src[y * width + x]);
+                    luma[y * width + x] = to_u8(l);
+                    sum = [sum[0] + u, sum[1] + v];
+                    n += 1.;
+                }
+            }
+            chroma(cy * cw + cx, to_u8(sum[0] / n), to_u8(sum[1] / n));
+        }
+    }
+}
+
+/* i420 */
+
+/// Decodes an I420 frame from its `[y, u, v]` `planes` into rows of `width` pixels.
+///
+/// Each chroma sample is replicated over its 2×2 block of pixels.
+///
+/// # Panics
+/// Panics if `width` is `0`, if the length of `dst` is not a multiple of it,
+/// or if any plane length differs from the frame dimensions.
+pub fn i420_to_srgb8(planes: [&[u8]; 3], width: usize, format: YuvFormat, dst: &mut [Srgb8]) {
+    let (height, cw, ch) = chroma_dimensions(dst.len(), width);
+    let [y, u, v] = planes;
+    assert![
+        y.len() == width * height && u.len() == cw * ch && v.len() == cw * ch,
+        "plane lengths differ from the frame dimensions"
+    ];
+    for (i, px) in dst.iter_mut().enumerate() {
+        let c = (i / width / 2) * cw + (i % width) / 2;
+        *px = format.decode(y[i], u[c], v[c]);
+    }
+}
+
+/// Encodes rows of `width` pixels into the `[y, u, v]` `planes` of an I420 frame.
+///
+/// Each chroma sample is the average of its 2×2 block of pixels.
+///
+/// # Panics
+/// Panics if `width` is `0`, if the length of `src` is not a multiple of it,
+/// or if any plane length differs from the frame dimensions.
+pub fn srgb8_to_i420(src: &[Srgb8], width: usize, format: YuvFormat, planes: [&mut [u8]; 3]) {
+    let (_, cw, ch) = chroma_dimensions(src.len(), width);
+    let [y, u, v] = planes;
+    assert![
+        u.len() == cw * ch && v.len() == cw * ch,
+        "plane lengths differ from the frame dimensions"
+    ];
+    downsample(src, width, format, y, |i, cb, cr| {
+        u[i] = cb;
+        v[i] = cr;
+    });
+}
+
+/* nv12 */
+
+/// Decodes an NV12 frame from its `y` plane and interleaved `uv` plane
+/// into rows of `width` pixels.
+///
+/// Each chroma sample is replicated over its 2×2 block of pixels.
+///
+/// # Panics
+/// Panics if `width` is `0`, if the length of `dst` is not a multiple of it,
+/// or if any plane length differs from the frame dimensions.
+pub fn nv12_to_srgb8(y: &[u8], uv: &[u8], width: usize, format: YuvFormat, dst: &mut [Srgb8]) {
+    let (height, cw, ch) = chroma_dimensions(dst.len(), width);
+    assert![
+        y.len() == width * height && uv.len() == 2 * cw * ch,
+        "plane lengths differ from the frame dimensions"
+    ];
+    for (i, px) in dst.iter_mut().enumerate() {
+        let c = (i / width / 2) * cw + (i % width) / 2;
+        *px = format.decode(y[i], uv[2 * c], uv[2 * c + 1]);
+    }
+}
+
+/// Encodes rows of `width` pixels into the `y` plane and interleaved `uv` plane
+/// of an NV12 frame.
+///
+/// Each chroma sample is the average of its 2×2 block of pixels.
+///
+/// # Panics
+/// Panics if `width` is `0`, if the length of `src` is not a multiple of it,
+/// or if any plane length differs from the frame dimensions.
+pub fn srgb8_to_nv12(src: &[Srgb8], width: usize, format: YuvFormat, y: &mut [u8], uv: &mut [u8]) {
+    let (_, cw, ch) = chroma_dimensions(src.len(), width);
+    assert![
+        uv.len() == 2 * cw * ch,
+        "plane lengths differ from the frame dimensions"
+    ];
+    downsample(src, width, format, y, |i, cb, cr| {
+        uv[2 * i] = cb;
+        uv[2 * i + 1] = cr;
+    });
+}